@@ -1,4 +1,4 @@
-use hpo::Ontology;
+use hpo::{HpoTermId, Ontology};
 use std::io::Read;
 use std::{fs::File, time::Duration};
 
@@ -19,9 +19,36 @@ fn build_ontology_benchmark(c: &mut Criterion) {
     });
 }
 
+fn retrieve_terms_benchmark(c: &mut Criterion) {
+    let ontology = Ontology::from_binary("tests/ontology.hpo").unwrap();
+    let ids: Vec<HpoTermId> = ontology.into_iter().map(|term| term.id()).collect();
+    let missing: Vec<HpoTermId> = (0..ids.len())
+        .map(|n| HpoTermId::from(u32::try_from(n).unwrap() * 397 + 5_300_000))
+        .collect();
+
+    c.bench_function("retrieve existing terms", |b| {
+        b.iter(|| {
+            ids.iter()
+                .filter(|id| ontology.hpo(black_box(**id)).is_some())
+                .count()
+        })
+    });
+    c.bench_function("retrieve missing terms", |b| {
+        b.iter(|| {
+            missing
+                .iter()
+                .filter(|id| ontology.hpo(black_box(**id)).is_some())
+                .count()
+        })
+    });
+    c.bench_function("clone ontology", |b| {
+        b.iter(|| black_box(&ontology).clone().len())
+    });
+}
+
 criterion_group! {
     name = ontology;
     config = Criterion::default().sample_size(20).measurement_time(Duration::from_secs(10));
-    targets = build_ontology_benchmark
+    targets = build_ontology_benchmark, retrieve_terms_benchmark
 }
 criterion_main!(ontology);
@@ -16,10 +16,16 @@ use core::hash::Hash;
 pub use gene::{Gene, GeneId, GeneIterator, Genes};
 use std::fmt::Display;
 
+mod decipher_disease;
 mod disease;
+mod disease_annotation;
 mod omim_disease;
 mod orpha_disease;
+pub use decipher_disease::{
+    DecipherDisease, DecipherDiseaseId, DecipherDiseaseIterator, DecipherDiseases,
+};
 pub use disease::Disease;
+pub use disease_annotation::{Aspect, DiseaseAnnotation, EvidenceCode, Sex};
 pub use omim_disease::{
     OmimDisease, OmimDiseaseFilter, OmimDiseaseId, OmimDiseaseIterator, OmimDiseases,
 };
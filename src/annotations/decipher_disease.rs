@@ -0,0 +1,218 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+use crate::annotations::disease::DiseaseIterator;
+use crate::annotations::{AnnotationId, Disease, DiseaseAnnotation, GeneId, Genes};
+use crate::term::HpoGroup;
+use crate::HpoError;
+use crate::HpoTermId;
+
+/// A set of DECIPHER diseases
+///
+/// The set does not contain [`DecipherDisease`]s itself, but only
+/// their [`DecipherDiseaseId`]s.
+/// Currently implemented using [`HashSet`] but any other implementation
+/// should work as well given that each [`DecipherDiseaseId`] must appear only once
+/// and it provides an iterator of [`DecipherDiseaseId`]
+pub type DecipherDiseases = HashSet<DecipherDiseaseId>;
+
+/// A unique identifier for a [`DecipherDisease`]
+///
+/// This value can - in theory - represent any numerical unique value.
+/// When using the default JAX provided masterdata, it represents
+/// the actual DECIPHER syndrome ID.
+#[derive(Clone, Copy, Default, Debug, Hash, PartialEq, PartialOrd, Eq, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct DecipherDiseaseId {
+    inner: u32,
+}
+
+impl AnnotationId for DecipherDiseaseId {
+    /// Convert `self` to `u32`
+    fn as_u32(&self) -> u32 {
+        self.inner
+    }
+}
+
+impl TryFrom<&str> for DecipherDiseaseId {
+    type Error = HpoError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(DecipherDiseaseId {
+            inner: value.parse::<u32>()?,
+        })
+    }
+}
+
+impl From<u32> for DecipherDiseaseId {
+    fn from(inner: u32) -> Self {
+        DecipherDiseaseId { inner }
+    }
+}
+
+impl Display for DecipherDiseaseId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DECIPHER:{}", self.inner)
+    }
+}
+
+/// A single DECIPHER disease
+///
+/// A disease has a unique [`DecipherDiseaseId`] and a name and is
+/// connected to a set of HPO terms
+#[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecipherDisease {
+    id: DecipherDiseaseId,
+    name: String,
+    hpos: HpoGroup,
+    frequencies: HashMap<HpoTermId, f32>,
+    annotations: HashMap<HpoTermId, DiseaseAnnotation>,
+    excluded: HpoGroup,
+    genes: Genes,
+}
+
+impl Disease for DecipherDisease {
+    type AnnoID = DecipherDiseaseId;
+
+    /// Initializes a new DECIPHER disease
+    ///
+    /// This method should rarely, if ever, be used directly. The
+    /// preferred way to create new diseases is through [`Builder::annotate_decipher_disease`](`crate::builder::Builder::annotate_decipher_disease`)
+    /// to ensure that each disease exists only once.
+    fn new(id: Self::AnnoID, name: &str) -> DecipherDisease {
+        Self {
+            name: name.to_string(),
+            id,
+            hpos: HpoGroup::default(),
+            frequencies: HashMap::new(),
+            annotations: HashMap::new(),
+            excluded: HpoGroup::default(),
+            genes: Genes::default(),
+        }
+    }
+
+    /// The unique [`DecipherDiseaseId`] of the disease, the DECIPHER syndrome ID
+    fn id(&self) -> &Self::AnnoID {
+        &self.id
+    }
+
+    /// The DECIPHER disease name
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Connect another [HPO term](`crate::HpoTerm`) to the disease
+    fn add_term<I: Into<HpoTermId>>(&mut self, term_id: I) -> bool {
+        self.hpos.insert(term_id)
+    }
+
+    /// The set of connected HPO terms
+    fn hpo_terms(&self) -> &HpoGroup {
+        &self.hpos
+    }
+
+    /// The frequencies of all connected HPO terms with a known frequency
+    fn term_frequencies(&self) -> &HashMap<HpoTermId, f32> {
+        &self.frequencies
+    }
+
+    /// Sets the frequency of a connected HPO term
+    fn set_term_frequency<I: Into<HpoTermId>>(&mut self, term_id: I, frequency: f32) {
+        self.frequencies.insert(term_id.into(), frequency);
+    }
+
+    /// The details of all connected HPO terms with known details
+    fn annotations(&self) -> &HashMap<HpoTermId, DiseaseAnnotation> {
+        &self.annotations
+    }
+
+    /// Adds or merges the details of a connected HPO term
+    fn add_annotation(&mut self, annotation: DiseaseAnnotation) {
+        match self.annotations.entry(annotation.term_id()) {
+            Entry::Occupied(mut entry) => entry.get_mut().merge(annotation),
+            Entry::Vacant(entry) => {
+                entry.insert(annotation);
+            }
+        }
+    }
+
+    /// The set of HPO terms that are explicitly not present
+    fn excluded_terms(&self) -> &HpoGroup {
+        &self.excluded
+    }
+
+    /// Records an HPO term that is explicitly not present
+    fn add_excluded_term<I: Into<HpoTermId>>(&mut self, term_id: I) -> bool {
+        self.excluded.insert(term_id)
+    }
+
+    /// The set of directly associated genes
+    fn genes(&self) -> &Genes {
+        &self.genes
+    }
+
+    /// Associates the disease with a gene
+    fn add_gene(&mut self, gene_id: GeneId) -> bool {
+        self.genes.insert(gene_id)
+    }
+}
+
+impl PartialEq for DecipherDisease {
+    fn eq(&self, other: &DecipherDisease) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for DecipherDisease {}
+
+impl TryFrom<&[u8]> for DecipherDisease {
+    type Error = HpoError;
+    /// Returns a [`DecipherDisease`] from a bytes vector
+    ///
+    /// The byte layout for this method is defined in
+    /// [`Disease::as_bytes`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::annotations::{Disease, DecipherDisease, DecipherDiseaseId};
+    ///
+    /// let bytes = vec![
+    ///     0u8, 0u8, 0u8, 22u8, // Total size of Blop
+    ///     0u8, 0u8, 0u8, 123u8, // ID of the disease => 123
+    ///     0u8, 0u8, 0u8, 6u8, // Length of name => 6
+    ///     b'F', b'o', b'o', b'b', b'a', b'r', // Foobar
+    ///     0u8, 0u8, 0u8, 0u8  // Number of associated HPO Terms => 0
+    /// ];
+    /// let disease = DecipherDisease::try_from(&bytes[..]).unwrap();
+    ///
+    /// assert_eq!(disease.name(), "Foobar");
+    /// assert_eq!(disease.id(), &DecipherDiseaseId::from(123u32));
+    /// ```
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl Hash for DecipherDisease {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Iterates [`DecipherDisease`]
+pub type DecipherDiseaseIterator<'a> = DiseaseIterator<'a, DecipherDiseaseId>;
+
+impl<'a> std::iter::Iterator for DiseaseIterator<'a, DecipherDiseaseId> {
+    type Item = &'a DecipherDisease;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.diseases.next().map(|decipher_id| {
+            self.ontology
+                .decipher_disease(decipher_id)
+                .expect("disease must exist in Ontology")
+        })
+    }
+}
@@ -1,9 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::sync::OnceLock;
 use tracing::error;
 
-use crate::annotations::AnnotationId;
+use crate::annotations::{AnnotationId, DiseaseAnnotation, GeneId, Genes};
 use crate::term::HpoGroup;
 use crate::u32_from_bytes;
 use crate::{HpoError, HpoSet, HpoTermId, Ontology};
@@ -29,6 +30,116 @@ pub trait Disease: PartialEq + Eq + Hash + Sized {
     /// Returns a reference to the associated [group of HPO terms](`crate::term::HpoGroup`)
     fn hpo_terms(&self) -> &HpoGroup;
 
+    /// Returns the frequency of the [HPO term](`crate::HpoTerm`) in patients
+    /// with the disease, normalised to `0.0..=1.0`
+    ///
+    /// Returns `None` if the frequency is not known or the term
+    /// is not associated to the disease.
+    fn term_frequency<I: Into<HpoTermId>>(&self, term_id: I) -> Option<f32> {
+        self.term_frequencies().get(&term_id.into()).copied()
+    }
+
+    /// Returns the frequencies of all [HPO terms](`crate::HpoTerm`)
+    /// with a known frequency
+    ///
+    /// The default implementation returns an empty map
+    fn term_frequencies(&self) -> &HashMap<HpoTermId, f32> {
+        static EMPTY: OnceLock<HashMap<HpoTermId, f32>> = OnceLock::new();
+        EMPTY.get_or_init(HashMap::new)
+    }
+
+    /// Sets the frequency of an [HPO term](`crate::HpoTerm`) in patients with the disease
+    ///
+    /// The frequency must be normalised to `0.0..=1.0`
+    ///
+    /// # Note
+    ///
+    /// This method does **not** connect the term to the disease.
+    /// Clients should not use this method, unless they are creating their own Ontology.
+    ///
+    /// The default implementation ignores the frequency.
+    fn set_term_frequency<I: Into<HpoTermId>>(&mut self, _term_id: I, _frequency: f32) {}
+
+    /// Returns the [`DiseaseAnnotation`] with the details of the association
+    /// to the [HPO term](`crate::HpoTerm`)
+    ///
+    /// Returns `None` if no details are known or the term is not
+    /// associated to the disease.
+    fn annotation<I: Into<HpoTermId>>(&self, term_id: I) -> Option<&DiseaseAnnotation> {
+        self.annotations().get(&term_id.into())
+    }
+
+    /// Returns the [`DiseaseAnnotation`]s of all associated [HPO terms](`crate::HpoTerm`)
+    /// with known details
+    ///
+    /// The default implementation returns an empty map
+    fn annotations(&self) -> &HashMap<HpoTermId, DiseaseAnnotation> {
+        static EMPTY: OnceLock<HashMap<HpoTermId, DiseaseAnnotation>> = OnceLock::new();
+        EMPTY.get_or_init(HashMap::new)
+    }
+
+    /// Adds the details of the association to an [HPO term](`crate::HpoTerm`)
+    ///
+    /// If the term already has an annotation, both are merged.
+    ///
+    /// # Note
+    ///
+    /// This method does **not** connect the term to the disease.
+    /// Clients should not use this method, unless they are creating their own Ontology.
+    ///
+    /// The default implementation ignores the annotation.
+    fn add_annotation(&mut self, _annotation: DiseaseAnnotation) {}
+
+    /// Returns the [group of HPO terms](`crate::term::HpoGroup`) that are
+    /// explicitly **not** present in patients with the disease
+    ///
+    /// These terms come from negated (`NOT`) annotations, e.g. `Pseudohyperkalemia`
+    /// explicitly lacks `HP:0001878 | Hemolytic anemia`. They can be used to penalise
+    /// diseases in a differential diagnosis if a patient shows one of these terms.
+    ///
+    /// The default implementation returns an empty group
+    fn excluded_terms(&self) -> &HpoGroup {
+        static EMPTY: OnceLock<HpoGroup> = OnceLock::new();
+        EMPTY.get_or_init(HpoGroup::new)
+    }
+
+    /// Records an [HPO term](`crate::HpoTerm`) that is explicitly **not**
+    /// present in patients with the disease
+    ///
+    /// # Note
+    ///
+    /// Clients should not use this method, unless they are creating their own Ontology.
+    ///
+    /// The default implementation ignores the term and returns `false`.
+    fn add_excluded_term<I: Into<HpoTermId>>(&mut self, _term_id: I) -> bool {
+        false
+    }
+
+    /// Returns the set of [`GeneId`]s of all genes that are directly
+    /// associated with the disease
+    ///
+    /// The association is based on the gene annotation data, e.g.
+    /// `genes_to_disease.txt`, see [`Gene::omim_diseases`](`crate::annotations::Gene::omim_diseases`).
+    /// It does not depend on shared HPO terms.
+    ///
+    /// The default implementation returns an empty set
+    fn genes(&self) -> &Genes {
+        static EMPTY: OnceLock<Genes> = OnceLock::new();
+        EMPTY.get_or_init(Genes::default)
+    }
+
+    /// Associates the disease with a [`Gene`](`crate::annotations::Gene`)
+    ///
+    /// # Note
+    ///
+    /// This method does **not** add the disease to the gene.
+    /// Clients should not use this method, unless they are creating their own Ontology.
+    ///
+    /// The default implementation ignores the gene and returns `false`.
+    fn add_gene(&mut self, _gene_id: GeneId) -> bool {
+        false
+    }
+
     /// Creates a new `crate::set::HpoSet`
     fn to_hpo_set<'a>(&self, ontology: &'a Ontology) -> HpoSet<'a> {
         HpoSet::new(ontology, self.hpo_terms().clone())
@@ -47,6 +158,17 @@ pub trait Disease: PartialEq + Eq + Hash + Sized {
     /// | 12 + n | 4 | The number of associated HPO terms as big-endian `u32` |
     /// | 16 + n | x * 4 | The [`HpoTermId`]s of the associated terms, each encoded as big-endian `u32` |
     ///
+    /// Followed by the annotation details, only if the disease has any:
+    ///
+    /// | Byte offset | Number of bytes | Description |
+    /// | --- | --- | --- |
+    /// | 16 + n + x * 4 | 4 | The number of term frequencies as big-endian `u32` |
+    /// | 20 + n + x * 4 | f * 8 | The [`HpoTermId`] as big-endian `u32` and its frequency as big-endian `f32` |
+    /// | 20 + n + x * 4 + f * 8 | 4 | The number of [`DiseaseAnnotation`]s as big-endian `u32` |
+    /// | 24 + n + x * 4 + f * 8 | ... | The [`DiseaseAnnotation`]s |
+    /// | ... | 4 | The number of excluded terms as big-endian `u32` |
+    /// | ... | e * 4 | The [`HpoTermId`]s of the excluded terms, each encoded as big-endian `u32` |
+    ///
     /// # Examples
     ///
     /// ```
@@ -65,7 +187,8 @@ pub trait Disease: PartialEq + Eq + Hash + Sized {
         }
         let name = self.name().as_bytes();
         let name_length = name.len();
-        let size = 4 + 4 + 4 + name_length + 4 + self.hpo_terms().len() * 4;
+        let mut details = details_as_bytes(self);
+        let size = 4 + 4 + 4 + name_length + 4 + self.hpo_terms().len() * 4 + details.len();
 
         let mut res = Vec::new();
 
@@ -89,6 +212,9 @@ pub trait Disease: PartialEq + Eq + Hash + Sized {
         // HPO terms
         res.append(&mut self.hpo_terms().as_bytes());
 
+        // Annotation details
+        res.append(&mut details);
+
         res
     }
 
@@ -183,6 +309,10 @@ pub trait Disease: PartialEq + Eq + Hash + Sized {
             disease.add_term(term_id);
         }
 
+        if idx_terms < total_len {
+            idx_terms += details_from_bytes(&mut disease, &bytes[idx_terms..])?;
+        }
+
         if idx_terms == total_len && idx_terms == bytes.len() {
             Ok(disease)
         } else {
@@ -195,6 +325,111 @@ pub trait Disease: PartialEq + Eq + Hash + Sized {
     }
 }
 
+/// Returns the binary representation of the annotation details of a [`Disease`]
+///
+/// The details are only added if the disease has any,
+/// see [`Disease::as_bytes`] for the layout.
+pub(crate) fn details_as_bytes<D: Disease>(disease: &D) -> Vec<u8> {
+    let frequencies = disease.term_frequencies();
+    let annotations = disease.annotations();
+    let excluded = disease.excluded_terms();
+    if frequencies.is_empty() && annotations.is_empty() && excluded.is_empty() {
+        return Vec::new();
+    }
+    let mut res = Vec::with_capacity(4 + frequencies.len() * 8 + 4 + annotations.len() * 19);
+
+    let mut frequencies: Vec<(&HpoTermId, &f32)> = frequencies.iter().collect();
+    frequencies.sort_by_key(|(term_id, _)| **term_id);
+    res.extend_from_slice(
+        &u32::try_from(frequencies.len())
+            .expect("unable to convert number of frequencies to u32")
+            .to_be_bytes(),
+    );
+    for (term_id, frequency) in frequencies {
+        res.extend_from_slice(&term_id.to_be_bytes());
+        res.extend_from_slice(&frequency.to_be_bytes());
+    }
+
+    let mut annotations: Vec<&DiseaseAnnotation> = annotations.values().collect();
+    annotations.sort_by_key(|annotation| annotation.term_id());
+    res.extend_from_slice(
+        &u32::try_from(annotations.len())
+            .expect("unable to convert number of annotations to u32")
+            .to_be_bytes(),
+    );
+    for annotation in annotations {
+        res.append(&mut annotation.as_bytes());
+    }
+
+    res.extend_from_slice(
+        &u32::try_from(excluded.len())
+            .expect("unable to convert number of excluded terms to u32")
+            .to_be_bytes(),
+    );
+    res.append(&mut excluded.as_bytes());
+    res
+}
+
+/// Parses the annotation details of a [`Disease`] and adds them to the disease
+///
+/// Returns the number of parsed bytes
+fn details_from_bytes<D: Disease>(disease: &mut D, bytes: &[u8]) -> Result<usize, HpoError> {
+    if bytes.len() < 4 {
+        error!("Too few bytes for the annotation details of a Disease");
+        return Err(HpoError::ParseBinaryError);
+    }
+    let n_frequencies = u32_from_bytes(bytes) as usize;
+    let mut idx = 4;
+    if bytes.len() < idx + n_frequencies * 8 {
+        error!("Too few bytes for {} term frequencies", n_frequencies);
+        return Err(HpoError::ParseBinaryError);
+    }
+    for _ in 0..n_frequencies {
+        let term_id = HpoTermId::from([bytes[idx], bytes[idx + 1], bytes[idx + 2], bytes[idx + 3]]);
+        let frequency = f32::from_be_bytes([
+            bytes[idx + 4],
+            bytes[idx + 5],
+            bytes[idx + 6],
+            bytes[idx + 7],
+        ]);
+        disease.set_term_frequency(term_id, frequency);
+        idx += 8;
+    }
+
+    if bytes.len() < idx + 4 {
+        error!("Too few bytes for the annotations of a Disease");
+        return Err(HpoError::ParseBinaryError);
+    }
+    let n_annotations = u32_from_bytes(&bytes[idx..]);
+    idx += 4;
+    for _ in 0..n_annotations {
+        let (annotation, len) = DiseaseAnnotation::from_bytes(&bytes[idx..])?;
+        disease.add_annotation(annotation);
+        idx += len;
+    }
+
+    if bytes.len() < idx + 4 {
+        error!("Too few bytes for the excluded terms of a Disease");
+        return Err(HpoError::ParseBinaryError);
+    }
+    let n_excluded = u32_from_bytes(&bytes[idx..]) as usize;
+    idx += 4;
+    if bytes.len() < idx + n_excluded * 4 {
+        error!("Too few bytes for {} excluded terms", n_excluded);
+        return Err(HpoError::ParseBinaryError);
+    }
+    for _ in 0..n_excluded {
+        disease.add_excluded_term(HpoTermId::from([
+            bytes[idx],
+            bytes[idx + 1],
+            bytes[idx + 2],
+            bytes[idx + 3],
+        ]));
+        idx += 4;
+    }
+    Ok(idx)
+}
+
 /// [`Disease`] Iterator
 pub struct DiseaseIterator<'a, DID> {
     pub(crate) ontology: &'a Ontology,
@@ -219,3 +454,77 @@ impl<DID> Debug for DiseaseIterator<'_, DID> {
         write!(f, "DiseaseIterator")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::annotations::OmimDiseaseId;
+
+    /// A minimal disease that only implements the required methods
+    struct MinimalDisease {
+        id: OmimDiseaseId,
+        name: String,
+        hpos: HpoGroup,
+    }
+
+    impl PartialEq for MinimalDisease {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for MinimalDisease {}
+
+    impl Hash for MinimalDisease {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state);
+        }
+    }
+
+    impl Disease for MinimalDisease {
+        type AnnoID = OmimDiseaseId;
+
+        fn new(id: Self::AnnoID, name: &str) -> Self {
+            Self {
+                id,
+                name: name.to_string(),
+                hpos: HpoGroup::new(),
+            }
+        }
+
+        fn id(&self) -> &Self::AnnoID {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn add_term<I: Into<HpoTermId>>(&mut self, term_id: I) -> bool {
+            self.hpos.insert(term_id)
+        }
+
+        fn hpo_terms(&self) -> &HpoGroup {
+            &self.hpos
+        }
+    }
+
+    #[test]
+    fn default_methods() {
+        let mut disease = MinimalDisease::new(1u32.into(), "Minimal");
+        disease.add_term(5u32);
+        disease.set_term_frequency(5u32, 0.5);
+        assert!(disease.term_frequencies().is_empty());
+        assert_eq!(disease.term_frequency(5u32), None);
+
+        disease.add_annotation(DiseaseAnnotation::new(5u32.into()));
+        assert!(disease.annotations().is_empty());
+        assert!(disease.annotation(5u32).is_none());
+
+        assert!(!disease.add_excluded_term(6u32));
+        assert!(disease.excluded_terms().is_empty());
+
+        assert!(!disease.add_gene(GeneId::from(1u32)));
+        assert!(disease.genes().is_empty());
+    }
+}
@@ -0,0 +1,511 @@
+use std::fmt::Display;
+
+use tracing::error;
+
+use crate::annotations::AnnotationId;
+use crate::{u32_from_bytes, HpoError, HpoResult, HpoTermId};
+
+/// The evidence code of a [`DiseaseAnnotation`]
+///
+/// It describes how the association between a disease and an
+/// [`HpoTerm`](`crate::HpoTerm`) was established.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EvidenceCode {
+    /// Inferred from electronic annotation (`IEA`), e.g. parsed from OMIM
+    Iea,
+    /// Traceable author statement (`TAS`), e.g. from review articles
+    Tas,
+    /// Published clinical study (`PCS`)
+    Pcs,
+}
+
+impl EvidenceCode {
+    /// Returns the code as used in `phenotype.hpoa`, e.g. `PCS`
+    pub fn as_str(&self) -> &str {
+        match self {
+            EvidenceCode::Iea => "IEA",
+            EvidenceCode::Tas => "TAS",
+            EvidenceCode::Pcs => "PCS",
+        }
+    }
+}
+
+impl TryFrom<&str> for EvidenceCode {
+    type Error = HpoError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "IEA" => Ok(EvidenceCode::Iea),
+            "TAS" => Ok(EvidenceCode::Tas),
+            "PCS" => Ok(EvidenceCode::Pcs),
+            _ => Err(HpoError::InvalidInput(format!(
+                "invalid evidence code: {value}"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<u8> for EvidenceCode {
+    type Error = HpoError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(EvidenceCode::Iea),
+            2 => Ok(EvidenceCode::Tas),
+            3 => Ok(EvidenceCode::Pcs),
+            _ => Err(HpoError::ParseBinaryError),
+        }
+    }
+}
+
+impl From<EvidenceCode> for u8 {
+    fn from(value: EvidenceCode) -> Self {
+        match value {
+            EvidenceCode::Iea => 1,
+            EvidenceCode::Tas => 2,
+            EvidenceCode::Pcs => 3,
+        }
+    }
+}
+
+impl Display for EvidenceCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The aspect of a [`DiseaseAnnotation`], i.e. the sub-ontology
+/// of the annotated [`HpoTerm`](`crate::HpoTerm`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Aspect {
+    /// Phenotypic abnormality (`P`)
+    Phenotype,
+    /// Mode of inheritance (`I`)
+    Inheritance,
+    /// Clinical course (`C`)
+    ClinicalCourse,
+    /// Clinical modifier (`M`)
+    ClinicalModifier,
+    /// Past medical history (`H`)
+    PastMedicalHistory,
+}
+
+impl Aspect {
+    /// Returns the aspect as used in `phenotype.hpoa`, e.g. `P`
+    pub fn as_char(&self) -> char {
+        match self {
+            Aspect::Phenotype => 'P',
+            Aspect::Inheritance => 'I',
+            Aspect::ClinicalCourse => 'C',
+            Aspect::ClinicalModifier => 'M',
+            Aspect::PastMedicalHistory => 'H',
+        }
+    }
+}
+
+impl TryFrom<char> for Aspect {
+    type Error = HpoError;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'P' => Ok(Aspect::Phenotype),
+            'I' => Ok(Aspect::Inheritance),
+            'C' => Ok(Aspect::ClinicalCourse),
+            'M' => Ok(Aspect::ClinicalModifier),
+            'H' => Ok(Aspect::PastMedicalHistory),
+            _ => Err(HpoError::InvalidInput(format!("invalid aspect: {value}"))),
+        }
+    }
+}
+
+impl TryFrom<&str> for Aspect {
+    type Error = HpoError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(aspect), None) => Aspect::try_from(aspect),
+            _ => Err(HpoError::InvalidInput(format!("invalid aspect: {value}"))),
+        }
+    }
+}
+
+impl Display for Aspect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+/// The sex of patients that a [`DiseaseAnnotation`] is specific to
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Sex {
+    /// Only male patients
+    Male,
+    /// Only female patients
+    Female,
+}
+
+impl TryFrom<&str> for Sex {
+    type Error = HpoError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.eq_ignore_ascii_case("male") {
+            Ok(Sex::Male)
+        } else if value.eq_ignore_ascii_case("female") {
+            Ok(Sex::Female)
+        } else {
+            Err(HpoError::InvalidInput(format!("invalid sex: {value}")))
+        }
+    }
+}
+
+impl Display for Sex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Sex::Male => write!(f, "male"),
+            Sex::Female => write!(f, "female"),
+        }
+    }
+}
+
+/// The details of the association between a disease and an [`HpoTerm`](`crate::HpoTerm`)
+///
+/// The details are provided by the `phenotype.hpoa` file and contain the
+/// evidence, onset, sex, modifiers, aspect and biocuration of the association.
+/// The frequency of the term is available via
+/// [`Disease::term_frequency`](`crate::annotations::Disease::term_frequency`).
+///
+/// If a disease is annotated to the same term multiple times, e.g. from different
+/// publications, all annotations are merged into a single `DiseaseAnnotation`.
+/// The onset and sex are only kept if all annotations agree on them.
+///
+/// # Examples
+///
+/// ```
+/// use hpo::HpoTermId;
+/// use hpo::annotations::{Aspect, DiseaseAnnotation, EvidenceCode};
+///
+/// let annotation = DiseaseAnnotation::new(HpoTermId::from(11097u32))
+///     .with_evidence(EvidenceCode::Pcs)
+///     .with_aspect(Aspect::Phenotype)
+///     .with_biocuration("HPO:probinson[2021-06-21]");
+///
+/// assert_eq!(annotation.evidence(), Some(EvidenceCode::Pcs));
+/// assert_eq!(annotation.aspect(), Some(Aspect::Phenotype));
+/// assert_eq!(annotation.onset(), None);
+/// assert_eq!(annotation.biocuration(), ["HPO:probinson[2021-06-21]"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiseaseAnnotation {
+    term_id: HpoTermId,
+    evidence: Option<EvidenceCode>,
+    onset: Option<HpoTermId>,
+    sex: Option<Sex>,
+    modifiers: Vec<HpoTermId>,
+    aspect: Option<Aspect>,
+    biocuration: Vec<String>,
+}
+
+impl DiseaseAnnotation {
+    /// Constructs a new [`DiseaseAnnotation`] of the term without any details
+    pub fn new(term_id: HpoTermId) -> Self {
+        Self {
+            term_id,
+            evidence: None,
+            onset: None,
+            sex: None,
+            modifiers: Vec::new(),
+            aspect: None,
+            biocuration: Vec::new(),
+        }
+    }
+
+    /// Sets the [`EvidenceCode`]
+    #[must_use]
+    pub fn with_evidence(mut self, evidence: EvidenceCode) -> Self {
+        self.evidence = Some(evidence);
+        self
+    }
+
+    /// Sets the onset, e.g. `HP:0003577 | Congenital onset`
+    #[must_use]
+    pub fn with_onset(mut self, onset: HpoTermId) -> Self {
+        self.onset = Some(onset);
+        self
+    }
+
+    /// Sets the [`Sex`] that the annotation is specific to
+    #[must_use]
+    pub fn with_sex(mut self, sex: Sex) -> Self {
+        self.sex = Some(sex);
+        self
+    }
+
+    /// Adds a modifier, e.g. `HP:0012828 | Severe`
+    #[must_use]
+    pub fn with_modifier(mut self, modifier: HpoTermId) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+
+    /// Sets the [`Aspect`]
+    #[must_use]
+    pub fn with_aspect(mut self, aspect: Aspect) -> Self {
+        self.aspect = Some(aspect);
+        self
+    }
+
+    /// Adds a biocuration entry, e.g. `HPO:probinson[2021-06-21]`
+    #[must_use]
+    pub fn with_biocuration(mut self, biocuration: &str) -> Self {
+        self.biocuration.push(biocuration.to_string());
+        self
+    }
+
+    /// The [`HpoTermId`] of the annotated term
+    pub fn term_id(&self) -> HpoTermId {
+        self.term_id
+    }
+
+    /// The [`EvidenceCode`] of the annotation
+    ///
+    /// If the annotation was merged from multiple sources, this is the
+    /// strongest evidence, from `PCS` over `TAS` to `IEA`.
+    pub fn evidence(&self) -> Option<EvidenceCode> {
+        self.evidence
+    }
+
+    /// The onset of the term, e.g. `HP:0003577 | Congenital onset`
+    pub fn onset(&self) -> Option<HpoTermId> {
+        self.onset
+    }
+
+    /// The [`Sex`] that the annotation is specific to, if any
+    pub fn sex(&self) -> Option<Sex> {
+        self.sex
+    }
+
+    /// Modifiers of the annotation, e.g. `HP:0012828 | Severe`
+    pub fn modifiers(&self) -> &[HpoTermId] {
+        &self.modifiers
+    }
+
+    /// The [`Aspect`] of the annotated term
+    pub fn aspect(&self) -> Option<Aspect> {
+        self.aspect
+    }
+
+    /// The biocuration entries, e.g. `HPO:probinson[2021-06-21]`
+    pub fn biocuration(&self) -> &[String] {
+        &self.biocuration
+    }
+
+    /// Replaces the annotated term, e.g. when an obsolete term is remapped
+    pub(crate) fn set_term_id(&mut self, term_id: HpoTermId) {
+        self.term_id = term_id;
+    }
+
+    /// Merges another annotation of the same term into `self`
+    ///
+    /// The strongest evidence is kept, a missing aspect is added
+    /// from `other` and modifiers and biocuration are combined.
+    ///
+    /// Onset and sex restrict the annotation, so they are only kept
+    /// if both annotations agree. Otherwise they are removed.
+    pub(crate) fn merge(&mut self, other: DiseaseAnnotation) {
+        self.evidence = self.evidence.max(other.evidence);
+        if self.onset != other.onset {
+            self.onset = None;
+        }
+        if self.sex != other.sex {
+            self.sex = None;
+        }
+        self.aspect = self.aspect.or(other.aspect);
+        for modifier in other.modifiers {
+            if !self.modifiers.contains(&modifier) {
+                self.modifiers.push(modifier);
+            }
+        }
+        for biocuration in other.biocuration {
+            if !self.biocuration.contains(&biocuration) {
+                self.biocuration.push(biocuration);
+            }
+        }
+    }
+
+    /// Returns the binary representation of the annotation
+    ///
+    /// | Byte offset | Number of bytes | Description |
+    /// | --- | --- | --- |
+    /// | 0 | 4 | The [`HpoTermId`] as big-endian `u32` |
+    /// | 4 | 1 | The [`EvidenceCode`] (`0` if missing) |
+    /// | 5 | 1 | The [`Aspect`] as ASCII character (`0` if missing) |
+    /// | 6 | 1 | The [`Sex`]: `1` male, `2` female (`0` if missing) |
+    /// | 7 | 4 | The onset [`HpoTermId`] as big-endian `u32` (`0` if missing) |
+    /// | 11 | 4 | The number of modifiers as big-endian `u32` |
+    /// | 15 | m * 4 | The modifier [`HpoTermId`]s as big-endian `u32` |
+    /// | 15 + m * 4 | 4 | The number of biocuration entries as big-endian `u32` |
+    /// | 19 + m * 4 | ... | Each entry as big-endian `u32` length, followed by the UTF-8 bytes |
+    pub(crate) fn as_bytes(&self) -> Vec<u8> {
+        fn usize_to_u32(n: usize) -> u32 {
+            n.try_into().expect("unable to convert {n} to u32")
+        }
+        let mut res = Vec::new();
+        res.extend_from_slice(&self.term_id.to_be_bytes());
+        res.push(self.evidence.map_or(0, u8::from));
+        res.push(self.aspect.map_or(0, |aspect| aspect.as_char() as u8));
+        res.push(match self.sex {
+            None => 0,
+            Some(Sex::Male) => 1,
+            Some(Sex::Female) => 2,
+        });
+        res.extend_from_slice(&self.onset.map_or(0, |onset| onset.as_u32()).to_be_bytes());
+        res.extend_from_slice(&usize_to_u32(self.modifiers.len()).to_be_bytes());
+        for modifier in &self.modifiers {
+            res.extend_from_slice(&modifier.to_be_bytes());
+        }
+        res.extend_from_slice(&usize_to_u32(self.biocuration.len()).to_be_bytes());
+        for biocuration in &self.biocuration {
+            res.extend_from_slice(&usize_to_u32(biocuration.len()).to_be_bytes());
+            res.extend_from_slice(biocuration.as_bytes());
+        }
+        res
+    }
+
+    /// Parses an annotation from bytes, as defined in [`DiseaseAnnotation::as_bytes`]
+    ///
+    /// Returns the annotation and the number of parsed bytes
+    pub(crate) fn from_bytes(bytes: &[u8]) -> HpoResult<(Self, usize)> {
+        fn u32_at(bytes: &[u8], idx: usize) -> HpoResult<u32> {
+            if bytes.len() < idx + 4 {
+                error!("Too few bytes for a DiseaseAnnotation");
+                return Err(HpoError::ParseBinaryError);
+            }
+            Ok(u32_from_bytes(&bytes[idx..]))
+        }
+
+        if bytes.len() < 19 {
+            error!("Too few bytes for a DiseaseAnnotation");
+            return Err(HpoError::ParseBinaryError);
+        }
+        let mut annotation = DiseaseAnnotation::new(HpoTermId::from_u32(u32_at(bytes, 0)?));
+        if bytes[4] > 0 {
+            annotation.evidence = Some(EvidenceCode::try_from(bytes[4])?);
+        }
+        if bytes[5] > 0 {
+            annotation.aspect = Some(
+                Aspect::try_from(char::from(bytes[5])).map_err(|_| HpoError::ParseBinaryError)?,
+            );
+        }
+        annotation.sex = match bytes[6] {
+            0 => None,
+            1 => Some(Sex::Male),
+            2 => Some(Sex::Female),
+            _ => return Err(HpoError::ParseBinaryError),
+        };
+        let onset = u32_at(bytes, 7)?;
+        if onset > 0 {
+            annotation.onset = Some(HpoTermId::from_u32(onset));
+        }
+
+        let mut idx = 11;
+        let n_modifiers = u32_at(bytes, idx)?;
+        idx += 4;
+        for _ in 0..n_modifiers {
+            annotation
+                .modifiers
+                .push(HpoTermId::from_u32(u32_at(bytes, idx)?));
+            idx += 4;
+        }
+
+        let n_biocuration = u32_at(bytes, idx)?;
+        idx += 4;
+        for _ in 0..n_biocuration {
+            let len = u32_at(bytes, idx)? as usize;
+            idx += 4;
+            let Some(biocuration) = bytes.get(idx..idx + len) else {
+                error!("Too few bytes for the biocuration of a DiseaseAnnotation");
+                return Err(HpoError::ParseBinaryError);
+            };
+            let Ok(biocuration) = String::from_utf8(biocuration.to_vec()) else {
+                error!("Invalid biocuration of a DiseaseAnnotation");
+                return Err(HpoError::ParseBinaryError);
+            };
+            annotation.biocuration.push(biocuration);
+            idx += len;
+        }
+        Ok((annotation, idx))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn merge() {
+        let mut annotation = DiseaseAnnotation::new(HpoTermId::from_u32(1))
+            .with_evidence(EvidenceCode::Iea)
+            .with_biocuration("HPO:iea[2009-02-17]");
+        annotation.merge(
+            DiseaseAnnotation::new(HpoTermId::from_u32(1))
+                .with_evidence(EvidenceCode::Pcs)
+                .with_onset(HpoTermId::from_u32(3577))
+                .with_biocuration("HPO:probinson[2021-06-21]"),
+        );
+        assert_eq!(annotation.evidence(), Some(EvidenceCode::Pcs));
+        assert_eq!(annotation.onset(), None);
+        assert_eq!(annotation.biocuration().len(), 2);
+    }
+
+    #[test]
+    fn merge_restrictions() {
+        let mut annotation = DiseaseAnnotation::new(HpoTermId::from_u32(1));
+        annotation.merge(DiseaseAnnotation::new(HpoTermId::from_u32(1)).with_sex(Sex::Female));
+        assert_eq!(annotation.sex(), None);
+
+        let mut annotation = DiseaseAnnotation::new(HpoTermId::from_u32(1))
+            .with_sex(Sex::Female)
+            .with_onset(HpoTermId::from_u32(3577));
+        annotation.merge(
+            DiseaseAnnotation::new(HpoTermId::from_u32(1))
+                .with_sex(Sex::Female)
+                .with_onset(HpoTermId::from_u32(3593)),
+        );
+        assert_eq!(annotation.sex(), Some(Sex::Female));
+        assert_eq!(annotation.onset(), None);
+
+        let mut annotation = DiseaseAnnotation::new(HpoTermId::from_u32(1)).with_sex(Sex::Male);
+        annotation.merge(DiseaseAnnotation::new(HpoTermId::from_u32(1)).with_sex(Sex::Female));
+        assert_eq!(annotation.sex(), None);
+    }
+
+    #[test]
+    fn to_and_from_bytes() {
+        let annotation = DiseaseAnnotation::new(HpoTermId::from_u32(11097))
+            .with_evidence(EvidenceCode::Tas)
+            .with_onset(HpoTermId::from_u32(3577))
+            .with_sex(Sex::Female)
+            .with_modifier(HpoTermId::from_u32(12828))
+            .with_aspect(Aspect::Phenotype)
+            .with_biocuration("HPO:probinson[2021-06-21]");
+        let bytes = annotation.as_bytes();
+        assert_eq!(bytes.len(), 4 + 1 + 1 + 1 + 4 + 4 + 4 + 4 + 4 + 25);
+
+        let (annotation2, len) = DiseaseAnnotation::from_bytes(&bytes).unwrap();
+        assert_eq!(len, bytes.len());
+        assert_eq!(annotation, annotation2);
+
+        assert!(DiseaseAnnotation::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn parse_values() {
+        assert_eq!(EvidenceCode::try_from("IEA").unwrap(), EvidenceCode::Iea);
+        assert!(EvidenceCode::try_from("FOO").is_err());
+        assert!(EvidenceCode::Pcs > EvidenceCode::Iea);
+        assert_eq!(Aspect::try_from("C").unwrap(), Aspect::ClinicalCourse);
+        assert!(Aspect::try_from("PC").is_err());
+        assert_eq!(Sex::try_from("MALE").unwrap(), Sex::Male);
+        assert_eq!(Sex::try_from("female").unwrap(), Sex::Female);
+    }
+}
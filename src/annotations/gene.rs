@@ -8,6 +8,7 @@ use std::hash::Hash;
 use tracing::error;
 
 use crate::annotations::AnnotationId;
+use crate::annotations::{OmimDiseaseId, OmimDiseases, OrphaDiseaseId, OrphaDiseases};
 use crate::set::HpoSet;
 use crate::term::HpoGroup;
 use crate::u32_from_bytes;
@@ -30,6 +31,8 @@ pub type Genes = HashSet<GeneId>;
 /// When using the default JAX provided masterdata, it represents
 /// the NCBI Gene ID
 #[derive(Clone, Copy, Default, Debug, Hash, PartialEq, PartialOrd, Eq, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct GeneId {
     inner: u32,
 }
@@ -65,12 +68,15 @@ impl Display for GeneId {
 /// A single gene
 ///
 /// A gene has a unique [`GeneId`] and a name (symbol) and is
-/// connected to a set of HPO terms
+/// connected to a set of HPO terms and to the diseases it causes
 #[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gene {
     id: GeneId,
     name: String,
     hpos: HpoGroup,
+    omim_diseases: OmimDiseases,
+    orpha_diseases: OrphaDiseases,
 }
 
 impl Gene {
@@ -84,6 +90,8 @@ impl Gene {
             id,
             name: name.to_string(),
             hpos: HpoGroup::default(),
+            omim_diseases: OmimDiseases::default(),
+            orpha_diseases: OrphaDiseases::default(),
         }
     }
 
@@ -101,6 +109,8 @@ impl Gene {
             id: GeneId::try_from(id)?,
             name: name.to_string(),
             hpos: HpoGroup::default(),
+            omim_diseases: OmimDiseases::default(),
+            orpha_diseases: OrphaDiseases::default(),
         })
     }
 
@@ -124,6 +134,38 @@ impl Gene {
         &self.hpos
     }
 
+    /// The set of [`OmimDiseaseId`]s of all diseases that are directly
+    /// associated with the gene
+    ///
+    /// The association is based on the `disease_id` column of
+    /// `genes_to_phenotype.txt` and on `genes_to_disease.txt`, it does
+    /// not depend on shared HPO terms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::Ontology;
+    /// use hpo::annotations::Disease;
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    /// let gene = ontology.gene_by_name("NAT2").unwrap();
+    /// for omim_id in gene.omim_diseases() {
+    ///     let disease = ontology.omim_disease(omim_id).unwrap();
+    ///     assert!(disease.genes().contains(gene.id()));
+    /// }
+    /// ```
+    pub fn omim_diseases(&self) -> &OmimDiseases {
+        &self.omim_diseases
+    }
+
+    /// The set of [`OrphaDiseaseId`]s of all diseases that are directly
+    /// associated with the gene
+    ///
+    /// See [`Gene::omim_diseases`] for details
+    pub fn orpha_diseases(&self) -> &OrphaDiseases {
+        &self.orpha_diseases
+    }
+
     /// Returns a binary representation of the `Gene`
     ///
     /// The binary layout is defined as:
@@ -137,6 +179,15 @@ impl Gene {
     /// | 9 + n | 4 | The number of associated HPO terms as big-endian `u32` |
     /// | 13 + n | x * 4 | The HPO Term IDs of the associated terms, each encoded as big-endian `u32` |
     ///
+    /// Followed by the associated diseases, only if the gene has any:
+    ///
+    /// | Byte offset | Number of bytes | Description |
+    /// | --- | --- | --- |
+    /// | 13 + n + x * 4 | 4 | The number of associated OMIM diseases as big-endian `u32` |
+    /// | 17 + n + x * 4 | o * 4 | The [`OmimDiseaseId`]s, each encoded as big-endian `u32` |
+    /// | 17 + n + x * 4 + o * 4 | 4 | The number of associated ORPHA diseases as big-endian `u32` |
+    /// | 21 + n + x * 4 + o * 4 | r * 4 | The [`OrphaDiseaseId`]s, each encoded as big-endian `u32` |
+    ///
     /// # Examples
     ///
     /// ```
@@ -155,7 +206,8 @@ impl Gene {
         }
         let name = self.name().as_bytes();
         let name_length = std::cmp::min(name.len(), 255);
-        let size = 4 + 4 + 1 + name_length + 4 + self.hpos.len() * 4;
+        let mut diseases = self.diseases_as_bytes();
+        let size = 4 + 4 + 1 + name_length + 4 + self.hpos.len() * 4 + diseases.len();
 
         let mut res = Vec::new();
 
@@ -181,9 +233,71 @@ impl Gene {
         // HPO terms
         res.append(&mut self.hpos.as_bytes());
 
+        // Associated diseases
+        res.append(&mut diseases);
+
+        res
+    }
+
+    /// Returns the binary representation of the associated diseases
+    ///
+    /// The diseases are only added if the gene has any,
+    /// see [`Gene::as_bytes`] for the layout.
+    fn diseases_as_bytes(&self) -> Vec<u8> {
+        fn ids_as_bytes<T: AnnotationId>(ids: &HashSet<T>, res: &mut Vec<u8>) {
+            let mut ids: Vec<&T> = ids.iter().collect();
+            ids.sort();
+            res.extend_from_slice(
+                &u32::try_from(ids.len())
+                    .expect("unable to convert number of diseases to u32")
+                    .to_be_bytes(),
+            );
+            for id in ids {
+                res.extend_from_slice(&id.to_be_bytes());
+            }
+        }
+        if self.omim_diseases.is_empty() && self.orpha_diseases.is_empty() {
+            return Vec::new();
+        }
+        let mut res =
+            Vec::with_capacity(8 + (self.omim_diseases.len() + self.orpha_diseases.len()) * 4);
+        ids_as_bytes(&self.omim_diseases, &mut res);
+        ids_as_bytes(&self.orpha_diseases, &mut res);
         res
     }
 
+    /// Parses the associated diseases and adds them to the gene
+    ///
+    /// Returns the number of parsed bytes
+    fn diseases_from_bytes(&mut self, bytes: &[u8]) -> HpoResult<usize> {
+        fn ids_from_bytes(bytes: &[u8]) -> HpoResult<(Vec<u32>, usize)> {
+            if bytes.len() < 4 {
+                error!("Too few bytes for the diseases of a Gene");
+                return Err(HpoError::ParseBinaryError);
+            }
+            let n_diseases = u32_from_bytes(bytes) as usize;
+            let end = 4 + n_diseases * 4;
+            if bytes.len() < end {
+                error!("Too few bytes for {} diseases of a Gene", n_diseases);
+                return Err(HpoError::ParseBinaryError);
+            }
+            Ok((
+                bytes[4..end].chunks_exact(4).map(u32_from_bytes).collect(),
+                end,
+            ))
+        }
+
+        let (omim_ids, idx) = ids_from_bytes(bytes)?;
+        let (orpha_ids, len) = ids_from_bytes(&bytes[idx..])?;
+        for id in omim_ids {
+            self.add_omim_disease(id.into());
+        }
+        for id in orpha_ids {
+            self.add_orpha_disease(id.into());
+        }
+        Ok(idx + len)
+    }
+
     /// Returns an [`HpoSet`] from the `Gene`
     pub fn to_hpo_set<'a>(&self, ontology: &'a Ontology) -> HpoSet<'a> {
         HpoSet::new(ontology, self.hpos.clone())
@@ -198,6 +312,36 @@ impl Gene {
     pub fn add_term<I: Into<HpoTermId>>(&mut self, term_id: I) -> bool {
         self.hpos.insert(term_id)
     }
+
+    /// Associates the gene with an [`OmimDisease`](`crate::annotations::OmimDisease`)
+    ///
+    /// # Note
+    ///
+    /// This method does **not** add the [`Gene`] to the disease.
+    /// Clients should not use this method, unless they are creating their own Ontology.
+    pub fn add_omim_disease(&mut self, omim_id: OmimDiseaseId) -> bool {
+        self.omim_diseases.insert(omim_id)
+    }
+
+    /// Associates the gene with an [`OrphaDisease`](`crate::annotations::OrphaDisease`)
+    ///
+    /// # Note
+    ///
+    /// This method does **not** add the [`Gene`] to the disease.
+    /// Clients should not use this method, unless they are creating their own Ontology.
+    pub fn add_orpha_disease(&mut self, orpha_id: OrphaDiseaseId) -> bool {
+        self.orpha_diseases.insert(orpha_id)
+    }
+
+    /// Removes all associated diseases that don't match the predicates
+    pub(crate) fn retain_diseases<O, R>(&mut self, omim: O, orpha: R)
+    where
+        O: Fn(&OmimDiseaseId) -> bool,
+        R: Fn(&OrphaDiseaseId) -> bool,
+    {
+        self.omim_diseases.retain(omim);
+        self.orpha_diseases.retain(orpha);
+    }
 }
 
 impl PartialEq for Gene {
@@ -292,6 +436,10 @@ impl TryFrom<&[u8]> for Gene {
             gene.add_term(term_id);
         }
 
+        if idx_terms < total_len {
+            idx_terms += gene.diseases_from_bytes(&bytes[idx_terms..])?;
+        }
+
         if idx_terms == total_len && idx_terms == bytes.len() {
             Ok(gene)
         } else {
@@ -420,6 +568,25 @@ mod test {
         }
     }
 
+    #[test]
+    fn gene_with_diseases() {
+        let mut gene = Gene::from_parts("123", "FooBar").unwrap();
+        gene.add_term(66u32);
+        gene.add_omim_disease(243_400u32.into());
+        gene.add_omim_disease(613_287u32.into());
+        gene.add_orpha_disease(432u32.into());
+
+        let bin = gene.as_bytes();
+        assert_eq!(bin.len(), 4 + 4 + 1 + 6 + 4 + 4 + 4 + 8 + 4 + 4);
+
+        let gene2 = Gene::try_from(&bin[..]).expect("Can't build Gene");
+        assert_eq!(gene2.hpo_terms().len(), 1);
+        assert_eq!(gene2.omim_diseases(), gene.omim_diseases());
+        assert_eq!(gene2.orpha_diseases(), gene.orpha_diseases());
+
+        assert!(Gene::try_from(&bin[..bin.len() - 4]).is_err());
+    }
+
     #[test]
     fn gene_with_wrong_length() {
         let mut gene = Gene::new(123u32.into(), "foobar");
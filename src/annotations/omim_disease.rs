@@ -1,10 +1,11 @@
+use std::collections::hash_map::Entry;
 use std::collections::hash_map::Values;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
-use crate::annotations::disease::DiseaseIterator;
-use crate::annotations::{AnnotationId, Disease};
+use crate::annotations::disease::{details_as_bytes, DiseaseIterator};
+use crate::annotations::{AnnotationId, Disease, DiseaseAnnotation, GeneId, Genes};
 use crate::term::HpoGroup;
 use crate::{HpoError, HpoSet, HpoTermId, Ontology};
 
@@ -23,6 +24,8 @@ pub type OmimDiseases = HashSet<OmimDiseaseId>;
 /// When using the default JAX provided masterdata, it represents
 /// the actual OMIM MIM ID.
 #[derive(Clone, Copy, Default, Debug, Hash, PartialEq, PartialOrd, Eq, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct OmimDiseaseId {
     inner: u32,
 }
@@ -60,10 +63,15 @@ impl Display for OmimDiseaseId {
 /// A disease has a unique [`OmimDiseaseId`] and a name and is
 /// connected to a set of HPO terms
 #[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OmimDisease {
     id: OmimDiseaseId,
     name: String,
     hpos: HpoGroup,
+    frequencies: HashMap<HpoTermId, f32>,
+    annotations: HashMap<HpoTermId, DiseaseAnnotation>,
+    excluded: HpoGroup,
+    genes: Genes,
 }
 
 impl Disease for OmimDisease {
@@ -79,6 +87,10 @@ impl Disease for OmimDisease {
             name: name.to_string(),
             id,
             hpos: HpoGroup::default(),
+            frequencies: HashMap::new(),
+            annotations: HashMap::new(),
+            excluded: HpoGroup::default(),
+            genes: Genes::default(),
         }
     }
 
@@ -97,6 +109,51 @@ impl Disease for OmimDisease {
         &self.hpos
     }
 
+    /// The frequencies of all connected HPO terms with a known frequency
+    fn term_frequencies(&self) -> &HashMap<HpoTermId, f32> {
+        &self.frequencies
+    }
+
+    /// Sets the frequency of a connected HPO term
+    fn set_term_frequency<I: Into<HpoTermId>>(&mut self, term_id: I, frequency: f32) {
+        self.frequencies.insert(term_id.into(), frequency);
+    }
+
+    /// The details of all connected HPO terms with known details
+    fn annotations(&self) -> &HashMap<HpoTermId, DiseaseAnnotation> {
+        &self.annotations
+    }
+
+    /// Adds or merges the details of a connected HPO term
+    fn add_annotation(&mut self, annotation: DiseaseAnnotation) {
+        match self.annotations.entry(annotation.term_id()) {
+            Entry::Occupied(mut entry) => entry.get_mut().merge(annotation),
+            Entry::Vacant(entry) => {
+                entry.insert(annotation);
+            }
+        }
+    }
+
+    /// The set of HPO terms that are explicitly not present
+    fn excluded_terms(&self) -> &HpoGroup {
+        &self.excluded
+    }
+
+    /// Records an HPO term that is explicitly not present
+    fn add_excluded_term<I: Into<HpoTermId>>(&mut self, term_id: I) -> bool {
+        self.excluded.insert(term_id)
+    }
+
+    /// The set of directly associated genes
+    fn genes(&self) -> &Genes {
+        &self.genes
+    }
+
+    /// Associates the disease with a gene
+    fn add_gene(&mut self, gene_id: GeneId) -> bool {
+        self.genes.insert(gene_id)
+    }
+
     /// Returns a binary representation of the `OmimDisease`
     ///
     /// The binary layout is defined as:
@@ -110,6 +167,8 @@ impl Disease for OmimDisease {
     /// | 12 + n | 4 | The number of associated HPO terms as big-endian `u32` |
     /// | 16 + n | x * 4 | The [`HpoTermId`]s of the associated terms, each encoded as big-endian `u32` |
     ///
+    /// Followed by the annotation details, see [`Disease::as_bytes`]
+    ///
     /// # Examples
     ///
     /// ```
@@ -128,7 +187,8 @@ impl Disease for OmimDisease {
         }
         let name = self.name().as_bytes();
         let name_length = name.len();
-        let size = 4 + 4 + 4 + name_length + 4 + self.hpos.len() * 4;
+        let mut details = details_as_bytes(self);
+        let size = 4 + 4 + 4 + name_length + 4 + self.hpos.len() * 4 + details.len();
 
         let mut res = Vec::new();
 
@@ -152,6 +212,9 @@ impl Disease for OmimDisease {
         // HPO terms
         res.append(&mut self.hpos.as_bytes());
 
+        // Annotation details
+        res.append(&mut details);
+
         res
     }
 
@@ -325,6 +388,57 @@ mod test {
         }
     }
 
+    #[test]
+    fn disease_with_frequencies() {
+        let mut disease = OmimDisease::new(123u32.into(), "FooBar");
+        disease.add_term(66u32);
+        disease.add_term(77u32);
+        disease.set_term_frequency(77u32, 0.25);
+
+        let bin = disease.as_bytes();
+        assert_eq!(bin.len(), 4 + 4 + 4 + 6 + 4 + 8 + 4 + 8 + 4 + 4);
+
+        let disease2 = OmimDisease::try_from(&bin[..]).expect("Can't build Disease");
+        assert_eq!(disease2.hpo_terms().len(), 2);
+        assert_eq!(disease2.term_frequency(77u32), Some(0.25));
+        assert_eq!(disease2.term_frequency(66u32), None);
+
+        assert!(OmimDisease::try_from(&bin[..bin.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn disease_with_annotations() {
+        let mut disease = OmimDisease::new(123u32.into(), "FooBar");
+        disease.add_term(66u32);
+        disease.add_term(77u32);
+        disease.add_annotation(
+            DiseaseAnnotation::new(66u32.into())
+                .with_evidence(crate::annotations::EvidenceCode::Tas),
+        );
+
+        let bin = disease.as_bytes();
+        let disease2 = OmimDisease::try_from(&bin[..]).expect("Can't build Disease");
+        assert_eq!(disease2.annotations().len(), 1);
+        assert_eq!(disease2.annotation(66u32), disease.annotation(66u32));
+        assert!(disease2.annotation(77u32).is_none());
+        assert!(disease2.term_frequencies().is_empty());
+    }
+
+    #[test]
+    fn disease_with_excluded_terms() {
+        let mut disease = OmimDisease::new(123u32.into(), "FooBar");
+        disease.add_term(66u32);
+        disease.add_excluded_term(88u32);
+
+        let bin = disease.as_bytes();
+        assert_eq!(bin.len(), 4 + 4 + 4 + 6 + 4 + 4 + 4 + 4 + 4 + 4);
+
+        let disease2 = OmimDisease::try_from(&bin[..]).expect("Can't build Disease");
+        assert_eq!(disease2.hpo_terms().len(), 1);
+        assert_eq!(disease2.excluded_terms().len(), 1);
+        assert!(disease2.excluded_terms().contains(&88u32.into()));
+    }
+
     #[test]
     fn disease_with_wrong_length() {
         let mut disease = OmimDisease::new(123u32.into(), "foobar");
@@ -342,4 +456,31 @@ mod test {
         assert!(OmimDisease::try_from(&bin[..30]).is_ok());
         assert!(OmimDisease::try_from(&bin[..31]).is_err());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn disease_serde_roundtrip() {
+        use crate::annotations::EvidenceCode;
+
+        let mut disease = OmimDisease::new(123u32.into(), "FooBar");
+        disease.add_term(66u32);
+        disease.add_term(77u32);
+        disease.set_term_frequency(66u32, 0.5);
+        disease
+            .add_annotation(DiseaseAnnotation::new(66u32.into()).with_evidence(EvidenceCode::Pcs));
+        disease.add_excluded_term(88u32);
+        disease.add_gene(GeneId::from(5u32));
+
+        let json = serde_json::to_string(&disease).unwrap();
+        assert!(json.starts_with(r#"{"id":123,"name":"FooBar","hpos":["HP:0000066","HP:0000077"]"#));
+
+        let disease2: OmimDisease = serde_json::from_str(&json).unwrap();
+        assert_eq!(disease2.id(), disease.id());
+        assert_eq!(disease2.name(), disease.name());
+        assert_eq!(disease2.hpo_terms().len(), 2);
+        assert_eq!(disease2.term_frequencies(), disease.term_frequencies());
+        assert_eq!(disease2.annotations(), disease.annotations());
+        assert!(disease2.excluded_terms().contains(&88u32.into()));
+        assert_eq!(disease2.genes(), disease.genes());
+    }
 }
@@ -1,9 +1,10 @@
-use std::collections::HashSet;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
 use crate::annotations::disease::DiseaseIterator;
-use crate::annotations::{AnnotationId, Disease};
+use crate::annotations::{AnnotationId, Disease, DiseaseAnnotation, GeneId, Genes};
 use crate::term::HpoGroup;
 use crate::HpoError;
 use crate::HpoTermId;
@@ -23,6 +24,8 @@ pub type OrphaDiseases = HashSet<OrphaDiseaseId>;
 /// When using the default JAX provided masterdata, it represents
 /// the actual Orpha MIM ID.
 #[derive(Clone, Copy, Default, Debug, Hash, PartialEq, PartialOrd, Eq, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct OrphaDiseaseId {
     inner: u32,
 }
@@ -60,10 +63,15 @@ impl Display for OrphaDiseaseId {
 /// A disease has a unique [`OrphaDiseaseId`] and a name and is
 /// connected to a set of HPO terms
 #[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrphaDisease {
     id: OrphaDiseaseId,
     name: String,
     hpos: HpoGroup,
+    frequencies: HashMap<HpoTermId, f32>,
+    annotations: HashMap<HpoTermId, DiseaseAnnotation>,
+    excluded: HpoGroup,
+    genes: Genes,
 }
 
 impl Disease for OrphaDisease {
@@ -79,6 +87,10 @@ impl Disease for OrphaDisease {
             name: name.to_string(),
             id,
             hpos: HpoGroup::default(),
+            frequencies: HashMap::new(),
+            annotations: HashMap::new(),
+            excluded: HpoGroup::default(),
+            genes: Genes::default(),
         }
     }
 
@@ -101,6 +113,51 @@ impl Disease for OrphaDisease {
     fn hpo_terms(&self) -> &HpoGroup {
         &self.hpos
     }
+
+    /// The frequencies of all connected HPO terms with a known frequency
+    fn term_frequencies(&self) -> &HashMap<HpoTermId, f32> {
+        &self.frequencies
+    }
+
+    /// Sets the frequency of a connected HPO term
+    fn set_term_frequency<I: Into<HpoTermId>>(&mut self, term_id: I, frequency: f32) {
+        self.frequencies.insert(term_id.into(), frequency);
+    }
+
+    /// The details of all connected HPO terms with known details
+    fn annotations(&self) -> &HashMap<HpoTermId, DiseaseAnnotation> {
+        &self.annotations
+    }
+
+    /// Adds or merges the details of a connected HPO term
+    fn add_annotation(&mut self, annotation: DiseaseAnnotation) {
+        match self.annotations.entry(annotation.term_id()) {
+            Entry::Occupied(mut entry) => entry.get_mut().merge(annotation),
+            Entry::Vacant(entry) => {
+                entry.insert(annotation);
+            }
+        }
+    }
+
+    /// The set of HPO terms that are explicitly not present
+    fn excluded_terms(&self) -> &HpoGroup {
+        &self.excluded
+    }
+
+    /// Records an HPO term that is explicitly not present
+    fn add_excluded_term<I: Into<HpoTermId>>(&mut self, term_id: I) -> bool {
+        self.excluded.insert(term_id)
+    }
+
+    /// The set of directly associated genes
+    fn genes(&self) -> &Genes {
+        &self.genes
+    }
+
+    /// Associates the disease with a gene
+    fn add_gene(&mut self, gene_id: GeneId) -> bool {
+        self.genes.insert(gene_id)
+    }
 }
 
 impl PartialEq for OrphaDisease {
@@ -20,8 +20,10 @@ pub mod utils;
 
 pub use ontology::builder;
 pub use ontology::comparison;
-pub use ontology::Ontology;
-pub use set::HpoSet;
+pub use ontology::{AnnotationMetadata, Ontology, OntologyMetadata};
+#[cfg(feature = "mmap")]
+pub use ontology::{OntologyView, TermIds, TermView};
+pub use set::{HpoSet, HpoSetSnapshot, ObsoleteTerm, TermSnapshot};
 #[doc(inline)]
 pub use term::{HpoTerm, HpoTermId};
 
@@ -30,11 +32,13 @@ const DEFAULT_NUM_ALL_PARENTS: usize = 30;
 const DEFAULT_NUM_GENES: usize = 50;
 const DEFAULT_NUM_OMIM: usize = 20;
 const DEFAULT_NUM_ORPHA: usize = 20;
-const MAX_HPO_ID_INTEGER: usize = 10_000_000;
 
 const OBO_FILENAME: &str = "hp.obo";
+#[cfg(feature = "obographs")]
+const OBOGRAPHS_FILENAME: &str = "hp.json";
 const GENE_FILENAME: &str = "phenotype_to_genes.txt";
 const GENE_TO_PHENO_FILENAME: &str = "genes_to_phenotype.txt";
+const GENE_TO_DISEASE_FILENAME: &str = "genes_to_disease.txt";
 const DISEASE_FILENAME: &str = "phenotype.hpoa";
 
 /// The `HpoTermId` of `HP:0000118 | Phenotypic abnormality`
@@ -64,6 +68,36 @@ pub enum HpoError {
     /// Failed to parse a line of input data from the JAX obo
     #[error("invalid input data: {0}")]
     InvalidInput(String),
+    /// Failed to parse a line of OBO data, e.g. from `hp.obo`
+    #[error("invalid OBO data in {file}, line {line} ({reason}): {text}")]
+    ParseOboError {
+        /// The name of the file or `<reader>`
+        file: String,
+        /// The line number
+        line: usize,
+        /// The offending line
+        text: String,
+        /// Description of the issue
+        reason: String,
+    },
+    /// A section of the binary HPO data is incomplete, e.g. in a truncated file
+    #[error("invalid binary data in section {section}: expected {expected} bytes, found {actual}")]
+    BinarySectionError {
+        /// The name of the section, e.g. `genes`
+        section: String,
+        /// The number of bytes required by the section
+        expected: usize,
+        /// The number of available bytes
+        actual: usize,
+    },
+    /// The checksum of the binary HPO data does not match, the data is corrupt
+    #[error("binary data is corrupt: checksum {actual:#010x} does not match {expected:#010x}")]
+    BinaryChecksumError {
+        /// The checksum stored in the binary data
+        expected: u32,
+        /// The checksum of the actual binary data
+        actual: u32,
+    },
 }
 
 impl From<ParseIntError> for HpoError {
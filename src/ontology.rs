@@ -1,32 +1,43 @@
-use crate::annotations::Disease;
+use crate::annotations::{AnnotationId, Disease};
 use core::fmt::Debug;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write as _;
-use std::fs::File;
-use std::io::Read;
+use std::io::{BufRead, Read};
 
 use std::path::Path;
 
 use tracing::debug;
 
+use crate::annotations::{DecipherDisease, DecipherDiseaseId};
 use crate::annotations::{Gene, GeneId};
 use crate::annotations::{OmimDisease, OmimDiseaseFilter, OmimDiseaseId};
 use crate::annotations::{OrphaDisease, OrphaDiseaseId};
 use crate::parser;
-use crate::parser::binary::BinaryVersion;
+use crate::parser::binary::ontology::{
+    length_prefixed_section, verify_checksum, Section, SectionDirectory,
+    INFORMATION_CONTENT_ENTRY_LEN, TERM_INDEX_ENTRY_LEN,
+};
+use crate::parser::binary::{BinaryVersion, Bytes};
+use crate::term::group::Combined;
 use crate::term::internal::HpoTermInternal;
-use crate::term::{HpoGroup, HpoTerm};
-use crate::u32_from_bytes;
+use crate::term::{HpoGroup, HpoTerm, ResolvedTerm, Xref};
 use crate::HpoResult;
 use crate::{HpoError, HpoTermId};
 
 pub mod builder;
 pub mod comparison;
+mod metadata;
 mod termarena;
+#[cfg(feature = "mmap")]
+mod view;
 use comparison::Comparison;
 use termarena::Arena;
 
-pub use builder::Builder;
+use builder::ConnectedTerms;
+pub use builder::{Builder, LoadReport, ParseReport, StandardLoader};
+pub use metadata::{AnnotationMetadata, OntologyMetadata};
+#[cfg(feature = "mmap")]
+pub use view::{OntologyView, TermIds, TermView};
 
 /// `Ontology` is the main interface of the `hpo` crate and contains all data
 ///
@@ -129,9 +140,10 @@ pub use builder::Builder;
 /// The [`Ontology`] contains all terms and all associated genes and diseases.
 /// [`HpoTerm`]s are connected to each other in a directed relationship. Every term
 /// (except the term `All`) has at least one parent term in an `is_a` relationship.
-/// Terms and [`crate::annotations`] ([`Gene`]s, [`OmimDisease`]s, [`OrphaDisease`]s) have a many-to-many relationship. The
-/// [`Ontology`] does not contain a direct relationship between genes and diseases. This relation
-/// is only present indirectly via the connected [`HpoTerm`]s.
+/// Terms and [`crate::annotations`] ([`Gene`]s, [`OmimDisease`]s, [`OrphaDisease`]s) have a many-to-many relationship.
+/// Genes and diseases are related indirectly via the connected [`HpoTerm`]s. In addition, genes
+/// can be directly associated with the diseases they cause, if the source data provides this
+/// information (see [`Gene::omim_diseases`] and [`Disease::genes`](`crate::annotations::Disease::genes`)).
 ///
 /// ```text
 ///    .----------.
@@ -213,7 +225,9 @@ pub struct Ontology {
     genes: HashMap<GeneId, Gene>,
     omim_diseases: HashMap<OmimDiseaseId, OmimDisease>,
     orpha_diseases: HashMap<OrphaDiseaseId, OrphaDisease>,
+    decipher_diseases: HashMap<DecipherDiseaseId, DecipherDisease>,
     hpo_version: (u16, u8, u8),
+    metadata: OntologyMetadata,
     categories: HpoGroup,
     modifier: HpoGroup,
 }
@@ -238,6 +252,14 @@ impl Ontology {
     ///
     /// and then specify the folder where the data is stored.
     ///
+    /// If the folder also contains [`genes_to_disease.txt`](http://purl.obolibrary.org/obo/hp/hpoa/genes_to_disease.txt),
+    /// genes are linked directly to their diseases as well, see [`Gene::omim_diseases`].
+    ///
+    /// With the `gzip` feature, all files can also be gzip-compressed. Compression
+    /// is detected from the file content, so the files keep their standard names.
+    ///
+    /// Use [`StandardLoader`] to load the data from individual files or readers instead.
+    ///
     /// # Errors
     ///
     /// This method can fail for various reasons:
@@ -276,10 +298,48 @@ impl Ontology {
     ///
     pub fn from_standard(folder: &str) -> HpoResult<Self> {
         let path = Path::new(folder);
-        let obo = path.join(crate::OBO_FILENAME);
-        let gene = path.join(crate::GENE_TO_PHENO_FILENAME);
-        let disease = path.join(crate::DISEASE_FILENAME);
-        parser::load_from_jax_files(&obo, &gene, &disease)
+        StandardLoader::from_obo_file(path.join(crate::OBO_FILENAME))
+            .genes_to_phenotype_file(path.join(crate::GENE_TO_PHENO_FILENAME))
+            .standard_diseases(path)
+            .load()
+    }
+
+    /// Initialize the [`Ontology`] from data provided by [Jax HPO](https://hpo.jax.org/)
+    /// and skip annotations of unknown terms
+    ///
+    /// This method is identical to [`Ontology::from_standard`], but annotation
+    /// lines that reference terms missing in `hp.obo` are skipped instead of failing.
+    /// If `remap_obsolete` is `true`, annotations of obsolete terms and alternative IDs
+    /// are added to their replacement term. See [`Builder::lenient_annotations`].
+    ///
+    /// All skipped and remapped lines are listed in the returned [`LoadReport`].
+    ///
+    /// # Errors
+    ///
+    /// - obo file not present or available: [`HpoError::CannotOpenFile`]
+    /// - annotation file(s) not present: [`HpoError::CannotOpenFile`]
+    /// - invalid data in the annotation file(s): [`HpoError::InvalidInput`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hpo::Ontology;
+    ///
+    /// let (ontology, report) = Ontology::from_standard_lenient("/path/to/jax_hpo_data/", true).unwrap();
+    /// for issue in report.issues() {
+    ///     println!("{issue}");
+    /// }
+    /// ```
+    pub fn from_standard_lenient(
+        folder: &str,
+        remap_obsolete: bool,
+    ) -> HpoResult<(Self, LoadReport)> {
+        let path = Path::new(folder);
+        StandardLoader::from_obo_file(path.join(crate::OBO_FILENAME))
+            .genes_to_phenotype_file(path.join(crate::GENE_TO_PHENO_FILENAME))
+            .standard_diseases(path)
+            .lenient_annotations(remap_obsolete)
+            .load_with_report()
     }
 
     /// Initialize the [`Ontology`] from data provided by [Jax HPO](https://hpo.jax.org/)
@@ -292,6 +352,9 @@ impl Ontology {
     ///
     /// and then specify the folder where the data is stored.
     ///
+    /// If the folder also contains [`genes_to_disease.txt`](http://purl.obolibrary.org/obo/hp/hpoa/genes_to_disease.txt),
+    /// genes are linked directly to their diseases as well, see [`Gene::omim_diseases`].
+    ///
     /// # Errors
     ///
     /// This method can fail for various reasons:
@@ -328,10 +391,133 @@ impl Ontology {
     ///
     pub fn from_standard_transitive(folder: &str) -> HpoResult<Self> {
         let path = Path::new(folder);
-        let obo = path.join(crate::OBO_FILENAME);
-        let gene = path.join(crate::GENE_FILENAME);
-        let disease = path.join(crate::DISEASE_FILENAME);
-        parser::load_from_jax_files_with_transivitve_genes(&obo, &gene, &disease)
+        StandardLoader::from_obo_file(path.join(crate::OBO_FILENAME))
+            .phenotype_to_genes_file(path.join(crate::GENE_FILENAME))
+            .standard_diseases(path)
+            .load()
+    }
+
+    /// Initialize the [`Ontology`] from data provided by [Jax HPO](https://hpo.jax.org/),
+    /// using the [OBO Graphs JSON](https://github.com/geneontology/obographs) ontology
+    ///
+    /// This method is identical to [`Ontology::from_standard`], but reads the
+    /// ontology from `hp.json` instead of `hp.obo`. You must download:
+    ///
+    /// - Actual ontology data: [`hp.json`](https://hpo.jax.org/app/data/ontology)
+    /// - Links between HPO and OMIM diseases: [`phenotype.hpoa`](https://hpo.jax.org/app/data/annotations)
+    /// - Links between HPO and Genes: [`genes_to_phenotype.txt`](http://purl.obolibrary.org/obo/hp/hpoa/genes_to_phenotype.txt)
+    ///
+    /// and then specify the folder where the data is stored.
+    ///
+    /// This method is only available with the `obographs` feature.
+    ///
+    /// # Errors
+    ///
+    /// This method can fail for various reasons:
+    ///
+    /// - json or annotation file(s) not present: [`HpoError::CannotOpenFile`]
+    /// - invalid JSON or annotation data: [`HpoError::InvalidInput`]
+    /// - references to non-existing HPO terms: [`HpoError::DoesNotExist`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hpo::Ontology;
+    /// let ontology = Ontology::from_obographs_json("/path/to/jax_hpo_data/").unwrap();
+    ///
+    /// assert!(ontology.len() > 15_000);
+    /// ```
+    #[cfg(feature = "obographs")]
+    pub fn from_obographs_json(folder: &str) -> HpoResult<Self> {
+        let path = Path::new(folder);
+        StandardLoader::from_obographs_json_file(path.join(crate::OBOGRAPHS_FILENAME))
+            .genes_to_phenotype_file(path.join(crate::GENE_TO_PHENO_FILENAME))
+            .standard_diseases(path)
+            .load()
+    }
+
+    /// Initialize the [`Ontology`] from OBO data, e.g. `hp.obo`, without annotations
+    ///
+    /// The data is read stanza by stanza from any [`BufRead`], e.g. a file,
+    /// an in-memory buffer, a network stream or stdin. The resulting ontology
+    /// contains all terms and their connections, but no gene or disease annotations.
+    ///
+    /// Use [`Ontology::from_obo_lenient`] to skip invalid data instead of failing.
+    ///
+    /// # Errors
+    ///
+    /// - the data can't be read or contains invalid lines: [`HpoError::ParseOboError`]
+    /// - the data does not contain the terms `HP:0000001` and `HP:0000118`: [`HpoError::DoesNotExist`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::BufReader;
+    /// use hpo::Ontology;
+    ///
+    /// let obo = "format-version: 1.2\r
+    /// data-version: hp/releases/2023-04-05\r
+    /// \r
+    /// [Term]\r
+    /// id: HP:0000001\r
+    /// name: All\r
+    /// \r
+    /// [Term]\r
+    /// id: HP:0000118\r
+    /// name: Phenotypic abnormality\r
+    /// is_a: HP:0000001 ! All\r
+    /// ";
+    ///
+    /// let ontology = Ontology::from_obo(BufReader::new(obo.as_bytes())).unwrap();
+    /// assert_eq!(ontology.len(), 2);
+    /// assert_eq!(ontology.hpo(118u32).unwrap().name(), "Phenotypic abnormality");
+    /// ```
+    pub fn from_obo<R: BufRead>(reader: R) -> HpoResult<Self> {
+        Builder::new()
+            .add_obo(reader)?
+            .connect_all_terms()
+            .calculate_information_content()?
+            .build_with_defaults()
+    }
+
+    /// Initialize the [`Ontology`] from OBO data and skip invalid data
+    ///
+    /// This method is identical to [`Ontology::from_obo`], but invalid lines
+    /// are skipped and reported in the returned [`ParseReport`] instead
+    /// of failing.
+    ///
+    /// # Errors
+    ///
+    /// - the data can't be read: [`HpoError::ParseOboError`]
+    /// - the data does not contain the terms `HP:0000001` and `HP:0000118`: [`HpoError::DoesNotExist`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::Ontology;
+    ///
+    /// let obo = "[Term]
+    /// id: HP:0000001
+    /// name: All
+    ///
+    /// [Term]
+    /// id: HP:0000118
+    /// name: Phenotypic abnormality
+    /// is_a: HP:0000001 ! All
+    /// xref: invalid
+    /// ";
+    ///
+    /// let (ontology, report) = Ontology::from_obo_lenient(obo.as_bytes()).unwrap();
+    /// assert_eq!(ontology.len(), 2);
+    /// assert_eq!(report.len(), 1);
+    /// ```
+    pub fn from_obo_lenient<R: BufRead>(reader: R) -> HpoResult<(Self, ParseReport)> {
+        let (builder, report) = Builder::new().add_obo_lenient(reader)?;
+        let ontology = builder
+            .connect_all_terms()
+            .calculate_information_content()?
+            .build_with_defaults()?;
+        Ok((ontology, report))
     }
 
     /// Build an Ontology from a binary data blob
@@ -342,20 +528,21 @@ impl Ontology {
     /// and ensures proper inheritance of gene/disease annotations.
     /// It also calculates the `InformationContent` for every term.
     ///
+    /// With the `gzip` feature, the file can also be gzip-compressed.
+    ///
     /// # Errors
     ///
     /// This method can fail for various reasons:
     ///
     /// - Binary file not available or readable: [`HpoError::CannotOpenFile`]
+    /// - Truncated data or a section is incomplete: [`HpoError::BinarySectionError`],
+    ///   with the affected section and the expected and actual length
+    /// - The checksum does not match (version 4 and newer): [`HpoError::BinaryChecksumError`]
+    /// - A required section is missing: [`HpoError::InvalidInput`]
     /// - Invalid data provided: [`HpoError::ParseBinaryError`]
     /// - Invalid binary version: [`HpoError::NotImplemented`]
     /// - Invalid reference to terms: [`HpoError::DoesNotExist`]
     ///
-    /// # Panics
-    ///
-    /// The method can panic if the provided data is incorrectly formatted or
-    /// contains invalid references between terms, genes or diseases
-    ///
     /// # Examples
     ///
     /// ```
@@ -373,28 +560,11 @@ impl Ontology {
     /// assert_eq!(root_term.name(), "All");
     /// ```
     pub fn from_binary<P: AsRef<Path>>(filename: P) -> HpoResult<Self> {
-        let bytes = match File::open(filename) {
-            Ok(mut file) => {
-                let len = file
-                    .metadata()
-                    .map_err(|_| {
-                        HpoError::CannotOpenFile(
-                            "unable to get filesize of binary file".to_string(),
-                        )
-                    })?
-                    .len();
-                let mut bytes = Vec::with_capacity(len.try_into()?);
-                file.read_to_end(&mut bytes).map_err(|_| {
-                    HpoError::CannotOpenFile("unable to read from binary file".to_string())
-                })?;
-                bytes
-            }
-            Err(_) => {
-                return Err(crate::HpoError::CannotOpenFile(
-                    "unable to open binary file".to_string(),
-                ))
-            }
-        };
+        let mut reader = parser::open_file(filename)?;
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|_| HpoError::CannotOpenFile("unable to read from binary file".to_string()))?;
         Self::from_bytes(&bytes)
     }
 
@@ -410,15 +580,14 @@ impl Ontology {
     ///
     /// This method can fail for various reasons:
     ///
+    /// - Truncated data or a section is incomplete: [`HpoError::BinarySectionError`],
+    ///   with the affected section and the expected and actual length
+    /// - The checksum does not match (version 4 and newer): [`HpoError::BinaryChecksumError`]
+    /// - A required section is missing: [`HpoError::InvalidInput`]
     /// - Invalid data provided: [`HpoError::ParseBinaryError`]
     /// - Invalid binary version: [`HpoError::NotImplemented`]
     /// - Invalid reference to terms: [`HpoError::DoesNotExist`]
     ///
-    /// # Panics
-    ///
-    /// The method can panic if the provided data is incorrectly formatted or
-    /// contains invalid references between terms, genes or diseases
-    ///
     /// # Examples
     ///
     /// ```
@@ -441,58 +610,99 @@ impl Ontology {
     /// assert_eq!(root_term.name(), "All");
     /// ```
     pub fn from_bytes(bytes: &[u8]) -> HpoResult<Self> {
-        let bytes = parser::binary::ontology::version(bytes)?;
-        debug!("Parsing from bytes v{}", bytes.version());
+        let data = parser::binary::ontology::version(bytes)?;
+        debug!("Parsing from bytes v{}", data.version());
+
+        if data.version() > BinaryVersion::V3 {
+            return Self::from_section_directory(bytes, &data);
+        }
 
         let mut builder = Builder::new();
+        let mut start = bytes.len() - data.len() + builder.hpo_version_from_bytes(&data)?;
 
-        let offset = builder.hpo_version_from_bytes(&bytes)?;
+        let terms = length_prefixed_section(bytes, &mut start, Section::Terms)?;
+        builder.add_terms_from_bytes(Bytes::new(terms, data.version()))?;
+        let mut builder = builder.terms_complete();
+
+        let parents = length_prefixed_section(bytes, &mut start, Section::Parents)?;
+        builder.add_parent_from_bytes(parents)?;
+        let mut builder = builder.connect_all_terms();
+
+        let genes = length_prefixed_section(bytes, &mut start, Section::Genes)?;
+        builder.add_genes_from_bytes(genes)?;
+
+        let diseases = length_prefixed_section(bytes, &mut start, Section::OmimDiseases)?;
+        builder.add_omim_disease_from_bytes(diseases)?;
+
+        if data.version() > BinaryVersion::V2 {
+            let diseases = length_prefixed_section(bytes, &mut start, Section::OrphaDiseases)?;
+            builder.add_orpha_disease_from_bytes(diseases)?;
+        }
 
-        let mut section_start = offset;
-        let mut section_end: usize;
+        if start != bytes.len() {
+            return Err(HpoError::BinarySectionError {
+                section: "end of data".to_string(),
+                expected: start,
+                actual: bytes.len(),
+            });
+        }
+        builder
+            .calculate_information_content()?
+            .build_with_defaults()
+    }
 
-        // Terms
-        let mut section_len = u32_from_bytes(&bytes[section_start..]) as usize;
-        section_end = section_start + 4 + section_len;
-        builder.add_terms_from_bytes(bytes.subset(section_start + 4..section_end));
-        section_start += section_len + 4;
+    /// Builds an Ontology from binary data with a section directory (version 4 and newer)
+    ///
+    /// `bytes` is the complete binary data, `data` the data after the binary version.
+    /// The checksum is verified before any section is parsed.
+    /// Sections are processed in the order required by the [`Builder`],
+    /// independent of their position in the binary data. Missing annotation
+    /// sections are treated as empty, unknown sections are skipped.
+    /// The information content is only calculated if it is not part of the data.
+    fn from_section_directory(bytes: &[u8], data: &Bytes) -> HpoResult<Self> {
+        let mut builder = Builder::new();
+        let start = bytes.len() - data.len() + builder.hpo_version_from_bytes(data)?;
+        verify_checksum(bytes, start)?;
+        let sections = SectionDirectory::from_bytes(bytes, start)?;
+
+        let required = |section: Section| {
+            sections.get(section).ok_or_else(|| {
+                HpoError::InvalidInput(format!(
+                    "binary data does not contain the {} section",
+                    section.name()
+                ))
+            })
+        };
 
+        builder.add_terms_from_bytes(Bytes::new(required(Section::Terms)?, data.version()))?;
         let mut builder = builder.terms_complete();
 
-        // Term - Parents
-        section_len = u32_from_bytes(&bytes[section_start..]) as usize;
-        section_end += 4 + section_len;
-        builder.add_parent_from_bytes(&bytes[section_start + 4..section_end]);
+        builder.add_parent_from_bytes(required(Section::Parents)?)?;
         let mut builder = builder.connect_all_terms();
-        section_start += section_len + 4;
-
-        // Genes
-        section_len = u32_from_bytes(&bytes[section_start..]) as usize;
-        section_end += 4 + section_len;
-        builder.add_genes_from_bytes(&bytes[section_start + 4..section_end])?;
-        section_start += section_len + 4;
-
-        // Omim Diseases
-        section_len = u32_from_bytes(&bytes[section_start..]) as usize;
-        section_end += 4 + section_len;
-        builder.add_omim_disease_from_bytes(&bytes[section_start + 4..section_end])?;
-        section_start += section_len + 4;
-
-        // Orpha Diseases
-        if bytes.version() > BinaryVersion::V2 {
-            section_len = u32_from_bytes(&bytes[section_start..]) as usize;
-            section_end += 4 + section_len;
-            builder.add_orpha_disease_from_bytes(&bytes[section_start + 4..section_end])?;
-            section_start += section_len + 4;
-        }
 
-        if section_start == bytes.len() {
-            builder
-                .calculate_information_content()?
-                .build_with_defaults()
-        } else {
-            Err(HpoError::ParseBinaryError)
+        if let Some(genes) = sections.get(Section::Genes) {
+            builder.add_genes_from_bytes(genes)?;
+        }
+        if let Some(diseases) = sections.get(Section::OmimDiseases) {
+            builder.add_omim_disease_from_bytes(diseases)?;
+        }
+        if let Some(diseases) = sections.get(Section::OrphaDiseases) {
+            builder.add_orpha_disease_from_bytes(diseases)?;
+        }
+        if let Some(diseases) = sections.get(Section::DecipherDiseases) {
+            builder.add_decipher_disease_from_bytes(diseases)?;
         }
+        if let Some(metadata) = sections.get(Section::Metadata) {
+            builder.metadata_from_bytes(metadata)?;
+        }
+
+        let builder = match sections.get(Section::InformationContent) {
+            Some(information_content) => {
+                builder.information_content_from_bytes(information_content)?
+            }
+            None => builder.calculate_information_content()?,
+        };
+        builder.build_with_defaults()
     }
 
     /// Returns the number of HPO-Terms in the Ontology
@@ -538,6 +748,95 @@ impl Ontology {
         HpoTerm::try_new(self, term_id).ok()
     }
 
+    /// Resolves an [`HpoTermId`] to the matching [`HpoTerm`]
+    ///
+    /// In contrast to [`Ontology::hpo`], this method also finds terms by
+    /// their alternative IDs (`alt_id`) and follows the replacement of
+    /// obsolete terms. The returned [`ResolvedTerm`] reports
+    /// whether the ID is a primary ID, an alternative ID or belongs
+    /// to an obsolete term with a replacement.
+    ///
+    /// If no term matches the ID, `None` is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::Ontology;
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// let resolved = ontology.resolve(11017u32).unwrap();
+    /// assert!(resolved.is_primary());
+    /// assert_eq!(resolved.term().name(), "Abnormal cellular physiology");
+    ///
+    /// assert!(ontology.resolve(66666u32).is_none());
+    /// ```
+    pub fn resolve<I: Into<HpoTermId>>(&'_ self, term_id: I) -> Option<ResolvedTerm<'_>> {
+        let term_id = term_id.into();
+        if let Some(term) = self.hpo(term_id) {
+            return match term.replaced_by() {
+                Some(replacement) if term.is_obsolete() => Some(ResolvedTerm::Obsolete {
+                    obsolete: term,
+                    replacement,
+                }),
+                _ => Some(ResolvedTerm::Primary(term)),
+            };
+        }
+        self.hpo_terms
+            .get_by_alt_id(term_id)
+            .map(|term| ResolvedTerm::Alternative(HpoTerm::new(self, term)))
+    }
+
+    /// Returns all [`HpoTerm`]s that have the given cross-reference
+    ///
+    /// The cross-reference must be provided as `<prefix>:<id>`,
+    /// e.g. `UMLS:C0011334` or `SNOMEDCT_US:87715008`. Cross-references
+    /// are only available if the ontology was built from
+    /// an `hp.obo` file or from a binary file (version 4 or newer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::Ontology;
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// for term in &ontology.hpo_by_xref("UMLS:C0011334") {
+    ///     println!("{}", term.name());
+    /// }
+    /// ```
+    pub fn hpo_by_xref(&self, xref: &str) -> Combined<'_> {
+        let group = Xref::try_from(xref)
+            .ok()
+            .and_then(|xref| self.hpo_terms.get_by_xref(&xref))
+            .cloned()
+            .unwrap_or_default();
+        Combined::new(group, self)
+    }
+
+    /// Returns all [`HpoTerm`]s that belong to the subset `name`
+    ///
+    /// HPO defines subsets (e.g. `hposlim_core`) in the `hp.obo` file. Subsets
+    /// are only available if the ontology was built from
+    /// an `hp.obo` file or from a binary file (version 4 or newer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::Ontology;
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// for term in &ontology.subset_terms("hposlim_core") {
+    ///     println!("{}", term.name());
+    /// }
+    /// ```
+    pub fn subset_terms(&self, name: &str) -> Combined<'_> {
+        let group = self
+            .hpo_terms
+            .get_by_subset(name)
+            .cloned()
+            .unwrap_or_default();
+        Combined::new(group, self)
+    }
+
     /// Returns a reference to the [`Gene`] of the provided [`GeneId`]
     ///
     /// If no such gene is present, `None` is returned
@@ -711,6 +1010,45 @@ impl Ontology {
         self.orpha_diseases.values()
     }
 
+    /// Returns a reference to the [`DecipherDisease`] of the provided [`DecipherDiseaseId`]
+    ///
+    /// If no such disease is present, `None` is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::Ontology;
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    /// // The example ontology does not contain any DECIPHER diseases
+    /// assert!(ontology.decipher_disease(&18u32.into()).is_none());
+    /// ```
+    pub fn decipher_disease(
+        &self,
+        decipher_disease_id: &DecipherDiseaseId,
+    ) -> Option<&DecipherDisease> {
+        self.decipher_diseases.get(decipher_disease_id)
+    }
+
+    /// Returns an Iterator of all [`DecipherDisease`]s from the Ontology
+    ///
+    /// It is likely that the return type will change to a dedicated Iterator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::Ontology;
+    /// use hpo::annotations::Disease;
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    /// for disease in ontology.decipher_diseases() {
+    ///     println!("{}", disease.name());
+    /// }
+    /// ```
+    pub fn decipher_diseases(
+        &self,
+    ) -> std::collections::hash_map::Values<'_, DecipherDiseaseId, DecipherDisease> {
+        self.decipher_diseases.values()
+    }
+
     /// Returns the Jax-Ontology release version
     ///
     /// e.g. `2023-03-13`
@@ -721,6 +1059,13 @@ impl Ontology {
         )
     }
 
+    /// Returns the metadata of the ontology release and its annotation sources
+    ///
+    /// See [`OntologyMetadata`] for details
+    pub fn metadata(&self) -> &OntologyMetadata {
+        &self.metadata
+    }
+
     /// Compares `self` to another `Ontology` to identify added/removed terms, genes and diseases
     ///
     /// # Examples
@@ -813,25 +1158,37 @@ impl Ontology {
 
     /// Returns a binary representation of the Ontology
     ///
-    /// The binary data is separated into sections:
+    /// The binary data starts with a header (see `Ontology::metadata_as_bytes`),
+    /// followed by a CRC-32 checksum, a section directory and the data of all sections
+    /// (see `parser::binary::ontology::with_sections`). Every entry of the
+    /// section directory contains the type id, offset and length of the section,
+    /// so that readers can skip sections they don't know.
+    ///
+    /// The data is separated into sections:
     ///
-    /// - Metadata (HPO and Bindat Version) (see `Ontology::metadata_as_bytes`)
     /// - Terms (Names + IDs) (see `HpoTermInternal::as_bytes`)
     /// - Term - Parent connection (Child ID - Parent ID)
     ///   (see `HpoTermInternal::parents_as_byte`)
     /// - Genes (Names + IDs + Connected HPO Terms) ([`Gene::as_bytes`])
     /// - OMIM Diseases (Names + IDs + Connected HPO Terms)
     ///   ([`OmimDisease::as_bytes`])
-    ///
-    /// Every section starts with 4 bytes to indicate its size
-    /// (big-endian encoded `u32`)
+    /// - ORPHA Diseases (Names + IDs + Connected HPO Terms)
+    ///   ([`OrphaDisease::as_bytes`](`Disease::as_bytes`))
+    /// - DECIPHER Diseases (Names + IDs + Connected HPO Terms)
+    ///   ([`DecipherDisease::as_bytes`](`Disease::as_bytes`))
+    /// - Metadata of the release and annotation sources (see [`OntologyMetadata`])
+    /// - Information content of all terms, including the custom information content
+    ///   (see [`InformationContent`](`crate::term::InformationContent`))
+    /// - An index of all terms, sorted by ID, and the ancestors of all terms
+    ///   (see `HpoTermInternal::ancestors_as_byte`). They are not needed to build
+    ///   the Ontology, but allow direct access to the data, e.g. with `OntologyView`
     ///
     /// This method is only useful if you use are modifying the ontology
     /// and want to save data for later re-use.
     ///
     /// # Panics
     ///
-    /// Panics when the buffer length of any subsegment larger than `u32::MAX`
+    /// Panics when the binary data is larger than `u32::MAX`
     ///
     /// # Examples
     ///
@@ -841,60 +1198,14 @@ impl Ontology {
     /// let bytes = ontology.as_bytes();
     /// ```
     pub fn as_bytes(&self) -> Vec<u8> {
-        fn usize_to_u32(n: usize) -> u32 {
-            n.try_into().expect("unable to convert {n} to u32")
-        }
-        let mut res = Vec::new();
-
-        // Add metadata, version info
-        res.append(&mut self.metadata_as_bytes());
-
-        // All HPO Terms
-        let mut buffer = Vec::new();
-        for term in self.hpo_terms.values() {
-            buffer.append(&mut term.as_bytes());
-        }
-        res.append(&mut usize_to_u32(buffer.len()).to_be_bytes().to_vec());
-        res.append(&mut buffer);
-
-        // All Term - Parent connections
-        buffer.clear();
-        for term in self.hpo_terms.values() {
-            buffer.append(&mut term.parents_as_byte());
-        }
-        res.append(&mut usize_to_u32(buffer.len()).to_be_bytes().to_vec());
-        res.append(&mut buffer);
-
-        // Genes and Gene-Term connections
-        buffer.clear();
-        for gene in self.genes.values() {
-            buffer.append(&mut gene.as_bytes());
-        }
-        res.append(&mut usize_to_u32(buffer.len()).to_be_bytes().to_vec());
-        res.append(&mut buffer);
-
-        // OMIM Disease and Disease-Term connections
-        buffer.clear();
-        for omim_disease in self.omim_diseases.values() {
-            buffer.append(&mut omim_disease.as_bytes());
-        }
-        res.append(&mut usize_to_u32(buffer.len()).to_be_bytes().to_vec());
-        res.append(&mut buffer);
-
-        // ORPHA Disease and Disease-Term connections
-        buffer.clear();
-        for orpha_disease in self.orpha_diseases.values() {
-            buffer.append(&mut orpha_disease.as_bytes());
-        }
-        res.append(&mut usize_to_u32(buffer.len()).to_be_bytes().to_vec());
-        res.append(&mut buffer);
-
-        res
+        parser::binary::ontology::with_sections(self.metadata_as_bytes(), &self.sections_as_bytes())
     }
 
     /// Constructs a smaller ontology that contains only the `leaves` terms and
     /// all terms needed to connect to each leaf to `root`
     ///
+    /// The new ontology keeps the HPO version and the [`OntologyMetadata`] of `self`.
+    ///
     /// # Errors
     ///
     /// Fails if `root` is not an ancestor of all leaves
@@ -934,11 +1245,20 @@ impl Ontology {
         let ids: HpoGroup = terms.iter().map(|term| *term.id()).collect();
 
         let mut builder = Builder::new();
+        builder.set_hpo_version(self.hpo_version);
+        *builder.metadata_mut() = self.metadata.clone();
 
         for &term in &terms {
             let mut copied_term = HpoTermInternal::new(term.name().to_string(), *term.id());
             *copied_term.obsolete_mut() = term.obsolete();
             *copied_term.replacement_mut() = term.replacement();
+            *copied_term.synonyms_mut() = term.synonyms().to_vec();
+            *copied_term.definition_mut() = term.definition().cloned();
+            *copied_term.comment_mut() = term.comment().map(str::to_string);
+            *copied_term.alt_ids_mut() = term.alt_ids().to_vec();
+            *copied_term.xrefs_mut() = term.xrefs().to_vec();
+            *copied_term.consider_mut() = term.consider().to_vec();
+            *copied_term.subsets_mut() = term.subsets().to_vec();
             builder.add_term(copied_term);
         }
 
@@ -976,39 +1296,87 @@ impl Ontology {
             for term in &(gene.hpo_terms() & &ids) {
                 builder.annotate_gene(*gene.id(), gene.name(), term)?;
             }
-        }
-
-        // Iterate all Omim diseases
-        for omim_disease in self.omim_diseases() {
-            // check if the omim_disease is linked to any phenotype terms
-            // --> don't include modifier terms here
-            if (omim_disease.hpo_terms() & &phenotype_ids).is_empty() {
-                continue;
+            for omim_id in gene.omim_diseases() {
+                builder.link_gene_omim_disease(*gene.id(), *omim_id);
             }
-
-            // Link the omim_disease to every term in the new ontology
-            // --> also modifier terms
-            for term in &(omim_disease.hpo_terms() & &ids) {
-                builder.annotate_omim_disease(*omim_disease.id(), omim_disease.name(), term)?;
+            for orpha_id in gene.orpha_diseases() {
+                builder.link_gene_orpha_disease(*gene.id(), *orpha_id);
             }
         }
 
-        // Iterate all Orpha diseases
-        for orpha_disease in self.orpha_diseases() {
-            // check if the orpha_disease is linked to any phenotype terms
-            // --> don't include modifier terms here
-            if (orpha_disease.hpo_terms() & &phenotype_ids).is_empty() {
-                continue;
-            }
+        self.copy_diseases(&mut builder, &ids, &phenotype_ids)?;
 
-            // Link the orpha_disease to every term in the new ontology
-            // --> also modifier terms
-            for term in &(orpha_disease.hpo_terms() & &ids) {
-                builder.annotate_orpha_disease(*orpha_disease.id(), orpha_disease.name(), term)?;
-            }
+        Ok(builder.calculate_information_content()?.build_minimal())
+    }
+
+    /// Constructs a slim ontology that contains only the terms of the subset `name`
+    ///
+    /// The slim also contains all terms that are needed to connect
+    /// the subset terms to the root term `HP:0000001`.
+    /// See [`Ontology::sub_ontology`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// - `DoesNotExist` if the ontology does not contain the root term
+    /// - `InvalidInput` if no term belongs to the subset
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::Ontology;
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// // The example ontology does not contain subsets
+    /// assert!(ontology.subset_ontology("hposlim_core").is_err());
+    /// ```
+    pub fn subset_ontology(&self, name: &str) -> HpoResult<Self> {
+        let root = self.hpo(1u32).ok_or(HpoError::DoesNotExist)?;
+        let terms = self.subset_terms(name);
+        if terms.is_empty() {
+            return Err(HpoError::InvalidInput(format!("no terms in subset {name}")));
         }
+        self.sub_ontology(root, &terms)
+    }
 
-        Ok(builder.calculate_information_content()?.build_minimal())
+    /// Writes the ontology as valid OBO 1.2 data, e.g. to create an `hp.obo` file
+    ///
+    /// The header contains the release information from [`Ontology::metadata`]. Without
+    /// metadata, the `data-version` is derived from [`Ontology::hpo_version`].
+    /// Every term is written as a `[Term]` stanza with its ID, name, alternative IDs,
+    /// definition, comment, subsets, synonyms, cross-references, `is_a` parents,
+    /// obsolete state, `consider` terms and replacement.
+    ///
+    /// This is useful to consume trimmed or custom-built ontologies, e.g. from
+    /// [`Ontology::sub_ontology`], with other OBO tools. The written data can
+    /// be loaded again with [`Ontology::from_obo`].
+    ///
+    /// Writing to a file is much faster if the file is wrapped in a [`std::io::BufWriter`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `writer` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::Ontology;
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    /// let sub = ontology.sub_ontology(
+    ///     ontology.hpo(1u32).unwrap(),
+    ///     vec![ontology.hpo(11017u32).unwrap()]
+    /// ).unwrap();
+    ///
+    /// let mut obo = Vec::new();
+    /// sub.write_obo(&mut obo).unwrap();
+    ///
+    /// let obo = String::from_utf8(obo).unwrap();
+    /// assert!(obo.starts_with("format-version: 1.2\n"));
+    /// assert!(obo.contains("id: HP:0011017\nname: Abnormal cellular physiology\n"));
+    /// ```
+    pub fn write_obo<W: std::io::Write>(&self, writer: W) -> std::io::Result<()> {
+        parser::hp_obo::write_obo(self, writer)
     }
 
     /// Returns the code to create a `Mermaid` flow diagram
@@ -1146,6 +1514,86 @@ impl Ontology {
         Ok(())
     }
 
+    /// Returns the binary representation of all sections of the Ontology,
+    /// in the order of [`Section::ALL`]
+    fn sections_as_bytes(&self) -> Vec<(u32, Vec<u8>)> {
+        let [mut terms, mut parents, mut ancestors, mut index] = self.term_sections_as_bytes();
+        Section::ALL
+            .into_iter()
+            .map(|section| {
+                let buffer = match section {
+                    Section::Terms => std::mem::take(&mut terms),
+                    Section::Parents => std::mem::take(&mut parents),
+                    Section::TermIndex => std::mem::take(&mut index),
+                    Section::Ancestors => std::mem::take(&mut ancestors),
+                    Section::Genes => self.genes.values().flat_map(Gene::as_bytes).collect(),
+                    Section::OmimDiseases => self
+                        .omim_diseases
+                        .values()
+                        .flat_map(OmimDisease::as_bytes)
+                        .collect(),
+                    Section::OrphaDiseases => self
+                        .orpha_diseases
+                        .values()
+                        .flat_map(OrphaDisease::as_bytes)
+                        .collect(),
+                    Section::DecipherDiseases => self
+                        .decipher_diseases
+                        .values()
+                        .flat_map(DecipherDisease::as_bytes)
+                        .collect(),
+                    Section::Metadata => self.metadata.as_bytes(),
+                    Section::InformationContent => {
+                        let mut buffer = Vec::with_capacity(
+                            INFORMATION_CONTENT_ENTRY_LEN * self.hpo_terms.len(),
+                        );
+                        for term in self.hpo_terms.values() {
+                            buffer.extend_from_slice(&term.id().to_be_bytes());
+                            buffer.extend_from_slice(&term.information_content().as_bytes());
+                        }
+                        buffer
+                    }
+                };
+                (section.id(), buffer)
+            })
+            .collect()
+    }
+
+    /// Returns the terms, parents, ancestors and term index sections
+    ///
+    /// The term index contains the offsets of every term in the other
+    /// three sections, so they are written together.
+    ///
+    /// # Panics
+    ///
+    /// Panics when one of the sections is larger than `u32::MAX`
+    fn term_sections_as_bytes(&self) -> [Vec<u8>; 4] {
+        let mut terms = Vec::new();
+        let mut parents = Vec::new();
+        let mut ancestors = Vec::new();
+        let mut offsets = Vec::with_capacity(self.hpo_terms.len());
+        for term in self.hpo_terms.values() {
+            offsets.push((
+                term.id().as_u32(),
+                [terms.len(), parents.len(), ancestors.len()],
+            ));
+            terms.append(&mut term.as_bytes());
+            parents.append(&mut term.parents_as_byte());
+            ancestors.append(&mut term.ancestors_as_byte());
+        }
+
+        offsets.sort_unstable_by_key(|(id, _)| *id);
+        let mut index = Vec::with_capacity(TERM_INDEX_ENTRY_LEN * offsets.len());
+        for (id, term_offsets) in offsets {
+            index.extend_from_slice(&id.to_be_bytes());
+            for offset in term_offsets {
+                let offset: u32 = offset.try_into().expect("term section too large");
+                index.extend_from_slice(&offset.to_be_bytes());
+            }
+        }
+        [terms, parents, ancestors, index]
+    }
+
     /// Returns a binary representation of the Ontology's metadata
     ///
     /// It adds the HPO-identifying bytes `HPO`, the version
@@ -1156,7 +1604,7 @@ impl Ontology {
         bytes.extend_from_slice(&[0x48, 0x50, 0x4f]);
 
         // Version
-        bytes.push(0x3);
+        bytes.push(0x4);
 
         bytes.extend_from_slice(&self.hpo_version.0.to_be_bytes()[..]);
         bytes.push(self.hpo_version.1);
@@ -1164,6 +1612,118 @@ impl Ontology {
         bytes
     }
 
+    /// Adds all diseases that are linked to any of the `phenotype_ids` to the builder
+    ///
+    /// The diseases are connected to all terms of `ids`, including their
+    /// annotation details and excluded terms. This is used to build a
+    /// [`Ontology::sub_ontology`].
+    fn copy_diseases(
+        &self,
+        builder: &mut Builder<ConnectedTerms>,
+        ids: &HpoGroup,
+        phenotype_ids: &HpoGroup,
+    ) -> HpoResult<()> {
+        // Iterate all Omim diseases
+        for omim_disease in self.omim_diseases() {
+            // check if the omim_disease is linked to any phenotype terms
+            // --> don't include modifier terms here
+            if (omim_disease.hpo_terms() & phenotype_ids).is_empty() {
+                continue;
+            }
+
+            // Link the omim_disease to every term in the new ontology
+            // --> also modifier terms
+            for term in &(omim_disease.hpo_terms() & ids) {
+                if let Some(annotation) = omim_disease.annotation(term) {
+                    builder.add_omim_disease_annotation(
+                        *omim_disease.id(),
+                        omim_disease.name(),
+                        annotation.clone(),
+                    )?;
+                } else {
+                    builder.annotate_omim_disease(*omim_disease.id(), omim_disease.name(), term)?;
+                }
+                if let Some(frequency) = omim_disease.term_frequency(term) {
+                    builder.set_omim_disease_frequency(*omim_disease.id(), term, frequency)?;
+                }
+            }
+            for term in &(omim_disease.excluded_terms() & ids) {
+                builder.exclude_omim_disease_term(*omim_disease.id(), term)?;
+            }
+        }
+
+        // Iterate all Orpha diseases
+        for orpha_disease in self.orpha_diseases() {
+            // check if the orpha_disease is linked to any phenotype terms
+            // --> don't include modifier terms here
+            if (orpha_disease.hpo_terms() & phenotype_ids).is_empty() {
+                continue;
+            }
+
+            // Link the orpha_disease to every term in the new ontology
+            // --> also modifier terms
+            for term in &(orpha_disease.hpo_terms() & ids) {
+                if let Some(annotation) = orpha_disease.annotation(term) {
+                    builder.add_orpha_disease_annotation(
+                        *orpha_disease.id(),
+                        orpha_disease.name(),
+                        annotation.clone(),
+                    )?;
+                } else {
+                    builder.annotate_orpha_disease(
+                        *orpha_disease.id(),
+                        orpha_disease.name(),
+                        term,
+                    )?;
+                }
+                if let Some(frequency) = orpha_disease.term_frequency(term) {
+                    builder.set_orpha_disease_frequency(*orpha_disease.id(), term, frequency)?;
+                }
+            }
+            for term in &(orpha_disease.excluded_terms() & ids) {
+                builder.exclude_orpha_disease_term(*orpha_disease.id(), term)?;
+            }
+        }
+
+        // Iterate all Decipher diseases
+        for decipher_disease in self.decipher_diseases() {
+            // check if the decipher_disease is linked to any phenotype terms
+            // --> don't include modifier terms here
+            if (decipher_disease.hpo_terms() & phenotype_ids).is_empty() {
+                continue;
+            }
+
+            // Link the decipher_disease to every term in the new ontology
+            // --> also modifier terms
+            for term in &(decipher_disease.hpo_terms() & ids) {
+                if let Some(annotation) = decipher_disease.annotation(term) {
+                    builder.add_decipher_disease_annotation(
+                        *decipher_disease.id(),
+                        decipher_disease.name(),
+                        annotation.clone(),
+                    )?;
+                } else {
+                    builder.annotate_decipher_disease(
+                        *decipher_disease.id(),
+                        decipher_disease.name(),
+                        term,
+                    )?;
+                }
+                if let Some(frequency) = decipher_disease.term_frequency(term) {
+                    builder.set_decipher_disease_frequency(
+                        *decipher_disease.id(),
+                        term,
+                        frequency,
+                    )?;
+                }
+            }
+            for term in &(decipher_disease.excluded_terms() & ids) {
+                builder.exclude_decipher_disease_term(*decipher_disease.id(), term)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the `HpoTermInternal` with the given `HpoTermId`
     ///
     /// Returns `None` if no such term is present
@@ -1275,7 +1835,8 @@ mod test {
             let t = HpoTermInternal::new(String::from(name), id.into());
             v.append(&mut t.as_bytes());
         }
-        ont.add_terms_from_bytes(Bytes::new(&v, parser::binary::BinaryVersion::V1));
+        ont.add_terms_from_bytes(Bytes::new(&v, parser::binary::BinaryVersion::V1))
+            .unwrap();
         let ont = ont
             .terms_complete()
             .connect_all_terms()
@@ -1310,7 +1871,7 @@ mod test {
         let bytes = fake_term.parents_as_byte();
 
         let mut ont = ont.terms_complete();
-        ont.add_parent_from_bytes(&bytes[..]);
+        ont.add_parent_from_bytes(&bytes[..]).unwrap();
 
         let ont = ont
             .connect_all_terms()
@@ -1340,6 +1901,132 @@ mod test {
         );
     }
 
+    #[test]
+    fn resolve_terms() {
+        let mut ont = Builder::new();
+
+        let mut merged = HpoTermInternal::new(String::from("Merged"), 1u32.into());
+        merged.alt_ids_mut().push(10u32.into());
+        ont.add_term(merged);
+
+        let mut obsolete = HpoTermInternal::new(String::from("Obsolete"), 2u32.into());
+        *obsolete.obsolete_mut() = true;
+        *obsolete.replacement_mut() = Some(1u32.into());
+        ont.add_term(obsolete);
+
+        let mut orphaned = HpoTermInternal::new(String::from("Orphaned"), 3u32.into());
+        *orphaned.obsolete_mut() = true;
+        *orphaned.replacement_mut() = Some(20u32.into());
+        ont.add_term(orphaned);
+
+        let ont = ont
+            .terms_complete()
+            .connect_all_terms()
+            .calculate_information_content()
+            .expect("Test can calculate IC")
+            .build_minimal();
+
+        let resolved = ont.resolve(1u32).unwrap();
+        assert!(resolved.is_primary());
+        assert_eq!(resolved.term().name(), "Merged");
+
+        assert!(ont.hpo(10u32).is_none());
+        let resolved = ont.resolve(10u32).unwrap();
+        assert!(resolved.is_alternative());
+        assert_eq!(resolved.term().id(), HpoTermId::from(1u32));
+
+        let resolved = ont.resolve(2u32).unwrap();
+        assert!(resolved.is_obsolete());
+        assert_eq!(resolved.term().id(), HpoTermId::from(1u32));
+        let ResolvedTerm::Obsolete { obsolete, .. } = resolved else {
+            panic!("Term must be resolved as obsolete");
+        };
+        assert_eq!(obsolete.id(), HpoTermId::from(2u32));
+
+        // The replacement is not part of the ontology
+        assert!(ont.resolve(3u32).unwrap().is_primary());
+
+        assert!(ont.resolve(20u32).is_none());
+    }
+
+    #[test]
+    fn subset_ontology() {
+        let mut ont = Builder::new();
+
+        for (name, id) in [("All", 1u32), ("Parent", 2), ("Slim", 3), ("Other", 4)] {
+            let mut term = HpoTermInternal::new(String::from(name), id.into());
+            if id == 3 {
+                term.subsets_mut().push(String::from("slim"));
+            }
+            ont.add_term(term);
+        }
+
+        let mut ont = ont.terms_complete();
+        ont.add_parent(1u32, 2u32).unwrap();
+        ont.add_parent(2u32, 3u32).unwrap();
+        ont.add_parent(1u32, 4u32).unwrap();
+        let ont = ont
+            .connect_all_terms()
+            .calculate_information_content()
+            .expect("Test can calculate IC")
+            .build_minimal();
+
+        let slim = ont.subset_ontology("slim").unwrap();
+        assert_eq!(slim.len(), 3);
+        assert!(slim.hpo(2u32).is_some());
+        assert!(slim.hpo(4u32).is_none());
+        assert_eq!(slim.hpo(3u32).unwrap().subsets(), ["slim"]);
+
+        assert!(ont.subset_ontology("foobar").is_err());
+    }
+
+    #[test]
+    fn sub_ontology_keeps_metadata() {
+        let mut ont = Builder::new();
+        for (name, id) in [
+            ("All", 1u32),
+            ("Phenotypic abnormality", 118),
+            ("Slim", 2),
+            ("Other", 3),
+        ] {
+            let mut term = HpoTermInternal::new(String::from(name), id.into());
+            if id == 2 {
+                term.subsets_mut().push(String::from("slim"));
+            }
+            ont.add_term(term);
+        }
+        ont.metadata_mut()
+            .set_data_version("hp/releases/2024-04-26");
+        let mut annotations = AnnotationMetadata::new(crate::DISEASE_FILENAME);
+        annotations.add_property("version", "2024-04-26");
+        ont.metadata_mut().add_annotation(annotations);
+
+        let mut ont = ont.terms_complete();
+        ont.add_parent(1u32, 118u32).unwrap();
+        ont.add_parent(118u32, 2u32).unwrap();
+        ont.add_parent(118u32, 3u32).unwrap();
+        let ont = ont
+            .connect_all_terms()
+            .calculate_information_content()
+            .expect("Test can calculate IC")
+            .build_minimal();
+
+        let slim = ont.subset_ontology("slim").unwrap();
+        assert_eq!(slim.metadata(), ont.metadata());
+
+        let slim = Ontology::from_bytes(&slim.as_bytes()).unwrap();
+        assert_eq!(
+            slim.metadata().data_version(),
+            Some("hp/releases/2024-04-26")
+        );
+        assert_eq!(
+            slim.metadata()
+                .annotation(crate::DISEASE_FILENAME)
+                .and_then(AnnotationMetadata::version),
+            Some("2024-04-26")
+        );
+    }
+
     #[test]
     fn parse_hpo_version() {
         let mut ont = Builder::new();
@@ -1397,6 +2084,273 @@ mod test {
         assert_eq!(diff.changed_omim_diseases().len(), 0);
     }
 
+    #[test]
+    fn decipher_diseases_to_and_from_binary() {
+        let mut builder = Builder::new();
+        builder.new_term("All", 1u32);
+        builder.new_term("Phenotypic abnormality", 118u32);
+        builder.new_term("Foo", 2u32);
+        let mut builder = builder.terms_complete();
+        builder.add_parent(1u32, 118u32).unwrap();
+        builder.add_parent(118u32, 2u32).unwrap();
+        let mut builder = builder.connect_all_terms();
+        builder
+            .annotate_decipher_disease(18u32.into(), "Wolf-Hirschhorn Syndrome", 2u32.into())
+            .unwrap();
+        builder
+            .annotate_decipher_disease(19u32.into(), "Other Syndrome", 118u32.into())
+            .unwrap();
+        let ont = builder
+            .calculate_information_content()
+            .unwrap()
+            .build_with_defaults()
+            .unwrap();
+
+        let ont2 = Ontology::from_bytes(&ont.as_bytes()).unwrap();
+        let disease = ont2.decipher_disease(&18u32.into()).unwrap();
+        assert_eq!(disease.name(), "Wolf-Hirschhorn Syndrome");
+        assert_eq!(disease.id().to_string(), "DECIPHER:18");
+        assert_eq!(ont2.decipher_diseases().count(), 2);
+        assert_eq!(ont2.hpo(118u32).unwrap().decipher_diseases().count(), 2);
+        let ic = ont2
+            .hpo(2u32)
+            .unwrap()
+            .information_content()
+            .decipher_disease();
+        assert!((ic - 2f32.ln()).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn gene_diseases_to_and_from_binary() {
+        let mut builder = Builder::new();
+        builder.new_term("All", 1u32);
+        builder.new_term("Phenotypic abnormality", 118u32);
+        let mut builder = builder.terms_complete();
+        builder.add_parent(1u32, 118u32).unwrap();
+        let mut builder = builder.connect_all_terms();
+        builder
+            .annotate_gene(10u32.into(), "NAT2", 118u32.into())
+            .unwrap();
+        builder
+            .annotate_omim_disease(243_400u32.into(), "Disease 1", 118u32.into())
+            .unwrap();
+        assert!(builder.link_gene_omim_disease(10u32.into(), 243_400u32.into()));
+        // The disease is not part of the ontology
+        assert!(builder.link_gene_omim_disease(10u32.into(), 613_287u32.into()));
+        let ont = builder
+            .calculate_information_content()
+            .unwrap()
+            .build_with_defaults()
+            .unwrap();
+
+        let ont2 = Ontology::from_bytes(&ont.as_bytes()).unwrap();
+        let gene = ont2.gene(&10u32.into()).unwrap();
+        assert_eq!(gene.omim_diseases().len(), 1);
+        assert!(gene.omim_diseases().contains(&243_400u32.into()));
+        let disease = ont2.omim_disease(&243_400u32.into()).unwrap();
+        assert!(disease.genes().contains(&10u32.into()));
+    }
+
+    #[test]
+    fn metadata_to_and_from_binary() {
+        let obo = "format-version: 1.2
+data-version: hp/releases/custom
+date: 26:04:2024 10:22
+remark: some remark
+
+[Term]
+id: HP:0000001
+name: All
+
+[Term]
+id: HP:0000118
+name: Phenotypic abnormality
+is_a: HP:0000001 ! All
+";
+        let hpoa = "#version: 2024-04-26
+OMIM:5\tDisease 1\t\tHP:0000118\tPMID:1\tPCS
+";
+        let ont = StandardLoader::from_obo_reader(obo.as_bytes())
+            .diseases_reader(hpoa.as_bytes())
+            .load()
+            .unwrap();
+        assert_eq!(ont.hpo_version(), "0000-00-00");
+        assert_eq!(ont.metadata().data_version(), Some("hp/releases/custom"));
+
+        let ont2 = Ontology::from_bytes(&ont.as_bytes()).unwrap();
+        assert_eq!(ont2.metadata(), ont.metadata());
+        assert_eq!(ont2.metadata().date(), Some("26:04:2024 10:22"));
+        assert_eq!(ont2.metadata().remarks(), ["some remark"]);
+        assert_eq!(
+            ont2.metadata()
+                .annotation("phenotype.hpoa")
+                .and_then(AnnotationMetadata::version),
+            Some("2024-04-26")
+        );
+    }
+
+    #[test]
+    fn binary_skips_unknown_sections() {
+        let ont = Ontology::from_binary("tests/example.hpo").unwrap();
+
+        // Sections in reverse order, with an additional unknown section
+        let mut sections = ont.sections_as_bytes();
+        sections.reverse();
+        sections.insert(3, (999, vec![0xff; 17]));
+        let bytes = parser::binary::ontology::with_sections(ont.metadata_as_bytes(), &sections);
+
+        let ont2 = Ontology::from_bytes(&bytes).unwrap();
+        assert_eq!(ont2.len(), ont.len());
+        assert_eq!(ont2.genes().count(), ont.genes().count());
+        assert_eq!(ont2.omim_diseases().count(), ont.omim_diseases().count());
+        assert_eq!(ont2.as_bytes().len(), ont.as_bytes().len());
+    }
+
+    #[test]
+    fn binary_term_shorter_than_its_name() {
+        let ont = Ontology::from_binary("tests/example.hpo").unwrap();
+        let mut sections = ont.sections_as_bytes();
+        // The first term claims to be only 6 bytes long. `with_sections`
+        // calculates a new checksum, so only the term data is invalid
+        sections[0].1[0..4].copy_from_slice(&6u32.to_be_bytes());
+        let bytes = parser::binary::ontology::with_sections(ont.metadata_as_bytes(), &sections);
+
+        assert!(matches!(
+            Ontology::from_bytes(&bytes),
+            Err(HpoError::ParseBinaryError)
+        ));
+    }
+
+    #[test]
+    fn binary_without_annotation_sections() {
+        let ont = Ontology::from_binary("tests/example.hpo").unwrap();
+        let sections: Vec<(u32, Vec<u8>)> = ont
+            .sections_as_bytes()
+            .into_iter()
+            .filter(|(id, _)| [Section::Terms.id(), Section::Parents.id()].contains(id))
+            .collect();
+        let bytes = parser::binary::ontology::with_sections(ont.metadata_as_bytes(), &sections);
+
+        let ont2 = Ontology::from_bytes(&bytes).unwrap();
+        assert_eq!(ont2.len(), ont.len());
+        assert_eq!(ont2.genes().count(), 0);
+
+        let bytes =
+            parser::binary::ontology::with_sections(ont.metadata_as_bytes(), &sections[1..]);
+        assert!(matches!(
+            Ontology::from_bytes(&bytes),
+            Err(HpoError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn information_content_to_and_from_binary() {
+        let mut ont = Ontology::from_binary("tests/example.hpo").unwrap();
+        ont.custom_information_content(&[(118u32, 1.3), (1u32, 0.25)])
+            .unwrap();
+
+        let ont2 = Ontology::from_bytes(&ont.as_bytes()).unwrap();
+        for term in &ont {
+            let ic = term.information_content();
+            let term2 = ont2.hpo(term.id()).unwrap();
+            let ic2 = term2.information_content();
+            assert_eq!(ic.gene().to_bits(), ic2.gene().to_bits());
+            assert_eq!(ic.omim_disease().to_bits(), ic2.omim_disease().to_bits());
+            assert_eq!(ic.orpha_disease().to_bits(), ic2.orpha_disease().to_bits());
+            assert_eq!(
+                ic.decipher_disease().to_bits(),
+                ic2.decipher_disease().to_bits()
+            );
+            assert_eq!(ic.custom().to_bits(), ic2.custom().to_bits());
+        }
+        assert_eq!(
+            ont2.hpo(118u32)
+                .unwrap()
+                .information_content()
+                .custom()
+                .to_bits(),
+            1.3f32.to_bits()
+        );
+    }
+
+    #[test]
+    fn information_content_does_not_match_terms() {
+        let ont = Ontology::from_binary("tests/example.hpo").unwrap();
+        let mut sections = ont.sections_as_bytes();
+        sections
+            .last_mut()
+            .unwrap()
+            .1
+            .truncate(INFORMATION_CONTENT_ENTRY_LEN);
+        let bytes = parser::binary::ontology::with_sections(ont.metadata_as_bytes(), &sections);
+
+        let err = Ontology::from_bytes(&bytes).unwrap_err();
+        assert!(matches!(
+            err,
+            HpoError::BinarySectionError { section, expected, actual: 24 }
+                if section == "information content" && expected == ont.len() * 24
+        ));
+    }
+
+    #[test]
+    fn truncated_binary() {
+        let bytes = std::fs::read("tests/example.hpo").unwrap();
+        for len in (0..bytes.len()).step_by(bytes.len() / 40) {
+            assert!(Ontology::from_bytes(&bytes[..len]).is_err());
+        }
+
+        let err = Ontology::from_bytes(&bytes[..bytes.len() - 10]).unwrap_err();
+        assert!(matches!(
+            err,
+            HpoError::BinarySectionError { section, .. } if section == "ORPHA diseases"
+        ));
+
+        let bytes = Ontology::from_binary("tests/example.hpo")
+            .unwrap()
+            .as_bytes();
+        for len in (0..bytes.len()).step_by(bytes.len() / 40) {
+            assert!(Ontology::from_bytes(&bytes[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn corrupt_binary() {
+        let mut bytes = Ontology::from_binary("tests/example.hpo")
+            .unwrap()
+            .as_bytes();
+        let idx = bytes.len() / 2;
+        bytes[idx] = bytes[idx].wrapping_add(1);
+        assert!(matches!(
+            Ontology::from_bytes(&bytes),
+            Err(HpoError::BinaryChecksumError { .. })
+        ));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn gzip_binary() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let filename = std::env::temp_dir().join("hpo_test_gzip_binary.hpo");
+        let mut encoder = GzEncoder::new(
+            std::fs::File::create(&filename).unwrap(),
+            Compression::default(),
+        );
+        encoder
+            .write_all(&std::fs::read("tests/example.hpo").unwrap())
+            .unwrap();
+        encoder.finish().unwrap();
+
+        let ont = Ontology::from_binary(&filename);
+        std::fs::remove_file(&filename).unwrap();
+        let ont = ont.expect("the compressed file can be loaded");
+        let ont2 = Ontology::from_binary("tests/example.hpo").unwrap();
+        assert_eq!(ont.len(), ont2.len());
+        assert_eq!(ont.genes().count(), ont2.genes().count());
+    }
+
     #[test]
     fn diseases_by_name() {
         let ont = Ontology::from_binary("tests/example.hpo").unwrap();
@@ -1448,7 +2402,8 @@ mod test {
             let t = HpoTermInternal::new(name.into(), id.into());
             v.append(&mut t.as_bytes());
         }
-        ont.add_terms_from_bytes(Bytes::new(&v, parser::binary::BinaryVersion::V3));
+        ont.add_terms_from_bytes(Bytes::new(&v, parser::binary::BinaryVersion::V3))
+            .unwrap();
         let mut ont = ont.terms_complete();
 
         ont.add_parent_unchecked(1u32, 2u32);
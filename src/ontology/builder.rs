@@ -3,25 +3,34 @@
 //! In most cases, this is not recommended, use the
 //! built-in functions in [`Ontology`](`crate::Ontology`) instead.
 
-use crate::annotations::Disease;
+use crate::annotations::{Disease, DiseaseAnnotation, EvidenceCode};
 use crate::term::internal::HpoTermInternal;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::io::BufRead;
 use std::marker::PhantomData;
 use std::ops::BitOr;
 
+use crate::annotations::{DecipherDisease, DecipherDiseaseId};
 use crate::annotations::{Gene, GeneId};
 use crate::annotations::{OmimDisease, OmimDiseaseId};
 use crate::annotations::{OrphaDisease, OrphaDiseaseId};
-use crate::parser::binary::{BinaryTermBuilder, BinaryVersion, Bytes};
-use crate::term::HpoGroup;
+use crate::parser::binary::ontology::{Section, INFORMATION_CONTENT_ENTRY_LEN};
+use crate::parser::binary::{checked_slice, checked_u32, BinaryTermBuilder, BinaryVersion, Bytes};
+use crate::parser::hp_obo;
+pub use crate::parser::hp_obo::ParseReport;
+pub use crate::parser::{LoadIssue, LoadReport};
+use crate::term::{HpoGroup, InformationContent};
 
 use crate::HpoError;
 use crate::HpoResult;
-use crate::{u32_from_bytes, HpoTermId, Ontology};
+use crate::{u32_from_bytes, HpoTermId, Ontology, OntologyMetadata};
 
 use crate::ontology::termarena::Arena;
 
+mod loader;
+pub use loader::StandardLoader;
+
 /// State of [`Builder`] that only contains some 'loose', unconnected terms
 pub struct LooseCollection;
 
@@ -35,15 +44,40 @@ pub struct ConnectedTerms;
 /// State of [`Builder`] that contains all terms and all gene and disease annotation
 pub struct FullyAnnotated;
 
+/// Sets the frequency of a term in a disease and keeps the highest frequency
+fn set_frequency<D: Disease>(disease: &mut D, term_id: HpoTermId, frequency: f32) -> HpoResult<()> {
+    if !disease.hpo_terms().contains(&term_id) {
+        return Err(HpoError::DoesNotExist);
+    }
+    if !(0.0..=1.0).contains(&frequency) {
+        return Err(HpoError::InvalidInput(format!(
+            "invalid frequency {frequency} for {term_id}"
+        )));
+    }
+    if disease
+        .term_frequency(term_id)
+        .is_none_or(|current| frequency > current)
+    {
+        disease.set_term_frequency(term_id, frequency);
+    }
+    Ok(())
+}
+
 fn transition_state<TX, TY>(builder: Builder<TX>) -> Builder<TY> {
     Builder::<TY> {
         hpo_terms: builder.hpo_terms,
         genes: builder.genes,
         omim_diseases: builder.omim_diseases,
         orpha_diseases: builder.orpha_diseases,
+        decipher_diseases: builder.decipher_diseases,
         hpo_version: builder.hpo_version,
+        metadata: builder.metadata,
         categories: builder.categories,
         modifier: builder.modifier,
+        excluded_evidence: builder.excluded_evidence,
+        lenient_annotations: builder.lenient_annotations,
+        remap_obsolete: builder.remap_obsolete,
+        load_report: builder.load_report,
         state: PhantomData,
     }
 }
@@ -123,9 +157,15 @@ pub struct Builder<T> {
     genes: HashMap<GeneId, Gene>,
     omim_diseases: HashMap<OmimDiseaseId, OmimDisease>,
     orpha_diseases: HashMap<OrphaDiseaseId, OrphaDisease>,
+    decipher_diseases: HashMap<DecipherDiseaseId, DecipherDisease>,
     hpo_version: (u16, u8, u8),
+    metadata: OntologyMetadata,
     categories: HpoGroup,
     modifier: HpoGroup,
+    excluded_evidence: Vec<EvidenceCode>,
+    lenient_annotations: bool,
+    remap_obsolete: bool,
+    load_report: LoadReport,
     state: PhantomData<T>,
 }
 
@@ -146,9 +186,15 @@ impl Builder<LooseCollection> {
             genes: HashMap::default(),
             omim_diseases: HashMap::default(),
             orpha_diseases: HashMap::default(),
+            decipher_diseases: HashMap::default(),
             hpo_version: (0u16, 0u8, 0u8),
+            metadata: OntologyMetadata::default(),
             categories: HpoGroup::default(),
             modifier: HpoGroup::default(),
+            excluded_evidence: Vec::new(),
+            lenient_annotations: false,
+            remap_obsolete: false,
+            load_report: LoadReport::default(),
             state: PhantomData,
         }
     }
@@ -163,10 +209,16 @@ impl Builder<LooseCollection> {
     /// like parent-child connection etc.
     ///
     /// See [`HpoTermInternal::as_bytes`] for explanation of the binary layout.
-    pub(crate) fn add_terms_from_bytes(&mut self, bytes: Bytes) {
+    ///
+    /// # Errors
+    ///
+    /// - Incomplete data: [`HpoError::BinarySectionError`]
+    /// - Invalid term data: [`HpoError::ParseBinaryError`]
+    pub(crate) fn add_terms_from_bytes(&mut self, bytes: Bytes) -> HpoResult<()> {
         for term in BinaryTermBuilder::new(bytes) {
-            self.add_term(term);
+            self.add_term(term?);
         }
+        Ok(())
     }
 
     /// Insert an `HpoTermInternal` to the ontology
@@ -210,6 +262,151 @@ impl Builder<LooseCollection> {
         self.add_term(term);
     }
 
+    /// Adds all terms and their parent-child connections from OBO data
+    ///
+    /// The data is read stanza by stanza from any [`BufRead`], e.g. a file,
+    /// an in-memory buffer or stdin. This method parses all `[Term]` stanzas
+    /// and the ontology version from the header. Both Unix and Windows line
+    /// endings are supported.
+    ///
+    /// Since the OBO data contains all terms of the ontology, the `Builder`
+    /// transitions to `Builder<AllTerms>` and already contains all
+    /// parent-child connections.
+    ///
+    /// Use [`Builder::add_obo_lenient`] to skip invalid data instead of failing.
+    ///
+    /// # Errors
+    ///
+    /// [`HpoError::ParseOboError`] if the data can't be read or contains
+    /// invalid lines, e.g. invalid term IDs or references to non-existing parent terms.
+    /// Invalid synonyms, xrefs and definitions are skipped with a warning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::builder::Builder;
+    ///
+    /// let obo = "format-version: 1.2
+    /// data-version: hp/releases/2023-04-05
+    ///
+    /// [Term]
+    /// id: HP:0000001
+    /// name: All
+    ///
+    /// [Term]
+    /// id: HP:0000118
+    /// name: Phenotypic abnormality
+    /// is_a: HP:0000001 ! All
+    /// ";
+    ///
+    /// let ontology = Builder::new()
+    ///     .add_obo(obo.as_bytes())
+    ///     .unwrap()
+    ///     .connect_all_terms()
+    ///     .calculate_information_content().unwrap()
+    ///     .build_minimal();
+    ///
+    /// assert_eq!(ontology.len(), 2);
+    /// assert_eq!(ontology.hpo_version(), "2023-04-05");
+    /// ```
+    pub fn add_obo<R: BufRead>(self, reader: R) -> HpoResult<Builder<AllTerms>> {
+        hp_obo::read_obo(reader, hp_obo::READER_SOURCE, self, false).map(|(builder, _)| builder)
+    }
+
+    /// Adds all terms and their parent-child connections from OBO data
+    /// and skips invalid data
+    ///
+    /// This method is identical to [`Builder::add_obo`], but does not fail on
+    /// invalid lines. Instead, they are skipped and reported in the
+    /// returned [`ParseReport`]. Terms without a valid ID or name are skipped
+    /// completely.
+    ///
+    /// # Errors
+    ///
+    /// [`HpoError::ParseOboError`] if the data can't be read
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::builder::Builder;
+    ///
+    /// let obo = "[Term]
+    /// id: HP:0000001
+    /// name: All
+    ///
+    /// [Term]
+    /// id: HP:0000002
+    /// name: Child
+    /// is_a: HP:0000001 ! All
+    /// is_a: HP:0000003 ! Does not exist
+    ///
+    /// [Term]
+    /// id: invalid
+    /// name: Invalid term
+    /// ";
+    ///
+    /// let (builder, report) = Builder::new().add_obo_lenient(obo.as_bytes()).unwrap();
+    /// // The non-existing parent, the invalid ID and the term without ID
+    /// assert_eq!(report.len(), 3);
+    ///
+    /// let ontology = builder
+    ///     .connect_all_terms()
+    ///     .calculate_information_content().unwrap()
+    ///     .build_minimal();
+    /// assert_eq!(ontology.len(), 2);
+    /// ```
+    pub fn add_obo_lenient<R: BufRead>(
+        self,
+        reader: R,
+    ) -> HpoResult<(Builder<AllTerms>, ParseReport)> {
+        hp_obo::read_obo(reader, hp_obo::READER_SOURCE, self, true)
+    }
+
+    /// Adds all terms and their parent-child connections from
+    /// [OBO Graphs JSON](https://github.com/geneontology/obographs) data, e.g. `hp.json`
+    ///
+    /// The terms contain the same details as when parsing OBO data
+    /// via [`Builder::add_obo`]. Nodes of other ontologies and all relationships
+    /// other than `is_a` are ignored.
+    ///
+    /// This method is only available with the `obographs` feature.
+    ///
+    /// # Errors
+    ///
+    /// - [`HpoError::InvalidInput`] if the data is not valid OBO Graphs JSON
+    /// - [`HpoError::DoesNotExist`] if a term references a non-existing parent term
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::builder::Builder;
+    ///
+    /// let json = r#"{"graphs": [{
+    ///     "meta": {"version": "http://purl.obolibrary.org/obo/hp/releases/2023-04-05/hp.json"},
+    ///     "nodes": [
+    ///         {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All", "type": "CLASS"},
+    ///         {"id": "http://purl.obolibrary.org/obo/HP_0000118", "lbl": "Phenotypic abnormality", "type": "CLASS"}
+    ///     ],
+    ///     "edges": [
+    ///         {"sub": "http://purl.obolibrary.org/obo/HP_0000118", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"}
+    ///     ]
+    /// }]}"#;
+    ///
+    /// let ontology = Builder::new()
+    ///     .add_obographs_json(json.as_bytes())
+    ///     .unwrap()
+    ///     .connect_all_terms()
+    ///     .calculate_information_content().unwrap()
+    ///     .build_minimal();
+    ///
+    /// assert_eq!(ontology.len(), 2);
+    /// assert_eq!(ontology.hpo_version(), "2023-04-05");
+    /// ```
+    #[cfg(feature = "obographs")]
+    pub fn add_obographs_json<R: std::io::Read>(self, reader: R) -> HpoResult<Builder<AllTerms>> {
+        crate::parser::obographs::read_obographs_json(reader, self)
+    }
+
     /// Transitions the state to `Builder<AllTerms>`
     ///
     /// This method indicates that all terms have been added. It is not possible
@@ -309,30 +506,30 @@ impl Builder<AllTerms> {
     ///
     /// See [`HpoTermInternal::parents_as_byte`] for explanation of the binary layout.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// This method will panic if the length of bytes does not exactly correspond
-    /// to the contained data or if a `parent_id` or `child_id` is not present
-    /// in the Ontology
-    pub(crate) fn add_parent_from_bytes(&mut self, bytes: &[u8]) {
+    /// - Incomplete data: [`HpoError::BinarySectionError`]
+    /// - A `parent_id` or `child_id` is not present in the Ontology: [`HpoError::DoesNotExist`]
+    pub(crate) fn add_parent_from_bytes(&mut self, bytes: &[u8]) -> HpoResult<()> {
+        let section = Section::Parents.name();
         let mut idx: usize = 0;
-        loop {
-            if idx == bytes.len() {
-                break;
+        while idx < bytes.len() {
+            let n_parents = checked_u32(bytes, idx, section)? as usize;
+            let term = HpoTermId::from(checked_u32(bytes, idx + 4, section)?);
+            let parents = checked_slice(bytes, idx + 8..idx + 8 + 4 * n_parents, section)?;
+            if self.hpo_terms.get(term).is_none() {
+                return Err(HpoError::DoesNotExist);
             }
-            let n_parents = u32_from_bytes(&bytes[idx..]) as usize;
-
-            idx += 4;
-            let term =
-                HpoTermId::from([bytes[idx], bytes[idx + 1], bytes[idx + 2], bytes[idx + 3]]);
-            idx += 4;
-            for _ in 0..n_parents {
-                let parent =
-                    HpoTermId::from([bytes[idx], bytes[idx + 1], bytes[idx + 2], bytes[idx + 3]]);
+            for parent in parents.chunks_exact(4) {
+                let parent = HpoTermId::from(u32_from_bytes(parent));
+                if self.hpo_terms.get(parent).is_none() {
+                    return Err(HpoError::DoesNotExist);
+                }
                 self.add_parent_unchecked(parent, term);
-                idx += 4;
             }
+            idx += 8 + 4 * n_parents;
         }
+        Ok(())
     }
 
     /// Transitions the state to `Builder<ConnectedTerms>`
@@ -477,6 +674,92 @@ impl Builder<ConnectedTerms> {
         Ok(())
     }
 
+    /// Associates the [`Gene`] directly with an [`OmimDisease`]
+    ///
+    /// The disease does not need to be present yet. When the ontology is built,
+    /// the association is added to the disease as well. Associations to diseases
+    /// that are not part of the ontology are dropped at that point.
+    ///
+    /// Returns `false` if the gene is not present in the builder. In this
+    /// case, nothing is recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::HpoTermId;
+    /// use hpo::annotations::{Disease, GeneId, OmimDiseaseId};
+    /// use hpo::builder::Builder;
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.new_term("Foo", 1u32);
+    /// let mut builder = builder.terms_complete().connect_all_terms();
+    ///
+    /// builder.annotate_gene(GeneId::from(10), "NAT2", HpoTermId::from(1u32)).unwrap();
+    /// builder.annotate_omim_disease(OmimDiseaseId::from(243400), "Disease 1", HpoTermId::from(1u32)).unwrap();
+    ///
+    /// assert!(builder.link_gene_omim_disease(GeneId::from(10), OmimDiseaseId::from(243400)));
+    /// assert!(!builder.link_gene_omim_disease(GeneId::from(16), OmimDiseaseId::from(243400)));
+    ///
+    /// let ontology = builder.calculate_information_content().unwrap().build_minimal();
+    /// let gene = ontology.gene(&GeneId::from(10)).unwrap();
+    /// assert!(gene.omim_diseases().contains(&OmimDiseaseId::from(243400)));
+    ///
+    /// let disease = ontology.omim_disease(&OmimDiseaseId::from(243400)).unwrap();
+    /// assert!(disease.genes().contains(&GeneId::from(10)));
+    /// ```
+    pub fn link_gene_omim_disease(&mut self, gene_id: GeneId, omim_id: OmimDiseaseId) -> bool {
+        self.genes
+            .get_mut(&gene_id)
+            .map(|gene| gene.add_omim_disease(omim_id))
+            .is_some()
+    }
+
+    /// Associates the [`Gene`] directly with an [`OrphaDisease`]
+    ///
+    /// See [`Builder::link_gene_omim_disease`] for details
+    pub fn link_gene_orpha_disease(&mut self, gene_id: GeneId, orpha_id: OrphaDiseaseId) -> bool {
+        self.genes
+            .get_mut(&gene_id)
+            .map(|gene| gene.add_orpha_disease(orpha_id))
+            .is_some()
+    }
+
+    /// Adds direct gene - disease associations from `genes_to_disease.txt` data
+    ///
+    /// Only genes that are already present, e.g. from `genes_to_phenotype.txt`,
+    /// are linked to their diseases. See [`Builder::link_gene_omim_disease`]
+    ///
+    /// # Errors
+    ///
+    /// [`HpoError::InvalidInput`]: The data can't be read or a line contains invalid data,
+    /// e.g. an invalid gene or disease ID. The error contains the line number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::HpoTermId;
+    /// use hpo::annotations::{GeneId, OmimDiseaseId};
+    /// use hpo::builder::Builder;
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.new_term("Foo", 1u32);
+    /// let mut builder = builder.terms_complete().connect_all_terms();
+    /// builder.annotate_gene(GeneId::from(10), "NAT2", HpoTermId::from(1u32)).unwrap();
+    ///
+    /// let data = "ncbi_gene_id\tgene_symbol\tassociation_type\tdisease_id\tsource
+    /// NCBIGene:10\tNAT2\tMENDELIAN\tOMIM:243400\tftp://ftp.omim.org/mim2gene_medgen
+    /// NCBIGene:16\tAARS1\tMENDELIAN\tOMIM:613287\tftp://ftp.omim.org/mim2gene_medgen
+    /// ";
+    /// builder.add_genes_to_disease(data.as_bytes()).unwrap();
+    /// ```
+    pub fn add_genes_to_disease<R: BufRead>(&mut self, reader: R) -> HpoResult<()> {
+        crate::parser::gene_to_hpo::parse_genes_to_disease_reader(
+            reader,
+            hp_obo::READER_SOURCE,
+            self,
+        )
+    }
+
     /// Add the [`OmimDisease`] as annotation to the [`HpoTerm`](`crate::HpoTerm`)
     ///
     /// The disease will be recursively connected to all parent `HpoTerms` as well.
@@ -593,6 +876,443 @@ impl Builder<ConnectedTerms> {
         Ok(())
     }
 
+    /// Add the [`DecipherDisease`] as annotation to the [`HpoTerm`](`crate::HpoTerm`)
+    ///
+    /// The disease will be recursively connected to all parent `HpoTerms` as well.
+    ///
+    /// # Errors
+    ///
+    /// If the HPO term is not present, an [`HpoError::DoesNotExist`] is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::HpoTermId;
+    /// use hpo::annotations::{Disease, DecipherDiseaseId};
+    /// use hpo::builder::Builder;
+    /// # use hpo::builder::ConnectedTerms;
+    ///
+    /// fn example_builder() -> Builder<ConnectedTerms>
+    /// # {
+    /// # let mut builder = Builder::new();
+    /// # builder.new_term("Foo", 1u32);
+    /// # builder.new_term("Bar", 2u32);
+    /// # let mut builder = builder.terms_complete();
+    /// # builder.add_parent(1u32, 2u32).unwrap();
+    /// # builder.connect_all_terms()
+    /// # }
+    ///
+    /// let mut builder: Builder<ConnectedTerms> = example_builder();
+    ///
+    /// builder.annotate_decipher_disease(DecipherDiseaseId::from(5), "Disease 1", HpoTermId::from(1u32));
+    ///
+    /// // quickly transition through all stages to build the ontology
+    /// let ontology = builder
+    ///     .calculate_information_content().unwrap()
+    ///     .build_minimal();
+    ///    
+    /// let term = ontology.hpo(1u32).unwrap();
+    /// assert!(term.decipher_diseases().find(|disease| disease.name() == "Disease 1").is_some());
+    /// assert!(term.decipher_diseases().find(|disease| disease.name() == "Foobar").is_none());
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn annotate_decipher_disease(
+        &mut self,
+        decipher_id: DecipherDiseaseId,
+        decipher_name: &str,
+        term_id: HpoTermId,
+    ) -> HpoResult<()> {
+        self.add_decipher_disease(decipher_name, decipher_id);
+        let gene = self
+            .decipher_diseases
+            .get_mut(&decipher_id)
+            .expect("Gene is present because it was just add_decipher_disease");
+
+        gene.add_term(term_id);
+        self.link_decipher_disease_term(term_id, decipher_id)?;
+
+        Ok(())
+    }
+
+    /// Adds disease annotations from `phenotype.hpoa` data to the ontology
+    ///
+    /// Each line connects an [`OmimDisease`], [`OrphaDisease`] or [`DecipherDisease`] to an
+    /// [`HpoTerm`](`crate::HpoTerm`) and contains the details of the association,
+    /// see [`DiseaseAnnotation`]. Annotations with an evidence code excluded via
+    /// [`Builder::exclude_evidence`] are skipped.
+    ///
+    /// Unknown evidence codes and frequencies are ignored with a warning,
+    /// the annotation itself is still added.
+    ///
+    /// Negated (`NOT`) annotations do not link the term to the disease, but are
+    /// recorded as [excluded terms](`Disease::excluded_terms`). They are only kept
+    /// for diseases that have at least one positive annotation.
+    ///
+    /// The `#key: value` header lines are kept as [`AnnotationMetadata`](`crate::AnnotationMetadata`)
+    /// of `phenotype.hpoa`, see [`Builder::metadata_mut`].
+    ///
+    /// # Errors
+    ///
+    /// - [`HpoError::InvalidInput`]: The data can't be read or a line contains invalid data
+    /// - [`HpoError::ParseIntError`]: A line contains an invalid disease ID
+    /// - [`HpoError::DoesNotExist`]: A line contains a non-existing [`HpoTermId`],
+    ///   unless lenient annotations are enabled, see [`Builder::lenient_annotations`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::HpoTermId;
+    /// use hpo::annotations::{Disease, EvidenceCode, OmimDiseaseId};
+    /// use hpo::builder::Builder;
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.new_term("Foo", 1u32);
+    /// builder.new_term("Bar", 2u32);
+    /// let mut builder = builder
+    ///     .exclude_evidence(EvidenceCode::Iea)
+    ///     .terms_complete()
+    ///     .connect_all_terms();
+    ///
+    /// let hpoa = "#description: \"HPO annotations for rare diseases\"
+    /// database_id\tdisease_name\tqualifier\thpo_id\treference\tevidence\tonset\tfrequency\tsex\tmodifier\taspect\tbiocuration
+    /// OMIM:5\tDisease 1\t\tHP:0000001\tPMID:1\tPCS\t\t1/2\t\t\tP\tHPO:probinson[2021-06-21]
+    /// OMIM:5\tDisease 1\t\tHP:0000002\tOMIM:5\tIEA\t\t\t\t\tP\tHPO:iea[2009-02-17]
+    /// OMIM:6\tDisease 2\t\tHP:0000001\tPMID:2\tPCS\t\t\t\t\tP\tHPO:probinson[2021-06-21]
+    /// OMIM:6\tDisease 2\tNOT\tHP:0000002\tPMID:2\tPCS\t\t\t\t\tP\tHPO:probinson[2021-06-21]
+    /// ";
+    /// builder.add_disease_annotations(hpoa.as_bytes()).unwrap();
+    ///
+    /// let ontology = builder.calculate_information_content().unwrap().build_minimal();
+    /// let disease = ontology.omim_disease(&OmimDiseaseId::from(5)).unwrap();
+    /// assert_eq!(disease.hpo_terms().len(), 1);
+    /// assert_eq!(disease.annotation(1u32).unwrap().evidence(), Some(EvidenceCode::Pcs));
+    /// assert_eq!(disease.term_frequency(1u32), Some(0.5));
+    ///
+    /// let disease = ontology.omim_disease(&OmimDiseaseId::from(6)).unwrap();
+    /// assert_eq!(disease.hpo_terms().len(), 1);
+    /// assert!(disease.excluded_terms().contains(&HpoTermId::from(2u32)));
+    /// ```
+    pub fn add_disease_annotations<R: BufRead>(&mut self, reader: R) -> HpoResult<()> {
+        crate::parser::disease_to_hpo::parse_reader(reader, hp_obo::READER_SOURCE, self)
+    }
+
+    /// Adds the [`DiseaseAnnotation`] to the [`OmimDisease`] and connects it to the term
+    ///
+    /// If the evidence of the annotation is excluded via [`Builder::exclude_evidence`],
+    /// the annotation is skipped and `false` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the HPO term is not present, an [`HpoError::DoesNotExist`] is returned
+    pub fn add_omim_disease_annotation(
+        &mut self,
+        omim_id: OmimDiseaseId,
+        omim_name: &str,
+        annotation: DiseaseAnnotation,
+    ) -> HpoResult<bool> {
+        if self.is_excluded(&annotation) {
+            return Ok(false);
+        }
+        self.annotate_omim_disease(omim_id, omim_name, annotation.term_id())?;
+        self.omim_diseases
+            .get_mut(&omim_id)
+            .ok_or(HpoError::DoesNotExist)?
+            .add_annotation(annotation);
+        Ok(true)
+    }
+
+    /// Adds the [`DiseaseAnnotation`] to the [`OrphaDisease`] and connects it to the term
+    ///
+    /// If the evidence of the annotation is excluded via [`Builder::exclude_evidence`],
+    /// the annotation is skipped and `false` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the HPO term is not present, an [`HpoError::DoesNotExist`] is returned
+    pub fn add_orpha_disease_annotation(
+        &mut self,
+        orpha_id: OrphaDiseaseId,
+        orpha_name: &str,
+        annotation: DiseaseAnnotation,
+    ) -> HpoResult<bool> {
+        if self.is_excluded(&annotation) {
+            return Ok(false);
+        }
+        self.annotate_orpha_disease(orpha_id, orpha_name, annotation.term_id())?;
+        self.orpha_diseases
+            .get_mut(&orpha_id)
+            .ok_or(HpoError::DoesNotExist)?
+            .add_annotation(annotation);
+        Ok(true)
+    }
+
+    /// Adds the [`DiseaseAnnotation`] to the [`DecipherDisease`] and connects it to the term
+    ///
+    /// If the evidence of the annotation is excluded via [`Builder::exclude_evidence`],
+    /// the annotation is skipped and `false` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the HPO term is not present, an [`HpoError::DoesNotExist`] is returned
+    pub fn add_decipher_disease_annotation(
+        &mut self,
+        decipher_id: DecipherDiseaseId,
+        decipher_name: &str,
+        annotation: DiseaseAnnotation,
+    ) -> HpoResult<bool> {
+        if self.is_excluded(&annotation) {
+            return Ok(false);
+        }
+        self.annotate_decipher_disease(decipher_id, decipher_name, annotation.term_id())?;
+        self.decipher_diseases
+            .get_mut(&decipher_id)
+            .ok_or(HpoError::DoesNotExist)?
+            .add_annotation(annotation);
+        Ok(true)
+    }
+
+    /// Records that the [`OmimDisease`] explicitly does **not** show the
+    /// [`HpoTerm`](`crate::HpoTerm`), e.g. from a `NOT` annotation in `phenotype.hpoa`
+    ///
+    /// Excluded terms are not linked to the disease and do not influence
+    /// the information content. They are available via [`Disease::excluded_terms`].
+    ///
+    /// Returns `false` if the disease is not present in the builder. In this
+    /// case, nothing is recorded.
+    ///
+    /// # Errors
+    ///
+    /// If the HPO term is not present, an [`HpoError::DoesNotExist`] is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::HpoTermId;
+    /// use hpo::annotations::{Disease, OmimDiseaseId};
+    /// use hpo::builder::Builder;
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.new_term("Foo", 1u32);
+    /// builder.new_term("Bar", 2u32);
+    /// let mut builder = builder.terms_complete().connect_all_terms();
+    ///
+    /// builder.annotate_omim_disease(OmimDiseaseId::from(5), "Disease 1", HpoTermId::from(1u32)).unwrap();
+    /// assert!(builder.exclude_omim_disease_term(OmimDiseaseId::from(5), HpoTermId::from(2u32)).unwrap());
+    /// assert!(!builder.exclude_omim_disease_term(OmimDiseaseId::from(6), HpoTermId::from(2u32)).unwrap());
+    ///
+    /// let ontology = builder.calculate_information_content().unwrap().build_minimal();
+    /// let disease = ontology.omim_disease(&OmimDiseaseId::from(5)).unwrap();
+    /// assert!(disease.excluded_terms().contains(&HpoTermId::from(2u32)));
+    /// assert!(!disease.hpo_terms().contains(&HpoTermId::from(2u32)));
+    /// ```
+    pub fn exclude_omim_disease_term(
+        &mut self,
+        omim_id: OmimDiseaseId,
+        term_id: HpoTermId,
+    ) -> HpoResult<bool> {
+        if self.hpo_terms.get(term_id).is_none() {
+            return Err(HpoError::DoesNotExist);
+        }
+        Ok(self
+            .omim_diseases
+            .get_mut(&omim_id)
+            .map(|disease| disease.add_excluded_term(term_id))
+            .is_some())
+    }
+
+    /// Records that the [`OrphaDisease`] explicitly does **not** show the
+    /// [`HpoTerm`](`crate::HpoTerm`), e.g. from a `NOT` annotation in `phenotype.hpoa`
+    ///
+    /// See [`Builder::exclude_omim_disease_term`] for details
+    ///
+    /// # Errors
+    ///
+    /// If the HPO term is not present, an [`HpoError::DoesNotExist`] is returned
+    pub fn exclude_orpha_disease_term(
+        &mut self,
+        orpha_id: OrphaDiseaseId,
+        term_id: HpoTermId,
+    ) -> HpoResult<bool> {
+        if self.hpo_terms.get(term_id).is_none() {
+            return Err(HpoError::DoesNotExist);
+        }
+        Ok(self
+            .orpha_diseases
+            .get_mut(&orpha_id)
+            .map(|disease| disease.add_excluded_term(term_id))
+            .is_some())
+    }
+
+    /// Records that the [`DecipherDisease`] explicitly does **not** show the
+    /// [`HpoTerm`](`crate::HpoTerm`), e.g. from a `NOT` annotation in `phenotype.hpoa`
+    ///
+    /// See [`Builder::exclude_omim_disease_term`] for details
+    ///
+    /// # Errors
+    ///
+    /// If the HPO term is not present, an [`HpoError::DoesNotExist`] is returned
+    pub fn exclude_decipher_disease_term(
+        &mut self,
+        decipher_id: DecipherDiseaseId,
+        term_id: HpoTermId,
+    ) -> HpoResult<bool> {
+        if self.hpo_terms.get(term_id).is_none() {
+            return Err(HpoError::DoesNotExist);
+        }
+        Ok(self
+            .decipher_diseases
+            .get_mut(&decipher_id)
+            .map(|disease| disease.add_excluded_term(term_id))
+            .is_some())
+    }
+
+    /// Returns `true` if the evidence of the annotation is excluded
+    fn is_excluded(&self, annotation: &DiseaseAnnotation) -> bool {
+        self.is_excluded_evidence(annotation.evidence())
+    }
+
+    /// Returns `true` if the evidence code is excluded via [`Builder::exclude_evidence`]
+    pub(crate) fn is_excluded_evidence(&self, evidence: Option<EvidenceCode>) -> bool {
+        evidence.is_some_and(|evidence| self.excluded_evidence.contains(&evidence))
+    }
+
+    /// Returns the term that an annotation line should be linked to
+    ///
+    /// In strict mode, this is always `term_id`. In lenient mode (see
+    /// [`Builder::lenient_annotations`]), obsolete terms and alternative IDs
+    /// are optionally remapped to their replacement and lines with
+    /// unknown terms are skipped by returning `None`. Every change is
+    /// recorded in the [`LoadReport`].
+    pub(crate) fn resolve_annotation_term(
+        &mut self,
+        term_id: HpoTermId,
+        file: &str,
+        line: usize,
+        text: &str,
+    ) -> Option<HpoTermId> {
+        if !self.lenient_annotations {
+            return Some(term_id);
+        }
+
+        if self.remap_obsolete {
+            let replacement = match self.hpo_terms.get(term_id) {
+                Some(term) if term.obsolete() => term
+                    .replacement()
+                    .filter(|replacement| self.hpo_terms.get(*replacement).is_some()),
+                Some(_) => None,
+                None => self.hpo_terms.get_by_alt_id(term_id).map(|term| *term.id()),
+            };
+            if let Some(replacement) = replacement {
+                self.load_report.add(LoadIssue::Remapped {
+                    file: file.to_string(),
+                    line,
+                    text: text.to_string(),
+                    term_id,
+                    replacement,
+                });
+                return Some(replacement);
+            }
+        }
+
+        if self.hpo_terms.get(term_id).is_some() {
+            return Some(term_id);
+        }
+        self.load_report.add(LoadIssue::Skipped {
+            file: file.to_string(),
+            line,
+            text: text.to_string(),
+            term_id,
+        });
+        None
+    }
+
+    /// Sets the frequency of an [`HpoTerm`](`crate::HpoTerm`) in patients with
+    /// the [`OmimDisease`]
+    ///
+    /// The frequency must be normalised to `0.0..=1.0`. If the frequency is set
+    /// multiple times, e.g. because the term is annotated from several sources,
+    /// the highest frequency is kept.
+    ///
+    /// # Errors
+    ///
+    /// - [`HpoError::DoesNotExist`] if the disease is not present or not annotated to the term
+    /// - [`HpoError::InvalidInput`] if the frequency is outside of `0.0..=1.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::HpoTermId;
+    /// use hpo::annotations::{Disease, OmimDiseaseId};
+    /// use hpo::builder::Builder;
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.new_term("Foo", 1u32);
+    /// let mut builder = builder.terms_complete().connect_all_terms();
+    ///
+    /// builder.annotate_omim_disease(OmimDiseaseId::from(5), "Disease 1", HpoTermId::from(1u32)).unwrap();
+    /// builder.set_omim_disease_frequency(OmimDiseaseId::from(5), HpoTermId::from(1u32), 0.5).unwrap();
+    ///
+    /// let ontology = builder.calculate_information_content().unwrap().build_minimal();
+    /// let disease = ontology.omim_disease(&OmimDiseaseId::from(5)).unwrap();
+    /// assert_eq!(disease.term_frequency(1u32), Some(0.5));
+    /// ```
+    pub fn set_omim_disease_frequency(
+        &mut self,
+        omim_id: OmimDiseaseId,
+        term_id: HpoTermId,
+        frequency: f32,
+    ) -> HpoResult<()> {
+        let disease = self
+            .omim_diseases
+            .get_mut(&omim_id)
+            .ok_or(HpoError::DoesNotExist)?;
+        set_frequency(disease, term_id, frequency)
+    }
+
+    /// Sets the frequency of an [`HpoTerm`](`crate::HpoTerm`) in patients with
+    /// the [`OrphaDisease`]
+    ///
+    /// See [`Builder::set_omim_disease_frequency`] for details
+    ///
+    /// # Errors
+    ///
+    /// - [`HpoError::DoesNotExist`] if the disease is not present or not annotated to the term
+    /// - [`HpoError::InvalidInput`] if the frequency is outside of `0.0..=1.0`
+    pub fn set_orpha_disease_frequency(
+        &mut self,
+        orpha_id: OrphaDiseaseId,
+        term_id: HpoTermId,
+        frequency: f32,
+    ) -> HpoResult<()> {
+        let disease = self
+            .orpha_diseases
+            .get_mut(&orpha_id)
+            .ok_or(HpoError::DoesNotExist)?;
+        set_frequency(disease, term_id, frequency)
+    }
+
+    /// Sets the frequency of an [`HpoTerm`](`crate::HpoTerm`) in patients with
+    /// the [`DecipherDisease`]
+    ///
+    /// See [`Builder::set_omim_disease_frequency`] for details
+    ///
+    /// # Errors
+    ///
+    /// - [`HpoError::DoesNotExist`] if the disease is not present or not annotated to the term
+    /// - [`HpoError::InvalidInput`] if the frequency is outside of `0.0..=1.0`
+    pub fn set_decipher_disease_frequency(
+        &mut self,
+        decipher_id: DecipherDiseaseId,
+        term_id: HpoTermId,
+        frequency: f32,
+    ) -> HpoResult<()> {
+        let disease = self
+            .decipher_diseases
+            .get_mut(&decipher_id)
+            .ok_or(HpoError::DoesNotExist)?;
+        set_frequency(disease, term_id, frequency)
+    }
+
     /// Calculates the [`crate::term::InformationContent`]s for every term
     /// and transitions to the `FullyAnnotated` state
     ///
@@ -642,6 +1362,47 @@ impl Builder<ConnectedTerms> {
         self.calculate_gene_ic()?;
         self.calculate_omim_disease_ic()?;
         self.calculate_orpha_disease_ic()?;
+        self.calculate_decipher_disease_ic()?;
+
+        Ok(transition_state(self))
+    }
+
+    /// Sets the information content of all terms from binary data
+    /// and transitions to the `FullyAnnotated` state
+    ///
+    /// This method is part of the Ontology-building, based on the binary
+    /// data format and replaces [`Builder::calculate_information_content`].
+    /// The data must contain the information content of every term.
+    ///
+    /// See `InformationContent::as_bytes` for explanation of the binary layout.
+    ///
+    /// # Errors
+    ///
+    /// - Incomplete data: [`HpoError::BinarySectionError`]
+    /// - The data does not match the terms of the ontology: [`HpoError::DoesNotExist`]
+    pub(crate) fn information_content_from_bytes(
+        mut self,
+        bytes: &[u8],
+    ) -> HpoResult<Builder<FullyAnnotated>> {
+        let expected = self.hpo_terms.len() * INFORMATION_CONTENT_ENTRY_LEN;
+        if bytes.len() != expected {
+            return Err(HpoError::BinarySectionError {
+                section: Section::InformationContent.name().to_string(),
+                expected,
+                actual: bytes.len(),
+            });
+        }
+        for entry in bytes.chunks_exact(INFORMATION_CONTENT_ENTRY_LEN) {
+            let (id, information_content) = entry.split_at(4);
+            let information_content: &[u8; 20] = information_content
+                .try_into()
+                .expect("entry contains 20 bytes of information content");
+            *self
+                .hpo_terms
+                .get_mut(u32_from_bytes(id).into())
+                .ok_or(HpoError::DoesNotExist)?
+                .information_content_mut() = InformationContent::from_bytes(information_content);
+        }
 
         Ok(transition_state(self))
     }
@@ -707,6 +1468,32 @@ impl Builder<ConnectedTerms> {
         }
     }
 
+    /// Adds a [`DecipherDisease`](`crate::annotations::DecipherDisease`) to the ontology
+    ///
+    /// The gene is not yet linked to any terms, this must be done
+    /// through [`Builder<ConnectedTerms>::annotate_decipher_disease`](`Builder::annotate_decipher_disease`)
+    ///
+    /// # Note:
+    ///
+    /// There is rarely need to call this method directly. The preferred way is to use
+    /// [`Builder<ConnectedTerms>::annotate_decipher_disease`](`Builder::annotate_decipher_disease`)
+    pub fn add_decipher_disease(
+        &mut self,
+        decipher_disease_name: &str,
+        decipher_disease_id: DecipherDiseaseId,
+    ) -> DecipherDiseaseId {
+        match self.decipher_diseases.entry(decipher_disease_id) {
+            std::collections::hash_map::Entry::Occupied(_) => decipher_disease_id,
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(DecipherDisease::new(
+                    decipher_disease_id,
+                    decipher_disease_name,
+                ));
+                decipher_disease_id
+            }
+        }
+    }
+
     /// Adds genes to the ontoloigy and connects them to connected terms
     ///
     /// This method is part of the Ontology-building, based on the binary
@@ -722,8 +1509,12 @@ impl Builder<ConnectedTerms> {
             if idx >= bytes.len() {
                 break;
             }
-            let gene_len = u32_from_bytes(&bytes[idx..]) as usize;
-            let gene = Gene::try_from(&bytes[idx..idx + gene_len])?;
+            let gene_len = checked_u32(bytes, idx, Section::Genes.name())?.max(4) as usize;
+            let gene = Gene::try_from(checked_slice(
+                bytes,
+                idx..idx + gene_len,
+                Section::Genes.name(),
+            )?)?;
             for term in gene.hpo_terms() {
                 self.link_gene_term(term, *gene.id())?;
             }
@@ -748,8 +1539,13 @@ impl Builder<ConnectedTerms> {
             if idx >= bytes.len() {
                 break;
             }
-            let disease_len = u32_from_bytes(&bytes[idx..]) as usize;
-            let disease = OmimDisease::try_from(&bytes[idx..idx + disease_len])?;
+            let disease_len =
+                checked_u32(bytes, idx, Section::OmimDiseases.name())?.max(4) as usize;
+            let disease = OmimDisease::try_from(checked_slice(
+                bytes,
+                idx..idx + disease_len,
+                Section::OmimDiseases.name(),
+            )?)?;
             for term in disease.hpo_terms() {
                 self.link_omim_disease_term(term, *disease.id())?;
             }
@@ -774,8 +1570,13 @@ impl Builder<ConnectedTerms> {
             if idx >= bytes.len() {
                 break;
             }
-            let disease_len = u32_from_bytes(&bytes[idx..]) as usize;
-            let disease = OrphaDisease::try_from(&bytes[idx..idx + disease_len])?;
+            let disease_len =
+                checked_u32(bytes, idx, Section::OrphaDiseases.name())?.max(4) as usize;
+            let disease = OrphaDisease::try_from(checked_slice(
+                bytes,
+                idx..idx + disease_len,
+                Section::OrphaDiseases.name(),
+            )?)?;
             for term in disease.hpo_terms() {
                 self.link_orpha_disease_term(term, *disease.id())?;
             }
@@ -785,6 +1586,37 @@ impl Builder<ConnectedTerms> {
         Ok(())
     }
 
+    /// Adds [`DecipherDisease`]s to the ontoloigy and connects them to connected terms
+    ///
+    /// This method is part of the Ontology-building, based on the binary
+    /// data format and requires a specified data layout.
+    ///
+    /// It connects all connected terms and their parents properly. The
+    /// method assumes that the bytes encode all Disease-term connections.
+    ///
+    /// See [`DecipherDisease::as_bytes`] for explanation of the binary layout
+    pub(crate) fn add_decipher_disease_from_bytes(&mut self, bytes: &[u8]) -> HpoResult<()> {
+        let mut idx: usize = 0;
+        loop {
+            if idx >= bytes.len() {
+                break;
+            }
+            let disease_len =
+                checked_u32(bytes, idx, Section::DecipherDiseases.name())?.max(4) as usize;
+            let disease = DecipherDisease::try_from(checked_slice(
+                bytes,
+                idx..idx + disease_len,
+                Section::DecipherDiseases.name(),
+            )?)?;
+            for term in disease.hpo_terms() {
+                self.link_decipher_disease_term(term, *disease.id())?;
+            }
+            self.decipher_diseases.insert(*disease.id(), disease);
+            idx += disease_len;
+        }
+        Ok(())
+    }
+
     /// Add the [`Gene`] as annotation to the [`HpoTerm`](`crate::HpoTerm`)
     ///
     /// The gene will be recursively connected to all parent `HpoTerms` as well.
@@ -880,6 +1712,39 @@ impl Builder<ConnectedTerms> {
         Ok(())
     }
 
+    /// Add the [`DecipherDisease`] as annotation to the [`HpoTerm`](`crate::HpoTerm`)
+    ///
+    /// The disease will be recursively connected to all parent `HpoTerms` as well.
+    ///
+    /// This method does not add the HPO-term to the [`DecipherDisease`], this
+    /// must be handled by the client.
+    ///
+    /// # Errors
+    ///
+    /// If the HPO term is not present, an [`HpoError`] is returned
+    ///
+    fn link_decipher_disease_term(
+        &mut self,
+        term_id: HpoTermId,
+        decipher_disease_id: DecipherDiseaseId,
+    ) -> HpoResult<()> {
+        let term = self
+            .hpo_terms
+            .get_mut(term_id)
+            .ok_or(HpoError::DoesNotExist)?;
+
+        if term.add_decipher_disease(decipher_disease_id) {
+            // If the disease is already associated to the term, this branch will
+            // be skipped. That is desired, because by definition
+            // all parent terms are already linked as well
+            let parents = term.all_parents().clone();
+            for parent in &parents {
+                self.link_decipher_disease_term(parent, decipher_disease_id)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Calculates the gene-specific Information Content for every term
     ///
     /// If no genes are present in the Ontology, no IC are calculated
@@ -920,6 +1785,20 @@ impl Builder<ConnectedTerms> {
         }
         Ok(())
     }
+
+    /// Calculates the Decipher-Disease-specific Information Content for every term
+    ///
+    /// If no diseases are present in the Ontology, no IC are calculated
+    fn calculate_decipher_disease_ic(&mut self) -> HpoResult<()> {
+        let n_decipher_diseases = self.decipher_diseases.len();
+
+        for term in self.hpo_terms.values_mut() {
+            let current_diseases = term.decipher_diseases().len();
+            term.information_content_mut()
+                .set_decipher_disease(n_decipher_diseases, current_diseases)?;
+        }
+        Ok(())
+    }
 }
 
 impl Builder<FullyAnnotated> {
@@ -951,19 +1830,151 @@ impl Builder<FullyAnnotated> {
     /// Use this method only with custom ontologies. When using the standard
     /// Jax ontology, use the recommended [`Builder::build_with_defaults`]
     /// method.
-    pub fn build_minimal(self) -> Ontology {
+    pub fn build_minimal(mut self) -> Ontology {
+        self.link_gene_diseases();
         Ontology {
             hpo_terms: self.hpo_terms,
             genes: self.genes,
             omim_diseases: self.omim_diseases,
             orpha_diseases: self.orpha_diseases,
+            decipher_diseases: self.decipher_diseases,
             hpo_version: self.hpo_version,
+            metadata: self.metadata,
             ..Default::default()
         }
     }
+
+    /// Adds the direct gene associations to all diseases
+    ///
+    /// Associations of genes to diseases that are not present
+    /// in the ontology are removed.
+    fn link_gene_diseases(&mut self) {
+        let omim_diseases = &mut self.omim_diseases;
+        let orpha_diseases = &mut self.orpha_diseases;
+        for gene in self.genes.values_mut() {
+            gene.retain_diseases(
+                |omim_id| omim_diseases.contains_key(omim_id),
+                |orpha_id| orpha_diseases.contains_key(orpha_id),
+            );
+            for omim_id in gene.omim_diseases() {
+                if let Some(disease) = omim_diseases.get_mut(omim_id) {
+                    disease.add_gene(*gene.id());
+                }
+            }
+            for orpha_id in gene.orpha_diseases() {
+                if let Some(disease) = orpha_diseases.get_mut(orpha_id) {
+                    disease.add_gene(*gene.id());
+                }
+            }
+        }
+    }
 }
 
 impl<T> Builder<T> {
+    /// Excludes all disease annotations with the given [`EvidenceCode`]
+    ///
+    /// Annotations with an excluded evidence code are skipped when they are
+    /// added via [`Builder::add_disease_annotations`], [`Builder::add_omim_disease_annotation`],
+    /// [`Builder::add_orpha_disease_annotation`] or [`Builder::add_decipher_disease_annotation`],
+    /// so they are not part of the
+    /// information content calculation either.
+    ///
+    /// This option must be set before any disease annotations are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::annotations::EvidenceCode;
+    /// use hpo::builder::Builder;
+    ///
+    /// let builder = Builder::new().exclude_evidence(EvidenceCode::Iea);
+    /// ```
+    #[must_use]
+    pub fn exclude_evidence(mut self, evidence: EvidenceCode) -> Self {
+        if !self.excluded_evidence.contains(&evidence) {
+            self.excluded_evidence.push(evidence);
+        }
+        self
+    }
+
+    /// Skips annotations of terms that are not part of the ontology
+    ///
+    /// By default, adding gene or disease annotations from annotation files, e.g.
+    /// via [`Builder::add_disease_annotations`], fails with [`HpoError::DoesNotExist`]
+    /// if a line references a term that is missing in the ontology. In lenient mode,
+    /// such lines are skipped instead.
+    ///
+    /// If `remap_obsolete` is `true`, lines that reference an obsolete term with a
+    /// replacement, or an alternative ID of a term, are added to the replacement term.
+    ///
+    /// All skipped and remapped lines are recorded in the [`LoadReport`],
+    /// see [`Builder::load_report`].
+    ///
+    /// This option must be set before any annotations are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::annotations::{Disease, OmimDiseaseId};
+    /// use hpo::builder::Builder;
+    /// use hpo::HpoTermId;
+    ///
+    /// let obo = "[Term]
+    /// id: HP:0000001
+    /// name: All
+    ///
+    /// [Term]
+    /// id: HP:0000002
+    /// name: Foo
+    /// is_a: HP:0000001 ! All
+    /// alt_id: HP:0000005
+    ///
+    /// [Term]
+    /// id: HP:0000003
+    /// name: Old Foo
+    /// is_obsolete: true
+    /// replaced_by: HP:0000002
+    /// ";
+    ///
+    /// let mut builder = Builder::new()
+    ///     .lenient_annotations(true)
+    ///     .add_obo(obo.as_bytes())
+    ///     .unwrap()
+    ///     .connect_all_terms();
+    ///
+    /// let hpoa = "OMIM:5\tDisease 1\t\tHP:0000003\tPMID:1\tPCS
+    /// OMIM:5\tDisease 1\t\tHP:0000004\tPMID:1\tPCS
+    /// OMIM:6\tDisease 2\t\tHP:0000005\tPMID:1\tPCS
+    /// ";
+    /// builder.add_disease_annotations(hpoa.as_bytes()).unwrap();
+    /// assert_eq!(builder.load_report().skipped().count(), 1);
+    /// assert_eq!(builder.load_report().remapped().count(), 2);
+    ///
+    /// let ontology = builder.calculate_information_content().unwrap().build_minimal();
+    /// let disease = ontology.omim_disease(&OmimDiseaseId::from(5)).unwrap();
+    /// assert!(disease.hpo_terms().contains(&HpoTermId::from(2u32)));
+    /// assert_eq!(disease.hpo_terms().len(), 1);
+    /// ```
+    #[must_use]
+    pub fn lenient_annotations(mut self, remap_obsolete: bool) -> Self {
+        self.lenient_annotations = true;
+        self.remap_obsolete = remap_obsolete;
+        self
+    }
+
+    /// Returns all annotation lines that were skipped or remapped so far
+    ///
+    /// The report is only populated in lenient mode,
+    /// see [`Builder::lenient_annotations`]
+    pub fn load_report(&self) -> &LoadReport {
+        &self.load_report
+    }
+
+    /// Takes the [`LoadReport`] out of the builder, leaving an empty report
+    pub(crate) fn take_load_report(&mut self) -> LoadReport {
+        std::mem::take(&mut self.load_report)
+    }
+
     /// Defines the HPO version of the Ontology
     /// The version should be specified as \[YEAR\]-\[MONTH\]-\[DAY\], e.g.
     /// `2024-08-21`
@@ -971,6 +1982,25 @@ impl<T> Builder<T> {
         self.hpo_version = version;
     }
 
+    /// Returns a mutable reference to the [`OntologyMetadata`] of the ontology
+    ///
+    /// The metadata is filled automatically when parsing the ontology and
+    /// annotation data, e.g. via [`Builder::add_obo`] or
+    /// [`Builder::add_disease_annotations`].
+    pub fn metadata_mut(&mut self) -> &mut OntologyMetadata {
+        &mut self.metadata
+    }
+
+    /// Parses the binary [`OntologyMetadata`]
+    ///
+    /// # Errors
+    ///
+    /// - Invalid metadata: [`HpoError::ParseBinaryError`]
+    pub(crate) fn metadata_from_bytes(&mut self, bytes: &[u8]) -> HpoResult<()> {
+        self.metadata = OntologyMetadata::from_bytes(bytes)?;
+        Ok(())
+    }
+
     /// Parses `Bytes` into the Jax-Ontology release version
     ///
     /// # Errors
@@ -981,9 +2011,7 @@ impl<T> Builder<T> {
             self.set_hpo_version((0u16, 0u8, 0u8));
             Ok(0)
         } else {
-            if bytes.len() < 4 {
-                return Err(HpoError::ParseBinaryError);
-            }
+            checked_slice(&bytes[..], 0..4, "header")?;
             let year = u16::from_be_bytes([bytes[0], bytes[1]]);
             let month = u8::from_be_bytes([bytes[2]]);
             let day = u8::from_be_bytes([bytes[3]]);
@@ -0,0 +1,411 @@
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use crate::annotations::EvidenceCode;
+use crate::parser::hp_obo::{self, READER_SOURCE};
+use crate::parser::{disease_to_hpo, gene_to_hpo, open_file};
+use crate::{HpoResult, Ontology};
+
+use super::{Builder, LoadReport, LooseCollection};
+
+/// A single data source, either a file or any reader
+enum Source<'a> {
+    File(PathBuf),
+    Reader(Box<dyn BufRead + 'a>),
+}
+
+impl<'a> Source<'a> {
+    /// Returns the name of the source, used to locate errors, and its data
+    fn open(self) -> HpoResult<(String, Box<dyn BufRead + 'a>)> {
+        match self {
+            Source::File(path) => Ok((path.display().to_string(), open_file(&path)?)),
+            Source::Reader(reader) => Ok((READER_SOURCE.to_string(), reader)),
+        }
+    }
+}
+
+/// The format of the ontology data
+enum OntologyFormat {
+    Obo,
+    #[cfg(feature = "obographs")]
+    ObographsJson,
+}
+
+/// The format of the gene annotation data
+enum GeneFormat {
+    GenesToPhenotype,
+    PhenotypeToGenes,
+}
+
+/// Loads an [`Ontology`] from individual data sources
+///
+/// [`Ontology::from_standard`] expects all files with their standard names in one
+/// folder. `StandardLoader` instead accepts each source independently, either as a
+/// path or as any [`BufRead`], e.g. an in-memory buffer or a network stream.
+///
+/// Only the ontology itself is required. Gene annotations, disease annotations and
+/// gene - disease associations are optional and can be omitted if they are not
+/// available.
+///
+/// With the `gzip` feature, all files can also be gzip-compressed.
+///
+/// # Examples
+///
+/// ```
+/// use hpo::builder::StandardLoader;
+/// use hpo::annotations::GeneId;
+///
+/// let obo = "[Term]
+/// id: HP:0000001
+/// name: All
+///
+/// [Term]
+/// id: HP:0000118
+/// name: Phenotypic abnormality
+/// is_a: HP:0000001 ! All
+/// ";
+///
+/// let genes = "ncbi_gene_id\tgene_symbol\thpo_id\thpo_name\tfrequency\tdisease_id
+/// 10\tNAT2\tHP:0000118\tPhenotypic abnormality\t-\tOMIM:243400
+/// ";
+///
+/// let ontology = StandardLoader::from_obo_reader(obo.as_bytes())
+///     .genes_to_phenotype_reader(genes.as_bytes())
+///     .load()
+///     .unwrap();
+///
+/// assert_eq!(ontology.len(), 2);
+/// assert_eq!(ontology.gene(&GeneId::from(10)).unwrap().name(), "NAT2");
+/// assert_eq!(ontology.omim_diseases().count(), 0);
+/// ```
+pub struct StandardLoader<'a> {
+    builder: Builder<LooseCollection>,
+    ontology: (OntologyFormat, Source<'a>),
+    genes: Option<(GeneFormat, Source<'a>)>,
+    diseases: Option<Source<'a>>,
+    gene_diseases: Option<Source<'a>>,
+}
+
+impl<'a> StandardLoader<'a> {
+    fn new(format: OntologyFormat, source: Source<'a>) -> Self {
+        Self {
+            builder: Builder::new(),
+            ontology: (format, source),
+            genes: None,
+            diseases: None,
+            gene_diseases: None,
+        }
+    }
+
+    /// Creates a new `StandardLoader` with the ontology from an OBO file, e.g. `hp.obo`
+    pub fn from_obo_file<P: AsRef<Path>>(file: P) -> Self {
+        Self::new(
+            OntologyFormat::Obo,
+            Source::File(file.as_ref().to_path_buf()),
+        )
+    }
+
+    /// Creates a new `StandardLoader` with the ontology from OBO data
+    pub fn from_obo_reader<R: BufRead + 'a>(reader: R) -> Self {
+        Self::new(OntologyFormat::Obo, Source::Reader(Box::new(reader)))
+    }
+
+    /// Creates a new `StandardLoader` with the ontology from an
+    /// OBO Graphs JSON file, e.g. `hp.json`
+    ///
+    /// This method is only available with the `obographs` feature.
+    #[cfg(feature = "obographs")]
+    pub fn from_obographs_json_file<P: AsRef<Path>>(file: P) -> Self {
+        Self::new(
+            OntologyFormat::ObographsJson,
+            Source::File(file.as_ref().to_path_buf()),
+        )
+    }
+
+    /// Creates a new `StandardLoader` with the ontology from OBO Graphs JSON data
+    ///
+    /// This method is only available with the `obographs` feature.
+    #[cfg(feature = "obographs")]
+    pub fn from_obographs_json_reader<R: BufRead + 'a>(reader: R) -> Self {
+        Self::new(
+            OntologyFormat::ObographsJson,
+            Source::Reader(Box::new(reader)),
+        )
+    }
+
+    /// Adds gene annotations from a `genes_to_phenotype.txt` file
+    ///
+    /// Only directly annotated terms are linked to each gene. This replaces
+    /// previously added gene annotations.
+    #[must_use]
+    pub fn genes_to_phenotype_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.genes = Some((
+            GeneFormat::GenesToPhenotype,
+            Source::File(file.as_ref().to_path_buf()),
+        ));
+        self
+    }
+
+    /// Adds gene annotations from `genes_to_phenotype.txt` data
+    ///
+    /// Only directly annotated terms are linked to each gene. This replaces
+    /// previously added gene annotations.
+    #[must_use]
+    pub fn genes_to_phenotype_reader<R: BufRead + 'a>(mut self, reader: R) -> Self {
+        self.genes = Some((
+            GeneFormat::GenesToPhenotype,
+            Source::Reader(Box::new(reader)),
+        ));
+        self
+    }
+
+    /// Adds gene annotations from a `phenotype_to_genes.txt` file
+    ///
+    /// Genes are linked transitively to all terms, see
+    /// [`Ontology::from_standard_transitive`]. This replaces previously added
+    /// gene annotations.
+    #[must_use]
+    pub fn phenotype_to_genes_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.genes = Some((
+            GeneFormat::PhenotypeToGenes,
+            Source::File(file.as_ref().to_path_buf()),
+        ));
+        self
+    }
+
+    /// Adds gene annotations from `phenotype_to_genes.txt` data
+    ///
+    /// Genes are linked transitively to all terms, see
+    /// [`Ontology::from_standard_transitive`]. This replaces previously added
+    /// gene annotations.
+    #[must_use]
+    pub fn phenotype_to_genes_reader<R: BufRead + 'a>(mut self, reader: R) -> Self {
+        self.genes = Some((
+            GeneFormat::PhenotypeToGenes,
+            Source::Reader(Box::new(reader)),
+        ));
+        self
+    }
+
+    /// Adds disease annotations from a `phenotype.hpoa` file
+    #[must_use]
+    pub fn diseases_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.diseases = Some(Source::File(file.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Adds disease annotations from `phenotype.hpoa` data
+    #[must_use]
+    pub fn diseases_reader<R: BufRead + 'a>(mut self, reader: R) -> Self {
+        self.diseases = Some(Source::Reader(Box::new(reader)));
+        self
+    }
+
+    /// Adds direct gene - disease associations from a `genes_to_disease.txt` file
+    ///
+    /// Only genes from the gene annotations are linked to their diseases.
+    #[must_use]
+    pub fn genes_to_disease_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.gene_diseases = Some(Source::File(file.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Adds direct gene - disease associations from `genes_to_disease.txt` data
+    ///
+    /// Only genes from the gene annotations are linked to their diseases.
+    #[must_use]
+    pub fn genes_to_disease_reader<R: BufRead + 'a>(mut self, reader: R) -> Self {
+        self.gene_diseases = Some(Source::Reader(Box::new(reader)));
+        self
+    }
+
+    /// Adds `phenotype.hpoa` and, if present, `genes_to_disease.txt`
+    /// with their standard names from `folder`
+    pub(crate) fn standard_diseases(self, folder: &Path) -> Self {
+        let loader = self.diseases_file(folder.join(crate::DISEASE_FILENAME));
+        let gene_diseases = folder.join(crate::GENE_TO_DISEASE_FILENAME);
+        if gene_diseases.exists() {
+            loader.genes_to_disease_file(gene_diseases)
+        } else {
+            loader
+        }
+    }
+
+    /// Skips annotations of terms that are not part of the ontology,
+    /// see [`Builder::lenient_annotations`]
+    #[must_use]
+    pub fn lenient_annotations(mut self, remap_obsolete: bool) -> Self {
+        self.builder = self.builder.lenient_annotations(remap_obsolete);
+        self
+    }
+
+    /// Excludes all disease annotations with the given [`EvidenceCode`],
+    /// see [`Builder::exclude_evidence`]
+    #[must_use]
+    pub fn exclude_evidence(mut self, evidence: EvidenceCode) -> Self {
+        self.builder = self.builder.exclude_evidence(evidence);
+        self
+    }
+
+    /// Loads the [`Ontology`] from all sources
+    ///
+    /// # Errors
+    ///
+    /// - a file is not present or can't be read: [`HpoError::CannotOpenFile`](`crate::HpoError::CannotOpenFile`)
+    /// - invalid ontology data: [`HpoError::ParseOboError`](`crate::HpoError::ParseOboError`)
+    ///   or [`HpoError::InvalidInput`](`crate::HpoError::InvalidInput`)
+    /// - invalid annotation data: [`HpoError::InvalidInput`](`crate::HpoError::InvalidInput`)
+    /// - annotations contain references to non-existing HPO terms:
+    ///   [`HpoError::DoesNotExist`](`crate::HpoError::DoesNotExist`), unless
+    ///   [`StandardLoader::lenient_annotations`] is set
+    pub fn load(self) -> HpoResult<Ontology> {
+        self.load_with_report().map(|(ontology, _)| ontology)
+    }
+
+    /// Loads the [`Ontology`] from all sources and returns it together with
+    /// all skipped or remapped annotation lines
+    ///
+    /// The [`LoadReport`] is only populated in lenient mode,
+    /// see [`StandardLoader::lenient_annotations`]
+    ///
+    /// # Errors
+    ///
+    /// See [`StandardLoader::load`]
+    pub fn load_with_report(self) -> HpoResult<(Ontology, LoadReport)> {
+        let (format, source) = self.ontology;
+        let (name, reader) = source.open()?;
+        let builder = match format {
+            OntologyFormat::Obo => hp_obo::read_obo(reader, &name, self.builder, false)?.0,
+            #[cfg(feature = "obographs")]
+            OntologyFormat::ObographsJson => {
+                crate::parser::obographs::read_obographs_json(reader, self.builder)?
+            }
+        };
+        let mut builder = builder.connect_all_terms();
+
+        if let Some((format, source)) = self.genes {
+            let (name, reader) = source.open()?;
+            match format {
+                GeneFormat::GenesToPhenotype => {
+                    gene_to_hpo::parse_genes_to_phenotype_reader(reader, &name, &mut builder)?;
+                }
+                GeneFormat::PhenotypeToGenes => {
+                    gene_to_hpo::parse_phenotype_to_genes_reader(reader, &name, &mut builder)?;
+                }
+            }
+        }
+
+        if let Some(source) = self.diseases {
+            let (name, reader) = source.open()?;
+            disease_to_hpo::parse_reader(reader, &name, &mut builder)?;
+        }
+
+        if let Some(source) = self.gene_diseases {
+            let (name, reader) = source.open()?;
+            gene_to_hpo::parse_genes_to_disease_reader(reader, &name, &mut builder)?;
+        }
+
+        let report = builder.take_load_report();
+        let ontology = builder
+            .calculate_information_content()?
+            .build_with_defaults()?;
+        Ok((ontology, report))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::annotations::{Disease, GeneId, OmimDiseaseId};
+    use crate::HpoError;
+
+    const OBO: &str = "[Term]
+id: HP:0000001
+name: All
+
+[Term]
+id: HP:0000118
+name: Phenotypic abnormality
+is_a: HP:0000001 ! All
+
+[Term]
+id: HP:0000002
+name: Abnormality of body height
+is_a: HP:0000118 ! Phenotypic abnormality
+";
+
+    const GENES_TO_PHENOTYPE: &str =
+        "ncbi_gene_id\tgene_symbol\thpo_id\thpo_name\tfrequency\tdisease_id
+10\tNAT2\tHP:0000002\tAbnormality of body height\t-\tOMIM:243400
+16\tAARS1\tHP:0000003\tUnknown\t-\tOMIM:613287
+";
+
+    const PHENOTYPE_TO_GENES: &str = "hpo_id\thpo_name\tncbi_gene_id\tgene_symbol\tdisease_id
+HP:0000002\tAbnormality of body height\t10\tNAT2\tOMIM:243400
+";
+
+    const DISEASES: &str = "database_id\tdisease_name\tqualifier\thpo_id\treference\tevidence\tonset\tfrequency\tsex\tmodifier\taspect\tbiocuration
+OMIM:243400\tDisease 1\t\tHP:0000002\tPMID:1\tPCS\t\t\t\t\tP\tHPO:probinson[2021-06-21]
+";
+
+    const GENES_TO_DISEASE: &str = "ncbi_gene_id\tgene_symbol\tassociation_type\tdisease_id\tsource
+NCBIGene:10\tNAT2\tMENDELIAN\tOMIM:243400\tftp://ftp.omim.org/mim2gene_medgen
+";
+
+    #[test]
+    fn ontology_only() {
+        let ontology = StandardLoader::from_obo_reader(OBO.as_bytes())
+            .load()
+            .unwrap();
+        assert_eq!(ontology.len(), 3);
+        assert_eq!(ontology.genes().count(), 0);
+        assert_eq!(ontology.omim_diseases().count(), 0);
+    }
+
+    #[test]
+    fn all_sources() {
+        let (ontology, report) = StandardLoader::from_obo_reader(OBO.as_bytes())
+            .genes_to_phenotype_reader(GENES_TO_PHENOTYPE.as_bytes())
+            .diseases_reader(DISEASES.as_bytes())
+            .genes_to_disease_reader(GENES_TO_DISEASE.as_bytes())
+            .lenient_annotations(false)
+            .load_with_report()
+            .unwrap();
+
+        assert_eq!(report.len(), 1);
+        assert_eq!(ontology.genes().count(), 1);
+        let disease = ontology
+            .omim_disease(&OmimDiseaseId::from(243_400))
+            .unwrap();
+        assert!(disease.genes().contains(&GeneId::from(10)));
+    }
+
+    #[test]
+    fn strict_unknown_term() {
+        let res = StandardLoader::from_obo_reader(OBO.as_bytes())
+            .genes_to_phenotype_reader(GENES_TO_PHENOTYPE.as_bytes())
+            .load();
+        assert!(matches!(res, Err(HpoError::DoesNotExist)));
+    }
+
+    #[test]
+    fn phenotype_to_genes() {
+        let ontology = StandardLoader::from_obo_reader(OBO.as_bytes())
+            .phenotype_to_genes_reader(PHENOTYPE_TO_GENES.as_bytes())
+            .diseases_reader(DISEASES.as_bytes())
+            .load()
+            .unwrap();
+        let gene = ontology.gene(&GeneId::from(10)).unwrap();
+        assert_eq!(gene.name(), "NAT2");
+        assert_eq!(gene.hpo_terms().len(), 1);
+        assert!(gene.omim_diseases().contains(&OmimDiseaseId::from(243_400)));
+    }
+
+    #[test]
+    fn missing_file() {
+        let res = StandardLoader::from_obo_reader(OBO.as_bytes())
+            .diseases_file("tests/missing.hpoa")
+            .load();
+        assert!(matches!(res, Err(HpoError::CannotOpenFile(_))));
+    }
+}
@@ -207,6 +207,7 @@ impl<'a> Comparison<'a> {
 }
 
 /// Differences between two [`HpoTerm`]s
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HpoTermDelta {
     term_id: HpoTermId,
     changed_name: (String, String),
@@ -320,6 +321,7 @@ impl HpoTermDelta {
 }
 
 /// Differences between two [`Gene`]s or [`OmimDisease`]s
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnnotationDelta {
     id: String,
     names: (String, String),
@@ -0,0 +1,372 @@
+//! Metadata of the ontology release and of the annotation sources
+//!
+//! The metadata is kept as it is found in the source data, without
+//! any validation or normalisation.
+
+use crate::u32_from_bytes;
+use crate::HpoError;
+use crate::HpoResult;
+
+/// Metadata of an annotation source, e.g. `phenotype.hpoa`
+///
+/// It contains all `key: value` properties from the header of the source,
+/// e.g. `#version: 2024-04-26` or `#date: 2022-10-05`.
+///
+/// # Examples
+///
+/// ```
+/// use hpo::builder::StandardLoader;
+///
+/// let obo = "[Term]
+/// id: HP:0000001
+/// name: All
+///
+/// [Term]
+/// id: HP:0000118
+/// name: Phenotypic abnormality
+/// is_a: HP:0000001 ! All
+/// ";
+/// let hpoa = "#description: \"HPO annotations for rare diseases\"
+/// #version: 2024-04-26
+/// database_id\tdisease_name\tqualifier\thpo_id\treference\tevidence\tonset\tfrequency\tsex\tmodifier\taspect\tbiocuration
+/// ";
+///
+/// let ontology = StandardLoader::from_obo_reader(obo.as_bytes())
+///     .diseases_reader(hpoa.as_bytes())
+///     .load()
+///     .unwrap();
+///
+/// let annotation = &ontology.metadata().annotations()[0];
+/// assert_eq!(annotation.name(), "phenotype.hpoa");
+/// assert_eq!(annotation.version(), Some("2024-04-26"));
+/// assert_eq!(annotation.property("description"), Some("\"HPO annotations for rare diseases\""));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AnnotationMetadata {
+    name: String,
+    properties: Vec<(String, String)>,
+}
+
+impl AnnotationMetadata {
+    /// Constructs a new `AnnotationMetadata` for the source without any properties
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            properties: Vec::new(),
+        }
+    }
+
+    /// The name of the annotation source, e.g. `phenotype.hpoa`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The version of the annotation source
+    ///
+    /// This is the `version` property or, for older releases, the `date` property
+    pub fn version(&self) -> Option<&str> {
+        self.property("version").or_else(|| self.property("date"))
+    }
+
+    /// Returns the value of the first property with the given `key`
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns all properties as `(key, value)` tuples, in the order of the source
+    pub fn properties(&self) -> &[(String, String)] {
+        &self.properties
+    }
+
+    /// Adds a property to the metadata
+    pub fn add_property(&mut self, key: &str, value: &str) {
+        self.properties.push((key.to_string(), value.to_string()));
+    }
+
+    /// Returns a binary representation of the metadata
+    ///
+    /// The binary layout is defined as:
+    ///
+    /// | Byte offset | Number of bytes | Description |
+    /// | --- | --- | --- |
+    /// | 0 | 4 | The length of the name as big-endian `u32` |
+    /// | 4 | n | The name as UTF-8 |
+    /// | 4 + n | 4 | The number of properties as big-endian `u32` |
+    /// | 8 + n | ... | The key and value of each property, encoded like the name |
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::new();
+        string_as_bytes(&self.name, &mut res);
+        count_as_bytes(self.properties.len(), &mut res);
+        for (key, value) in &self.properties {
+            string_as_bytes(key, &mut res);
+            string_as_bytes(value, &mut res);
+        }
+        res
+    }
+
+    fn from_bytes(reader: &mut ByteReader) -> HpoResult<Self> {
+        let mut metadata = Self::new(&reader.string()?);
+        for _ in 0..reader.u32()? {
+            let key = reader.string()?;
+            let value = reader.string()?;
+            metadata.add_property(&key, &value);
+        }
+        Ok(metadata)
+    }
+}
+
+/// Metadata of the ontology release, e.g. from the header of `hp.obo`
+///
+/// In addition to the header of the ontology, it contains the
+/// metadata of all annotation sources, see [`AnnotationMetadata`].
+///
+/// # Examples
+///
+/// ```
+/// use hpo::Ontology;
+///
+/// let obo = "format-version: 1.2
+/// data-version: hp/releases/2024-04-26
+/// remark: first remark
+/// remark: second remark
+/// ontology: hp
+///
+/// [Term]
+/// id: HP:0000001
+/// name: All
+///
+/// [Term]
+/// id: HP:0000118
+/// name: Phenotypic abnormality
+/// is_a: HP:0000001 ! All
+/// ";
+///
+/// let ontology = Ontology::from_obo(obo.as_bytes()).unwrap();
+/// let metadata = ontology.metadata();
+/// assert_eq!(metadata.format_version(), Some("1.2"));
+/// assert_eq!(metadata.data_version(), Some("hp/releases/2024-04-26"));
+/// assert_eq!(metadata.ontology(), Some("hp"));
+/// assert_eq!(metadata.remarks(), ["first remark", "second remark"]);
+/// assert!(metadata.date().is_none());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OntologyMetadata {
+    format_version: Option<String>,
+    data_version: Option<String>,
+    ontology: Option<String>,
+    date: Option<String>,
+    remarks: Vec<String>,
+    annotations: Vec<AnnotationMetadata>,
+}
+
+impl OntologyMetadata {
+    /// The `format-version` of the OBO data, e.g. `1.2`
+    pub fn format_version(&self) -> Option<&str> {
+        self.format_version.as_deref()
+    }
+
+    /// The full `data-version`, e.g. `hp/releases/2024-04-26`
+    ///
+    /// Unlike [`Ontology::hpo_version`](`crate::Ontology::hpo_version`),
+    /// this also contains non-standard versions.
+    pub fn data_version(&self) -> Option<&str> {
+        self.data_version.as_deref()
+    }
+
+    /// The ontology ID or IRI, e.g. `hp`
+    pub fn ontology(&self) -> Option<&str> {
+        self.ontology.as_deref()
+    }
+
+    /// The `date` of the release, e.g. `26:04:2024 10:22`
+    pub fn date(&self) -> Option<&str> {
+        self.date.as_deref()
+    }
+
+    /// All remarks of the release
+    pub fn remarks(&self) -> &[String] {
+        &self.remarks
+    }
+
+    /// The metadata of all annotation sources
+    pub fn annotations(&self) -> &[AnnotationMetadata] {
+        &self.annotations
+    }
+
+    /// Returns the metadata of the annotation source with the given `name`
+    pub fn annotation(&self, name: &str) -> Option<&AnnotationMetadata> {
+        self.annotations
+            .iter()
+            .find(|annotation| annotation.name() == name)
+    }
+
+    /// Sets the `format-version`
+    pub fn set_format_version(&mut self, version: &str) {
+        self.format_version = Some(version.to_string());
+    }
+
+    /// Sets the `data-version`
+    pub fn set_data_version(&mut self, version: &str) {
+        self.data_version = Some(version.to_string());
+    }
+
+    /// Sets the ontology ID or IRI
+    pub fn set_ontology(&mut self, ontology: &str) {
+        self.ontology = Some(ontology.to_string());
+    }
+
+    /// Sets the `date` of the release
+    pub fn set_date(&mut self, date: &str) {
+        self.date = Some(date.to_string());
+    }
+
+    /// Adds a remark
+    pub fn add_remark(&mut self, remark: &str) {
+        self.remarks.push(remark.to_string());
+    }
+
+    /// Adds the metadata of an annotation source
+    ///
+    /// Metadata of a source with the same name is replaced
+    pub fn add_annotation(&mut self, annotation: AnnotationMetadata) {
+        self.annotations
+            .retain(|existing| existing.name() != annotation.name());
+        self.annotations.push(annotation);
+    }
+
+    /// Returns a binary representation of the metadata
+    ///
+    /// The binary layout is defined as:
+    ///
+    /// | Byte offset | Number of bytes | Description |
+    /// | --- | --- | --- |
+    /// | 0 | ... | `format-version`, `data-version`, ontology and date |
+    /// | ... | 4 | The number of remarks as big-endian `u32` |
+    /// | ... | ... | All remarks |
+    /// | ... | 4 | The number of annotation sources as big-endian `u32` |
+    /// | ... | ... | All annotation sources, see `AnnotationMetadata::as_bytes` |
+    ///
+    /// Every string is encoded as its length (big-endian `u32`), followed by
+    /// the UTF-8 bytes. Missing values are encoded as empty strings.
+    pub(crate) fn as_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::new();
+        for value in [
+            &self.format_version,
+            &self.data_version,
+            &self.ontology,
+            &self.date,
+        ] {
+            string_as_bytes(value.as_deref().unwrap_or_default(), &mut res);
+        }
+        count_as_bytes(self.remarks.len(), &mut res);
+        for remark in &self.remarks {
+            string_as_bytes(remark, &mut res);
+        }
+        count_as_bytes(self.annotations.len(), &mut res);
+        for annotation in &self.annotations {
+            res.append(&mut annotation.as_bytes());
+        }
+        res
+    }
+
+    /// Parses the binary representation of the metadata
+    ///
+    /// # Errors
+    ///
+    /// [`HpoError::ParseBinaryError`]: The data is incomplete or contains invalid UTF-8
+    pub(crate) fn from_bytes(bytes: &[u8]) -> HpoResult<Self> {
+        let mut reader = ByteReader { bytes, idx: 0 };
+        let mut optional = || -> HpoResult<Option<String>> {
+            Ok(Some(reader.string()?).filter(|value| !value.is_empty()))
+        };
+        let mut metadata = Self {
+            format_version: optional()?,
+            data_version: optional()?,
+            ontology: optional()?,
+            date: optional()?,
+            ..Default::default()
+        };
+        for _ in 0..reader.u32()? {
+            metadata.remarks.push(reader.string()?);
+        }
+        for _ in 0..reader.u32()? {
+            metadata
+                .annotations
+                .push(AnnotationMetadata::from_bytes(&mut reader)?);
+        }
+        if reader.idx == bytes.len() {
+            Ok(metadata)
+        } else {
+            Err(HpoError::ParseBinaryError)
+        }
+    }
+}
+
+fn count_as_bytes(n: usize, res: &mut Vec<u8>) {
+    let n: u32 = n.try_into().expect("too many metadata entries");
+    res.extend_from_slice(&n.to_be_bytes());
+}
+
+fn string_as_bytes(value: &str, res: &mut Vec<u8>) {
+    count_as_bytes(value.len(), res);
+    res.extend_from_slice(value.as_bytes());
+}
+
+/// Reads `u32`s and strings from binary metadata
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    idx: usize,
+}
+
+impl ByteReader<'_> {
+    fn u32(&mut self) -> HpoResult<u32> {
+        let bytes = self
+            .bytes
+            .get(self.idx..self.idx + 4)
+            .ok_or(HpoError::ParseBinaryError)?;
+        self.idx += 4;
+        Ok(u32_from_bytes(bytes))
+    }
+
+    fn string(&mut self) -> HpoResult<String> {
+        let len = self.u32()? as usize;
+        let bytes = self
+            .bytes
+            .get(self.idx..self.idx + len)
+            .ok_or(HpoError::ParseBinaryError)?;
+        self.idx += len;
+        String::from_utf8(bytes.to_vec()).map_err(|_| HpoError::ParseBinaryError)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn metadata_to_and_from_bytes() {
+        let mut metadata = OntologyMetadata::default();
+        metadata.set_format_version("1.2");
+        metadata.set_data_version("hp/releases/2024-04-26");
+        metadata.add_remark("first");
+        metadata.add_remark("");
+        let mut annotation = AnnotationMetadata::new("phenotype.hpoa");
+        annotation.add_property("version", "2024-04-26");
+        metadata.add_annotation(annotation);
+
+        let bytes = metadata.as_bytes();
+        assert_eq!(OntologyMetadata::from_bytes(&bytes).unwrap(), metadata);
+        assert!(OntologyMetadata::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn empty_metadata_to_and_from_bytes() {
+        let metadata = OntologyMetadata::default();
+        let bytes = metadata.as_bytes();
+        assert_eq!(bytes.len(), 24);
+        assert_eq!(OntologyMetadata::from_bytes(&bytes).unwrap(), metadata);
+    }
+}
@@ -6,17 +6,33 @@
 //! - `terms` holds all `HpoTerm`s (`HpoTermInternal`) with one element for every term
 //!   in the ontology. The terms are not sorted and are added in the same order they
 //!   are defined in the source data. The order has no impact.
-//! - `ids` is a lookup table that contains one element for every possible [`HpoTermId`].
-//!   HPO-Term-IDs can be any integer between 1 and 10 Million, so it has 10,000,000 elements.
-//!   Each element contains either `0` (no such term present) or the index of the
-//!   corresponding term in `terms`.
+//! - `ids` is a lookup table that returns the index of the corresponding term in `terms`
+//!   for every [`HpoTermId`], or `0` if no such term is present.
 //!
 //! To account for the fact that a `0` value in `ids` signals that no term is present, the
 //! first entry in `terms` is a fake `HpoTerm`.
 //!
+//! # Lookup table
+//!
+//! HPO-Term-IDs can be any integer, but they are not evenly distributed. Almost all IDs are
+//! below 1 million, mostly between 1 and ~100,000, and a few small ranges exist at 3 million,
+//! 4 million and 5 million. The lookup table `ids` therefore consists of two parts:
+//!
+//! - `direct` contains one element for every ID below `DIRECT_IDS`, up to the highest
+//!   inserted ID. Each element contains either `0` (no such term present) or the index of the
+//!   corresponding term in `terms`.
+//! - All higher IDs are split into pages of `PAGE_SIZE` consecutive IDs. Only pages that
+//!   contain at least one term are allocated, all others point to a shared empty page:
+//!     - `pages` contains one element for every possible page, up to the highest ID.
+//!       Each element is the offset of the page in `slots`.
+//!     - `slots` contains `PAGE_SIZE` elements for every allocated page, plus the empty page
+//!       at offset `0`. Each element contains either `0` or the index of the term in `terms`.
+//!
 //! # Data layout example
 //!
-//! ## `ids` Vector
+//! Using a `DIRECT_IDS` of 8 and a `PAGE_SIZE` of 4
+//!
+//! ## `direct` Vector
 //!
 //! |  i  | value |
 //! | --- | --- |
@@ -29,6 +45,25 @@
 //! |  6  |  3  |
 //! |  7  |  4  |
 //!
+//! ## `pages` Vector
+//!
+//! |  i  | IDs | value |
+//! | --- | --- | --- |
+//! |  0  | 0 - 3 |  0  |
+//! |  1  | 4 - 7 |  0  |
+//! |  2  | 8 - 11 |  0  |
+//! |  3  | 12 - 15 |  4  |
+//!
+//! ## `slots` Vector
+//!
+//! |  i  | ID | value |
+//! | --- | --- | --- |
+//! | 0 - 3 | - (empty page) |  0  |
+//! |  4  | 12 |  0  |
+//! |  5  | 13 |  6  |
+//! |  6  | 14 |  0  |
+//! |  7  | 15 |  0  |
+//!
 //!
 //! ## `terms` Vector
 //!
@@ -40,16 +75,18 @@
 //! |  3  |  6  |
 //! |  4  |  7  |
 //! |  5  |  3  |
+//! |  6  |  13  |
 //!
 //!
 //! This is a bit like a cheap version of a Hashmap. The advantage over a hashmap are
 //!
 //! - very cheap hash algorithm - The integer representation for each term is given with the term ID
 //! - we don't have to worry about or account for hash collisions
-//! - we know the size of the lookup vector and can allocate it once
+//! - the size of the lookup table is proportional to the actual ID ranges, less than 1 MB
+//!   for the full ontology, so creating and cloning an `Ontology` is cheap
 //! - we have an extremely good estimate of the amount of HPO terms, so we can pre-allocate that vector as well
 //! - every lookup is O(3)
-//!     - get `idx` from `ids` vector
+//!     - get `idx` from `direct` vector (or from `pages` and `slots` for the few high IDs)
 //!     - check if `idx` is 0
 //!     - get `HpoTermInternal` from `terms` vector
 //!
@@ -62,31 +99,125 @@
 //! Since the retrieval of `HpoTerm`s from the ontology is the most frequent operation, I wanted to optimize
 //! this lookup as much as possible.
 //!
+//! # Alternative IDs
+//!
+//! Terms that were merged into another term keep their ID as an alternative ID (`alt_id`)
+//! of the remaining term. Alternative IDs are not part of `ids`, but are kept in a separate
+//! `alt_ids` lookup table that maps each alternative ID to the ID of its primary term.
+//! This way, [`Arena::get`] only ever returns terms by their primary ID.
+//!
+//! # Cross-references
+//!
+//! The `xrefs` table is a reverse index from each [`Xref`] to the terms
+//! that reference it. Likewise, `subsets` maps the name of each subset to
+//! the terms that belong to it.
+//!
 //! If you're looking at this and are shaking your head, please let me know how to improve this. I'd love to hear feedback,
 //! this part of the crate is the most performance-critical and I am also very interested in learning how to improve
 //! such data structures.
 //!
 
-#![allow(clippy::slow_vector_initialization)]
+use std::collections::HashMap;
+
 use crate::term::internal::HpoTermInternal;
+use crate::term::{HpoGroup, Xref};
 use crate::HpoTermId;
-use tracing::{trace, warn};
+use tracing::trace;
+
+/// [`HpoTermId`]s below this value are stored in the `direct` table of [`Lookup`]
+const DIRECT_IDS: usize = 1 << 18;
+/// The number of bits of an [`HpoTermId`] that address a slot within a page
+const PAGE_BITS: usize = 10;
+/// The number of consecutive [`HpoTermId`]s in each page of the [`Lookup`] table
+const PAGE_SIZE: usize = 1 << PAGE_BITS;
+const PAGE_MASK: usize = PAGE_SIZE - 1;
+
+/// Lookup table from [`HpoTermId`] to the index of the term in the `Arena`
+///
+/// See the module documentation for the data layout
+#[derive(Clone)]
+struct Lookup {
+    direct: Vec<u32>,
+    pages: Vec<u32>,
+    slots: Vec<u32>,
+}
 
-use crate::MAX_HPO_ID_INTEGER as HPO_TERM_NUMBERS;
+impl Default for Lookup {
+    fn default() -> Self {
+        Self {
+            direct: Vec::new(),
+            pages: Vec::new(),
+            slots: vec![0; PAGE_SIZE],
+        }
+    }
+}
+
+impl Lookup {
+    /// Returns the index of the term with the given ID or `0` if no such term is present
+    #[inline]
+    fn get(&self, id: usize) -> usize {
+        match self.direct.get(id) {
+            Some(idx) => *idx as usize,
+            None => self.get_paged(id),
+        }
+    }
+
+    /// Returns the index of a term that is not part of the `direct` table
+    #[cold]
+    #[inline(never)]
+    fn get_paged(&self, id: usize) -> usize {
+        match self.pages.get(id >> PAGE_BITS) {
+            Some(offset) => self.slots[*offset as usize | (id & PAGE_MASK)] as usize,
+            None => 0,
+        }
+    }
+
+    /// Sets the index of the term with the given ID
+    ///
+    /// The `direct` table or the page of the ID is grown or allocated if needed
+    ///
+    /// # Panics
+    ///
+    /// If the index or the number of slots exceeds `u32::MAX`
+    fn insert(&mut self, id: usize, idx: usize) {
+        let idx = u32::try_from(idx).expect("the number of terms must fit into u32");
+        if id < DIRECT_IDS {
+            if id >= self.direct.len() {
+                self.direct.resize(id + 1, 0);
+            }
+            self.direct[id] = idx;
+            return;
+        }
+        let page_idx = id >> PAGE_BITS;
+        if page_idx >= self.pages.len() {
+            self.pages.resize(page_idx + 1, 0);
+        }
+        if self.pages[page_idx] == 0 {
+            self.pages[page_idx] =
+                u32::try_from(self.slots.len()).expect("the number of slots must fit into u32");
+            self.slots.resize(self.slots.len() + PAGE_SIZE, 0);
+        }
+        self.slots[self.pages[page_idx] as usize | (id & PAGE_MASK)] = idx;
+    }
+}
 
 #[derive(Clone)]
 pub(super) struct Arena {
     terms: Vec<HpoTermInternal>,
-    ids: Vec<usize>,
+    ids: Lookup,
+    alt_ids: HashMap<HpoTermId, HpoTermId>,
+    xrefs: HashMap<Xref, HpoGroup>,
+    subsets: HashMap<String, HpoGroup>,
 }
 
 impl Default for Arena {
     fn default() -> Self {
-        let mut ids = Vec::with_capacity(HPO_TERM_NUMBERS);
-        ids.resize(HPO_TERM_NUMBERS, 0);
         let mut s = Self {
             terms: Vec::with_capacity(18_000),
-            ids,
+            ids: Lookup::default(),
+            alt_ids: HashMap::new(),
+            xrefs: HashMap::new(),
+            subsets: HashMap::new(),
         };
         s.terms.push(HpoTermInternal::default());
         s
@@ -102,29 +233,64 @@ impl Arena {
     /// Inserts a new [`HpoTerm`](crate::term::HpoTerm) into the arena
     ///
     /// If a term with the same ID exists already, it does nothing
+    ///
+    /// All alternative IDs, cross-references and subsets of the term are registered as well
     pub fn insert(&mut self, term: HpoTermInternal) {
         let id = term.id().to_usize();
-        if self.ids[id] == 0 {
+        if self.ids.get(id) == 0 {
+            for alt_id in term.alt_ids() {
+                self.alt_ids.insert(*alt_id, *term.id());
+            }
+            for xref in term.xrefs() {
+                self.xrefs
+                    .entry(xref.clone())
+                    .or_default()
+                    .insert(*term.id());
+            }
+            for subset in term.subsets() {
+                self.subsets
+                    .entry(subset.clone())
+                    .or_default()
+                    .insert(*term.id());
+            }
             let idx = self.terms.len();
             self.terms.push(term);
-            self.ids[id] = idx;
+            self.ids.insert(id, idx);
         }
     }
 
+    /// Returns the [`HpoTermInternal`] that has the given `HpoTermId` as
+    /// an alternative ID
+    ///
+    /// If no term has this alternative ID, returns `None`
+    pub fn get_by_alt_id(&self, alt_id: HpoTermId) -> Option<&HpoTermInternal> {
+        self.alt_ids.get(&alt_id).and_then(|id| self.get(*id))
+    }
+
+    /// Returns the [`HpoGroup`] of all terms that have the given [`Xref`]
+    ///
+    /// If no term has this cross-reference, returns `None`
+    pub fn get_by_xref(&self, xref: &Xref) -> Option<&HpoGroup> {
+        self.xrefs.get(xref)
+    }
+
+    /// Returns the [`HpoGroup`] of all terms that belong to the given subset
+    ///
+    /// If no term belongs to the subset, returns `None`
+    pub fn get_by_subset(&self, subset: &str) -> Option<&HpoGroup> {
+        self.subsets.get(subset)
+    }
+
     /// Returns the [`HpoTermInternal`] with the given `HpoTermId`
     ///
     /// If no such term is present, returns `None`
     pub fn get(&self, id: HpoTermId) -> Option<&HpoTermInternal> {
         match self.ids.get(id.to_usize()) {
-            Some(0) => {
+            0 => {
                 trace!("Term does not exist in Arena: {}", id);
                 None
             }
-            Some(n) => Some(&self.terms[*n]),
-            None => {
-                warn!("Index of Arena out of bounds for {id}");
-                None
-            }
+            n => Some(&self.terms[n]),
         }
     }
 
@@ -137,7 +303,7 @@ impl Arena {
     ///
     /// If no `HpoTerm` with the given ID exists in the Arena
     pub fn get_unchecked(&self, id: HpoTermId) -> &HpoTermInternal {
-        &self.terms[self.ids[id.to_usize()]]
+        &self.terms[self.ids.get(id.to_usize())]
     }
 
     /// Returns a mutable reference to the [`HpoTermInternal`] with the given `HpoTermId`
@@ -149,7 +315,7 @@ impl Arena {
     ///
     /// If no `HpoTerm` with the given ID exists in the Arena
     pub fn get_unchecked_mut(&mut self, id: HpoTermId) -> &mut HpoTermInternal {
-        &mut self.terms[self.ids[id.to_usize()]]
+        &mut self.terms[self.ids.get(id.to_usize())]
     }
 
     /// Returns a mutable reference to the [`HpoTermInternal`] with the given `HpoTermId`
@@ -157,12 +323,8 @@ impl Arena {
     /// If no such term is present, returns `None`
     pub fn get_mut(&mut self, id: HpoTermId) -> Option<&mut HpoTermInternal> {
         match self.ids.get(id.to_usize()) {
-            Some(0) => None,
-            Some(n) => Some(&mut self.terms[*n]),
-            None => {
-                warn!("Index of Arena out of bounds for {id}");
-                None
-            }
+            0 => None,
+            n => Some(&mut self.terms[n]),
         }
     }
 
@@ -201,3 +363,38 @@ impl Iterator for Iter<'_> {
         self.0.next()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn insert_and_get_across_pages() {
+        let mut arena = Arena::default();
+        for id in [1u32, 4, 1023, 1024, 3_000_050, 5_200_001, 99_999_999] {
+            arena.insert(HpoTermInternal::new(format!("Term {id}"), id.into()));
+        }
+        assert_eq!(arena.len(), 7);
+        assert_eq!(arena.ids.direct.len(), 1025);
+        assert_eq!(arena.ids.slots.len(), 4 * PAGE_SIZE);
+
+        for id in [1u32, 4, 1023, 1024, 3_000_050, 5_200_001, 99_999_999] {
+            let term = arena.get(id.into()).expect("term must be present");
+            assert_eq!(term.id(), &HpoTermId::from(id));
+            assert_eq!(term.name(), format!("Term {id}"));
+        }
+
+        for id in [0u32, 2, 1025, 2048, 3_000_051, 100_000_000] {
+            assert!(arena.get(id.into()).is_none());
+        }
+    }
+
+    #[test]
+    fn insert_duplicate() {
+        let mut arena = Arena::default();
+        arena.insert(HpoTermInternal::new("Foo".to_string(), 5u32.into()));
+        arena.insert(HpoTermInternal::new("Bar".to_string(), 5u32.into()));
+        assert_eq!(arena.len(), 1);
+        assert_eq!(arena.get(5u32.into()).unwrap().name(), "Foo");
+    }
+}
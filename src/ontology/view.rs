@@ -0,0 +1,424 @@
+//! Read-only, memory-mapped view of a binary ontology
+
+use std::fmt::Debug;
+use std::fs::File;
+use std::ops::Range;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::parser::binary::ontology::{
+    verify_checksum, version, Section, SectionDirectory, TERM_INDEX_ENTRY_LEN,
+};
+use crate::parser::binary::{checked_slice, BinaryVersion};
+use crate::{u32_from_bytes, HpoError, HpoResult, HpoTermId};
+
+/// A read-only view of a binary ontology that is backed by a memory-mapped file
+///
+/// In contrast to [`Ontology::from_binary`](`crate::Ontology::from_binary`),
+/// the file is not parsed into an [`Ontology`](`crate::Ontology`). All terms are
+/// read directly from the mapped file when they are accessed. Opening the view
+/// is therefore almost instant and several processes that open the same file
+/// share the same memory pages.
+///
+/// # Scope
+///
+/// The view only provides the IDs, names, parents and ancestors of all terms.
+/// This is enough to look up terms or to check if one term is a subclass of
+/// another, e.g. to filter or expand a list of terms.
+///
+/// The view does **not** provide genes, diseases or the information content
+/// of the terms. Similarity calculations and enrichments need those, so they
+/// require a full [`Ontology`](`crate::Ontology`), e.g. from
+/// [`Ontology::from_binary`](`crate::Ontology::from_binary`).
+///
+/// Only uncompressed binary files of version 4 or newer, as created by
+/// [`Ontology::as_bytes`](`crate::Ontology::as_bytes`), are supported.
+///
+/// This struct is only available with the `mmap` feature.
+///
+/// # Examples
+///
+/// ```
+/// use hpo::{Ontology, OntologyView};
+///
+/// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+/// let filename = std::env::temp_dir().join("hpo_doctest_view.hpo");
+/// std::fs::write(&filename, ontology.as_bytes()).unwrap();
+///
+/// let view = OntologyView::open(&filename).unwrap();
+/// assert_eq!(view.len(), ontology.len());
+///
+/// let term = view.hpo(11017u32).unwrap();
+/// assert_eq!(term.name(), "Abnormal cellular physiology");
+/// assert!(term.all_parent_ids().any(|id| id == "HP:0000001"));
+/// # std::fs::remove_file(filename).unwrap();
+/// ```
+pub struct OntologyView {
+    mmap: Mmap,
+    hpo_version: (u16, u8, u8),
+    index: Range<usize>,
+    terms: Range<usize>,
+    parents: Range<usize>,
+    ancestors: Range<usize>,
+}
+
+impl OntologyView {
+    /// Opens a binary ontology file as a memory-mapped view
+    ///
+    /// The file is validated once when it is opened, so that accessing
+    /// terms later on never fails.
+    ///
+    /// The file must not be modified while the view is open.
+    ///
+    /// # Errors
+    ///
+    /// - The file is not present or can't be mapped: [`HpoError::CannotOpenFile`]
+    /// - The binary version does not support direct access or a required
+    ///   section is missing: [`HpoError::InvalidInput`]
+    /// - The header or section directory is incomplete: [`HpoError::BinarySectionError`]
+    /// - Invalid term data: [`HpoError::ParseBinaryError`]
+    /// - Invalid binary version: [`HpoError::NotImplemented`]
+    pub fn open<P: AsRef<Path>>(filename: P) -> HpoResult<Self> {
+        let path = filename.as_ref();
+        let file =
+            File::open(path).map_err(|_| HpoError::CannotOpenFile(path.display().to_string()))?;
+
+        // SAFETY: The mapped file is only ever read. Modifying or truncating
+        // the file while it is mapped is undefined behaviour, which is
+        // documented as a requirement of this method.
+        let mmap = unsafe { Mmap::map(&file) }
+            .map_err(|_| HpoError::CannotOpenFile(path.display().to_string()))?;
+
+        Self::from_mmap(mmap)
+    }
+
+    fn from_mmap(mmap: Mmap) -> HpoResult<Self> {
+        let (hpo_version, [index, terms, parents, ancestors]) = {
+            let data = version(&mmap)?;
+            if data.version() < BinaryVersion::V4 {
+                return Err(HpoError::InvalidInput(format!(
+                    "binary version {} does not support direct access, version 4 or newer is required",
+                    data.version()
+                )));
+            }
+            checked_slice(&mmap, 0..8, "header")?;
+            let hpo_version = (u16::from_be_bytes([data[0], data[1]]), data[2], data[3]);
+
+            let directory = SectionDirectory::from_bytes(&mmap, 8)?;
+            let mut ranges = [0..0, 0..0, 0..0, 0..0];
+            for (range, section) in ranges.iter_mut().zip([
+                Section::TermIndex,
+                Section::Terms,
+                Section::Parents,
+                Section::Ancestors,
+            ]) {
+                *range = directory.range(section).ok_or_else(|| {
+                    HpoError::InvalidInput(format!(
+                        "binary data does not contain the {} section",
+                        section.name()
+                    ))
+                })?;
+            }
+            (hpo_version, ranges)
+        };
+
+        let view = Self {
+            mmap,
+            hpo_version,
+            index,
+            terms,
+            parents,
+            ancestors,
+        };
+        view.validate()?;
+        Ok(view)
+    }
+
+    /// Ensures that every entry of the term index points to valid term data
+    fn validate(&self) -> HpoResult<()> {
+        let index = self.index_data();
+        if !index.len().is_multiple_of(TERM_INDEX_ENTRY_LEN) {
+            return Err(HpoError::ParseBinaryError);
+        }
+        let mut previous = None;
+        for entry in index.chunks_exact(TERM_INDEX_ENTRY_LEN) {
+            let term = self
+                .term_from_entry(entry)
+                .ok_or(HpoError::ParseBinaryError)?;
+            if previous >= Some(term.id) {
+                return Err(HpoError::ParseBinaryError);
+            }
+            previous = Some(term.id);
+        }
+        Ok(())
+    }
+
+    /// Verifies the checksum of the binary file
+    ///
+    /// Opening the view only validates the structure of the term data, so that
+    /// opening is fast even for large files. Use this method to also detect
+    /// corrupt data, e.g. after downloading a file.
+    ///
+    /// # Errors
+    ///
+    /// [`HpoError::BinaryChecksumError`]: The file is corrupt
+    pub fn verify_checksum(&self) -> HpoResult<()> {
+        verify_checksum(&self.mmap, 8)
+    }
+
+    /// Returns the HPO release version, e.g. `2023-04-05`
+    pub fn hpo_version(&self) -> String {
+        format!(
+            "{:0>4}-{:0>2}-{:0>2}",
+            self.hpo_version.0, self.hpo_version.1, self.hpo_version.2,
+        )
+    }
+
+    /// Returns the number of HPO-Terms in the view
+    pub fn len(&self) -> usize {
+        self.index_data().len() / TERM_INDEX_ENTRY_LEN
+    }
+
+    /// Returns `true` if the view does not contain any HPO-Terms
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the [`TermView`] of the provided [`HpoTermId`]
+    ///
+    /// If no such term is present, `None` is returned
+    pub fn hpo<I: Into<HpoTermId>>(&self, term_id: I) -> Option<TermView<'_>> {
+        let term_id = term_id.into();
+        let index = self.index_data();
+        let mut low = 0;
+        let mut high = self.len();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let entry = &index[mid * TERM_INDEX_ENTRY_LEN..(mid + 1) * TERM_INDEX_ENTRY_LEN];
+            match HpoTermId::from(u32_from_bytes(entry)).cmp(&term_id) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => return self.term_from_entry(entry),
+            }
+        }
+        None
+    }
+
+    /// Returns an iterator of all terms, sorted by their [`HpoTermId`]
+    pub fn iter(&self) -> impl Iterator<Item = TermView<'_>> {
+        self.index_data()
+            .chunks_exact(TERM_INDEX_ENTRY_LEN)
+            .filter_map(|entry| self.term_from_entry(entry))
+    }
+
+    fn index_data(&self) -> &[u8] {
+        &self.mmap[self.index.clone()]
+    }
+
+    /// Reads the term of an entry of the term index
+    ///
+    /// Returns `None` if the entry does not point to valid data
+    fn term_from_entry(&self, entry: &[u8]) -> Option<TermView<'_>> {
+        let id = u32_from_bytes(&entry[0..4]);
+
+        let term_offset = u32_from_bytes(&entry[4..8]) as usize;
+        let terms = &self.mmap[self.terms.clone()];
+        let term_len = u32_from_bytes(terms.get(term_offset..term_offset + 4)?) as usize;
+        let term = terms.get(term_offset..term_offset + term_len)?;
+        if term.len() < 9 || u32_from_bytes(&term[4..8]) != id {
+            return None;
+        }
+        let name_len = term[8] as usize;
+        let name = std::str::from_utf8(term.get(9..9 + name_len)?).ok()?;
+        let flags = term.get(9 + name_len..14 + name_len)?;
+
+        let parents_offset = u32_from_bytes(&entry[8..12]) as usize;
+        let parents = &self.mmap[self.parents.clone()];
+        let n_parents = u32_from_bytes(parents.get(parents_offset..parents_offset + 4)?) as usize;
+        let parents = parents.get(parents_offset + 4..parents_offset + 8 + 4 * n_parents)?;
+        if u32_from_bytes(parents) != id {
+            return None;
+        }
+
+        let ancestors_offset = u32_from_bytes(&entry[12..16]) as usize;
+        let ancestors = &self.mmap[self.ancestors.clone()];
+        let n_ancestors =
+            u32_from_bytes(ancestors.get(ancestors_offset..ancestors_offset + 4)?) as usize;
+        let ancestors =
+            ancestors.get(ancestors_offset + 4..ancestors_offset + 4 + 4 * n_ancestors)?;
+
+        Some(TermView {
+            id: id.into(),
+            name,
+            obsolete: flags[0] & 1u8 == 1u8,
+            replacement: u32_from_bytes(&flags[1..]),
+            parents: &parents[4..],
+            ancestors,
+        })
+    }
+}
+
+impl Debug for OntologyView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OntologyView")
+            .field("hpo_version", &self.hpo_version())
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A single HPO term of an [`OntologyView`]
+///
+/// All data is borrowed directly from the memory-mapped file.
+#[derive(Debug, Clone, Copy)]
+pub struct TermView<'a> {
+    id: HpoTermId,
+    name: &'a str,
+    obsolete: bool,
+    replacement: u32,
+    parents: &'a [u8],
+    ancestors: &'a [u8],
+}
+
+impl<'a> TermView<'a> {
+    /// Returns the [`HpoTermId`] of the term
+    pub fn id(&self) -> HpoTermId {
+        self.id
+    }
+
+    /// Returns the name of the term
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns `true` if the term is marked as obsolete
+    pub fn is_obsolete(&self) -> bool {
+        self.obsolete
+    }
+
+    /// Returns the [`HpoTermId`] of the replacement term, if the term is obsolete
+    /// and has a replacement
+    pub fn replacement_id(&self) -> Option<HpoTermId> {
+        if self.replacement == 0 {
+            None
+        } else {
+            Some(self.replacement.into())
+        }
+    }
+
+    /// Returns the [`HpoTermId`]s of the direct parents
+    pub fn parent_ids(&self) -> TermIds<'a> {
+        TermIds(self.parents.chunks_exact(4))
+    }
+
+    /// Returns the [`HpoTermId`]s of all direct and indirect parents, sorted ascending
+    pub fn all_parent_ids(&self) -> TermIds<'a> {
+        TermIds(self.ancestors.chunks_exact(4))
+    }
+
+    /// Returns `true` if `self` is a direct or indirect child of `other`
+    pub fn child_of(&self, other: &TermView) -> bool {
+        let mut low = 0;
+        let mut high = self.ancestors.len() / 4;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match HpoTermId::from(u32_from_bytes(&self.ancestors[mid * 4..])).cmp(&other.id) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => return true,
+            }
+        }
+        false
+    }
+
+    /// Returns `true` if `self` is a direct or indirect parent of `other`
+    pub fn parent_of(&self, other: &TermView) -> bool {
+        other.child_of(self)
+    }
+}
+
+/// An iterator of [`HpoTermId`]s, read directly from the memory-mapped file
+#[derive(Debug, Clone)]
+pub struct TermIds<'a>(std::slice::ChunksExact<'a, u8>);
+
+impl Iterator for TermIds<'_> {
+    type Item = HpoTermId;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|bytes| u32_from_bytes(bytes).into())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for TermIds<'_> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Ontology;
+
+    fn write_binary(name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let filename = std::env::temp_dir().join(name);
+        std::fs::write(&filename, bytes).unwrap();
+        filename
+    }
+
+    #[test]
+    fn view_matches_ontology() {
+        let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+        let filename = write_binary("hpo_test_view.hpo", &ontology.as_bytes());
+        let view = OntologyView::open(&filename).unwrap();
+        assert!(view.verify_checksum().is_ok());
+
+        assert_eq!(view.len(), ontology.len());
+        assert_eq!(view.hpo_version(), ontology.hpo_version());
+        assert_eq!(view.iter().count(), ontology.len());
+        for term in &ontology {
+            let term_view = view.hpo(term.id()).unwrap();
+            assert_eq!(term_view.name(), term.name());
+            assert_eq!(term_view.is_obsolete(), term.is_obsolete());
+            assert_eq!(term_view.replacement_id(), term.replacement_id());
+            assert!(term_view.parent_ids().eq(term.parent_ids().iter()));
+            assert!(term_view.all_parent_ids().eq(term.all_parent_ids().iter()));
+        }
+        assert!(view.hpo(66666u32).is_none());
+
+        let root = view.hpo(1u32).unwrap();
+        let term = view.hpo(11017u32).unwrap();
+        assert!(term.child_of(&root));
+        assert!(root.parent_of(&term));
+        assert!(!root.child_of(&term));
+
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn view_requires_version_4() {
+        let filename = write_binary(
+            "hpo_test_view_v3.hpo",
+            &std::fs::read("tests/example.hpo").unwrap(),
+        );
+        assert!(matches!(
+            OntologyView::open(&filename),
+            Err(HpoError::InvalidInput(_))
+        ));
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn view_of_corrupt_data() {
+        let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+        let bytes = ontology.as_bytes();
+        let filename = write_binary("hpo_test_view_truncated.hpo", &bytes[..bytes.len() - 10]);
+        assert!(OntologyView::open(&filename).is_err());
+        std::fs::remove_file(filename).unwrap();
+
+        assert!(matches!(
+            OntologyView::open("tests/missing.hpo"),
+            Err(HpoError::CannotOpenFile(_))
+        ));
+    }
+}
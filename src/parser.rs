@@ -1,30 +1,211 @@
 //! Parsing the HPO master data provided by Jax
 
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 use std::path::Path;
 
-use builder::Builder;
+use tracing::warn;
 
-use crate::{ontology::builder, HpoResult, Ontology};
+use crate::{HpoError, HpoResult, HpoTermId};
 
 pub(crate) mod binary;
 /// Module to parse `hp.obo` file
 pub(crate) mod hp_obo;
+/// Module to parse `hp.json` file in OBO Graphs format
+#[cfg(feature = "obographs")]
+pub(crate) mod obographs;
+
+/// The first two bytes of every gzip-compressed file
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+
+/// Opens the file for buffered reading
+///
+/// With the `gzip` feature, gzip-compressed files are decompressed
+/// transparently. Compression is detected by the magic bytes at the
+/// start of the file, not by the file extension.
+///
+/// # Errors
+///
+/// - [`HpoError::CannotOpenFile`]: The file is not present or can't be read
+/// - [`HpoError::InvalidInput`]: The file is gzip-compressed, but the `gzip` feature is not enabled
+pub(crate) fn open_file<P: AsRef<Path>>(file: P) -> HpoResult<Box<dyn BufRead>> {
+    let filename = file.as_ref().display().to_string();
+    let file = File::open(file).map_err(|_| HpoError::CannotOpenFile(filename.clone()))?;
+    let mut reader = BufReader::new(file);
+    let compressed = reader
+        .fill_buf()
+        .map_err(|_| HpoError::CannotOpenFile(filename.clone()))?
+        .starts_with(&GZIP_MAGIC_BYTES);
+
+    if !compressed {
+        return Ok(Box::new(reader));
+    }
+
+    #[cfg(feature = "gzip")]
+    {
+        Ok(Box::new(BufReader::new(
+            flate2::bufread::MultiGzDecoder::new(reader),
+        )))
+    }
+    #[cfg(not(feature = "gzip"))]
+    {
+        Err(HpoError::InvalidInput(format!(
+            "{filename} is gzip-compressed, enable the `gzip` feature to read it"
+        )))
+    }
+}
+
+/// Parses the date of an HPO release, e.g. `2023-04-05`, into year, month and day
+///
+/// Returns `None` if the date does not start with a `YYYY-MM-DD` date
+pub(crate) fn release_version(date: &str) -> Option<(u16, u8, u8)> {
+    Some((
+        date.get(0..4)?.parse().ok()?,
+        date.get(5..7)?.parse().ok()?,
+        date.get(8..10)?.parse().ok()?,
+    ))
+}
+
+/// An annotation line that was skipped or remapped during lenient loading
+///
+/// See [`Builder::lenient_annotations`](`crate::builder::Builder::lenient_annotations`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadIssue {
+    /// The line references a term that is not present in the ontology
+    /// and was skipped
+    Skipped {
+        /// The name of the file or `<reader>`
+        file: String,
+        /// The line number
+        line: usize,
+        /// The offending line
+        text: String,
+        /// The referenced term
+        term_id: HpoTermId,
+    },
+    /// The line references an obsolete term or an alternative ID and
+    /// was added to the replacement term instead
+    Remapped {
+        /// The name of the file or `<reader>`
+        file: String,
+        /// The line number
+        line: usize,
+        /// The remapped line
+        text: String,
+        /// The referenced term
+        term_id: HpoTermId,
+        /// The term that was used instead
+        replacement: HpoTermId,
+    },
+}
+
+impl Display for LoadIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadIssue::Skipped {
+                file,
+                line,
+                text,
+                term_id,
+            } => write!(
+                f,
+                "skipped {file}, line {line} ({term_id} does not exist): {text}"
+            ),
+            LoadIssue::Remapped {
+                file,
+                line,
+                text,
+                term_id,
+                replacement,
+            } => write!(
+                f,
+                "remapped {file}, line {line} ({term_id} to {replacement}): {text}"
+            ),
+        }
+    }
+}
+
+/// Lists all annotation lines that were skipped or remapped
+/// while loading annotations in lenient mode
+///
+/// # Examples
+///
+/// ```
+/// use hpo::builder::Builder;
+///
+/// let mut builder = Builder::new();
+/// builder.new_term("Foo", 1u32);
+/// let mut builder = builder
+///     .lenient_annotations(false)
+///     .terms_complete()
+///     .connect_all_terms();
+///
+/// let hpoa = "OMIM:5\tDisease 1\t\tHP:0000001\tPMID:1\tPCS
+/// OMIM:5\tDisease 1\t\tHP:0000002\tPMID:1\tPCS
+/// ";
+/// builder.add_disease_annotations(hpoa.as_bytes()).unwrap();
+///
+/// let report = builder.load_report();
+/// assert_eq!(report.len(), 1);
+/// for issue in report.skipped() {
+///     println!("{issue}");
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct LoadReport {
+    issues: Vec<LoadIssue>,
+}
+
+impl LoadReport {
+    /// Returns all skipped and remapped lines, in the order they were read
+    pub fn issues(&self) -> &[LoadIssue] {
+        &self.issues
+    }
+
+    /// Returns an iterator of all skipped lines
+    pub fn skipped(&self) -> impl Iterator<Item = &LoadIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| matches!(issue, LoadIssue::Skipped { .. }))
+    }
+
+    /// Returns an iterator of all remapped lines
+    pub fn remapped(&self) -> impl Iterator<Item = &LoadIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| matches!(issue, LoadIssue::Remapped { .. }))
+    }
+
+    /// Returns the number of skipped and remapped lines
+    pub fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    /// Returns `true` if all lines were loaded unchanged
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Records an issue and logs it as warning
+    pub(crate) fn add(&mut self, issue: LoadIssue) {
+        warn!("{issue}");
+        self.issues.push(issue);
+    }
+}
 
 /// Module to parse HPO - `Gene` associations
 ///
-/// It contains functions to parse `genes_to_phenotype.txt` and
-/// `phenotype_to_genes.txt` input files
+/// It contains functions to parse `genes_to_phenotype.txt`,
+/// `phenotype_to_genes.txt` and `genes_to_disease.txt` input files
 pub(crate) mod gene_to_hpo {
 
-    use crate::annotations::GeneId;
+    use crate::annotations::{GeneId, OmimDiseaseId, OrphaDiseaseId};
     use crate::ontology::builder::ConnectedTerms;
     use crate::ontology::Builder;
-    use crate::parser::Path;
     use crate::HpoError;
     use crate::HpoResult;
-    use std::fs::File;
     use std::io::BufRead;
-    use std::io::BufReader;
 
     use crate::HpoTermId;
 
@@ -32,16 +213,23 @@ pub(crate) mod gene_to_hpo {
         ncbi_id: GeneId,
         symbol: &'a str,
         hpo: HpoTermId,
+        disease: Option<&'a str>,
     }
 
     impl<'a> ParsedGene<'a> {
-        fn try_new(ncbi_id: &'a str, symbol: &'a str, hpo: &'a str) -> HpoResult<Self> {
+        fn try_new(
+            ncbi_id: &'a str,
+            symbol: &'a str,
+            hpo: &'a str,
+            disease: Option<&'a str>,
+        ) -> HpoResult<Self> {
             let hpo = HpoTermId::try_from(hpo)?;
             let ncbi_id = GeneId::try_from(ncbi_id)?;
             Ok(Self {
                 ncbi_id,
                 symbol,
                 hpo,
+                disease: disease.map(str::trim).filter(|disease| !disease.is_empty()),
             })
         }
     }
@@ -90,13 +278,16 @@ pub(crate) mod gene_to_hpo {
             return Err(HpoError::InvalidInput(line.to_string()));
         };
 
-        ParsedGene::try_new(ncbi_id, symbol, hpo)
+        // Column 6 is the disease ID
+        let disease = cols.nth(2);
+
+        ParsedGene::try_new(ncbi_id, symbol, hpo, disease)
     }
 
     /// Parses a single line of `phenotype_to_genes.txt`
     ///
     /// ```text
-    /// HP:0000002  Abnormality of body height  81848   SPRY4       orphadata   ORPHA:432
+    /// HP:0000002  Abnormality of body height  81848   SPRY4   ORPHA:432
     /// ```
     fn phenotype_to_gene_line(line: &str) -> HpoResult<ParsedGene<'_>> {
         let mut cols = line.split('\t');
@@ -121,10 +312,57 @@ pub(crate) mod gene_to_hpo {
             return Err(HpoError::InvalidInput(line.to_string()));
         };
 
-        ParsedGene::try_new(ncbi_id, symbol, hpo)
+        // Column 5 is the disease ID
+        let disease = cols.next();
+
+        ParsedGene::try_new(ncbi_id, symbol, hpo, disease)
     }
 
-    /// Parse `genes_to_phenotype.txt` file
+    /// Parses a single line of `genes_to_disease.txt`
+    ///
+    /// and returns the `GeneId` and the disease ID
+    ///
+    /// ```text
+    /// NCBIGene:10  NAT2    MENDELIAN   OMIM:243400 ftp://ftp.omim.org/mim2gene_medgen
+    /// ```
+    fn genes_to_disease_line(line: &str) -> HpoResult<(GeneId, &str)> {
+        let mut cols = line.trim_end().split('\t');
+
+        // Column 1 is the NCBI-ID of the gene, e.g. `NCBIGene:10`
+        let Some(ncbi_id) = cols.next() else {
+            return Err(HpoError::InvalidInput(line.to_string()));
+        };
+        let ncbi_id = ncbi_id.rsplit(':').next().unwrap_or(ncbi_id);
+
+        // Column 4 is the disease ID
+        let Some(disease) = cols.nth(2) else {
+            return Err(HpoError::InvalidInput(line.to_string()));
+        };
+
+        Ok((GeneId::try_from(ncbi_id)?, disease))
+    }
+
+    /// Associates the gene with the disease, e.g. `OMIM:243400`
+    ///
+    /// Diseases from other sources are ignored
+    fn link_disease(
+        builder: &mut Builder<ConnectedTerms>,
+        gene_id: GeneId,
+        disease: &str,
+    ) -> HpoResult<()> {
+        match disease.split_once(':') {
+            Some(("OMIM", id)) => {
+                builder.link_gene_omim_disease(gene_id, OmimDiseaseId::try_from(id)?);
+            }
+            Some(("ORPHA", id)) => {
+                builder.link_gene_orpha_disease(gene_id, OrphaDiseaseId::try_from(id)?);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Parses `genes_to_phenotype.txt` data from any reader
     ///
     /// ```text
     /// ncbi_gene_id    gene_symbol hpo_id  hpo_name    frequency   disease_id
@@ -132,14 +370,15 @@ pub(crate) mod gene_to_hpo {
     /// 10  NAT2    HP:0001939  Abnormality of metabolism/homeostasis   -       OMIM:243400
     /// 16  AARS1   HP:0002460  Distal muscle weakness                  15/15   OMIM:613287
     /// ```
-    pub fn parse_genes_to_phenotype<P: AsRef<Path>>(
-        file: P,
+    pub fn parse_genes_to_phenotype_reader<R: BufRead>(
+        reader: R,
+        source: &str,
         builder: &mut Builder<ConnectedTerms>,
     ) -> HpoResult<()> {
-        parse(file, builder, genes_to_phenotype_line)
+        parse(reader, source, builder, genes_to_phenotype_line)
     }
 
-    /// Parse `phenotype_to_genes.txt` file
+    /// Parses `phenotype_to_genes.txt` data from any reader
     ///
     /// ```text
     /// #Format: HPO-id<tab>HPO label<tab>entrez-gene-id<tab>entrez-gene-symbol<tab>Additional Info from G-D source<tab>G-D source<tab>disease-ID for link
@@ -147,32 +386,73 @@ pub(crate) mod gene_to_hpo {
     /// HP:0000002  Abnormality of body height  204219  CERS3       orphadata   ORPHA:79394
     /// HP:0000002  Abnormality of body height  51360   MBTPS2  -   mim2gene    OMIM:308205
     /// ```
-    pub fn parse_phenotype_to_genes<P: AsRef<Path>>(
-        file: P,
+    pub fn parse_phenotype_to_genes_reader<R: BufRead>(
+        reader: R,
+        source: &str,
         builder: &mut Builder<ConnectedTerms>,
     ) -> HpoResult<()> {
-        parse(file, builder, phenotype_to_gene_line)
+        parse(reader, source, builder, phenotype_to_gene_line)
     }
 
-    /// Parses a file to connect genes to HPO terms
-    fn parse<P: AsRef<Path>, F: Fn(&str) -> HpoResult<ParsedGene<'_>>>(
-        file: P,
+    /// Parses gene data to connect genes to HPO terms
+    fn parse<R: BufRead, F: Fn(&str) -> HpoResult<ParsedGene<'_>>>(
+        mut reader: R,
+        source: &str,
         builder: &mut Builder<ConnectedTerms>,
         parse_line: F,
     ) -> HpoResult<()> {
-        let filename = file.as_ref().display().to_string();
-        let file = File::open(file).map_err(|_| HpoError::CannotOpenFile(filename))?;
-        let mut reader = BufReader::new(file);
-
         remove_header(&mut reader)?;
 
-        for line in reader.lines() {
+        for (idx, line) in reader.lines().enumerate() {
             let line = line.map_err(|_| {
                 HpoError::InvalidInput("Invalid data in genes_to_phenotype.txt".to_string())
             })?;
 
             let gene = parse_line(&line)?;
-            builder.annotate_gene(gene.ncbi_id, gene.symbol, gene.hpo)?;
+            // the header is the first line
+            let Some(hpo) = builder.resolve_annotation_term(gene.hpo, source, idx + 2, &line)
+            else {
+                continue;
+            };
+            builder.annotate_gene(gene.ncbi_id, gene.symbol, hpo)?;
+            if let Some(disease) = gene.disease {
+                link_disease(builder, gene.ncbi_id, disease)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses `genes_to_disease.txt` data from any reader
+    ///
+    /// ```text
+    /// ncbi_gene_id    gene_symbol association_type    disease_id  source
+    /// NCBIGene:10 NAT2    MENDELIAN   OMIM:243400 ftp://ftp.omim.org/mim2gene_medgen
+    /// NCBIGene:16 AARS1   MENDELIAN   OMIM:613287 ftp://ftp.omim.org/mim2gene_medgen
+    /// ```
+    ///
+    /// Only genes that are already present in the builder are linked
+    /// to their diseases.
+    ///
+    /// `source` is the name of the data source, e.g. the filename, and is
+    /// used to locate invalid lines in the returned error.
+    pub fn parse_genes_to_disease_reader<R: BufRead>(
+        mut reader: R,
+        source: &str,
+        builder: &mut Builder<ConnectedTerms>,
+    ) -> HpoResult<()> {
+        remove_header(&mut reader)?;
+
+        for (idx, line) in reader.lines().enumerate() {
+            let line =
+                line.map_err(|_| HpoError::InvalidInput(format!("Invalid data in {source}")))?;
+            if line.is_empty() {
+                continue;
+            }
+            // the header is the first line
+            let invalid_line =
+                |_| HpoError::InvalidInput(format!("{source}, line {}: {line}", idx + 2));
+            let (gene_id, disease) = genes_to_disease_line(&line).map_err(invalid_line)?;
+            link_disease(builder, gene_id, disease).map_err(invalid_line)?;
         }
         Ok(())
     }
@@ -180,6 +460,8 @@ pub(crate) mod gene_to_hpo {
     #[cfg(test)]
     mod test {
         use super::*;
+        use std::io::BufReader;
+
         #[test]
         fn test_remove_header_ncbi_gene() {
             let x = "ncbi_gene_id\txyz\n10\tNAT2\n".as_bytes();
@@ -272,6 +554,64 @@ pub(crate) mod gene_to_hpo {
             let res = genes_to_phenotype_line(line);
             assert!(res.is_err());
         }
+
+        #[test]
+        fn test_parse_disease() {
+            let line = "10\tNAT2\tHP:0000007\tAutosomal recessive inheritance\t-\tOMIM:243400\n";
+            let res = genes_to_phenotype_line(line).expect("This line should parse correctly");
+            assert_eq!(res.disease, Some("OMIM:243400"));
+
+            let line = "10\tNAT2\tHP:0000007\tfoobar";
+            let res = genes_to_phenotype_line(line).expect("This line should parse correctly");
+            assert_eq!(res.disease, None);
+        }
+    }
+
+    #[cfg(test)]
+    mod test_genes_to_disease {
+        use super::*;
+        use crate::annotations::AnnotationId;
+
+        #[test]
+        fn test_parse_correct_line() {
+            let line =
+                "NCBIGene:10\tNAT2\tMENDELIAN\tOMIM:243400\tftp://ftp.omim.org/mim2gene_medgen\n";
+            let (gene_id, disease) =
+                genes_to_disease_line(line).expect("This line should parse correctly");
+            assert_eq!(gene_id.as_u32(), 10);
+            assert_eq!(disease, "OMIM:243400");
+        }
+
+        #[test]
+        fn test_parse_missing_disease() {
+            let line = "NCBIGene:10\tNAT2\tMENDELIAN\n";
+            assert!(genes_to_disease_line(line).is_err());
+        }
+
+        #[test]
+        fn test_parse_invalid_gene() {
+            let line = "NCBIGene:NAT2\tNAT2\tMENDELIAN\tOMIM:243400\tfoobar\n";
+            assert!(genes_to_disease_line(line).is_err());
+        }
+
+        #[test]
+        fn test_invalid_line_reports_source() {
+            let data = "ncbi_gene_id\tgene_symbol\tassociation_type\tdisease_id\tsource
+NCBIGene:10\tNAT2\tMENDELIAN\tOMIM:243400\tfoobar
+NCBIGene:NAT2\tNAT2\tMENDELIAN\tOMIM:243400\tfoobar
+";
+            let mut builder = Builder::new().terms_complete().connect_all_terms();
+            let err = parse_genes_to_disease_reader(
+                data.as_bytes(),
+                "genes_to_disease.txt",
+                &mut builder,
+            )
+            .unwrap_err();
+            assert!(matches!(
+                err,
+                HpoError::InvalidInput(msg) if msg.starts_with("genes_to_disease.txt, line 3:")
+            ));
+        }
     }
 
     #[cfg(test)]
@@ -297,6 +637,17 @@ pub(crate) mod gene_to_hpo {
             assert_eq!(res.hpo.as_u32(), 7u32);
         }
 
+        #[test]
+        fn test_parse_disease() {
+            let line = "HP:0000002\tAbnormality of body height\t81848\tSPRY4\tORPHA:432\n";
+            let res = phenotype_to_gene_line(line).expect("This line should parse correctly");
+            assert_eq!(res.disease, Some("ORPHA:432"));
+
+            let line = "HP:0000002\tAbnormality of body height\t81848\tSPRY4";
+            let res = phenotype_to_gene_line(line).expect("This line should parse correctly");
+            assert_eq!(res.disease, None);
+        }
+
         #[test]
         fn test_parse_missing_id() {
             let line = "HP:0000007\tAbnormality of body height\tNAT2\tfoobar\n";
@@ -339,27 +690,32 @@ pub(crate) mod gene_to_hpo {
 /// ```
 ///
 pub(crate) mod disease_to_hpo {
+    use crate::annotations::DecipherDiseaseId;
     use crate::annotations::OmimDiseaseId;
     use crate::annotations::OrphaDiseaseId;
+    use crate::annotations::{Aspect, DiseaseAnnotation, EvidenceCode, Sex};
     use crate::ontology::builder::ConnectedTerms;
     use crate::ontology::Builder;
+    use crate::AnnotationMetadata;
     use crate::HpoError;
     use crate::HpoResult;
     use crate::HpoTermId;
-    use std::fs::File;
     use std::io::BufRead;
-    use std::io::BufReader;
-    use std::path::Path;
+    use tracing::warn;
 
     enum DiseaseKind<'a> {
         Omim(DiseaseComponents<'a>),
         Orpha(DiseaseComponents<'a>),
+        Decipher(DiseaseComponents<'a>),
     }
 
     struct DiseaseComponents<'a> {
         id: &'a str,
         name: &'a str,
         hpo_id: HpoTermId,
+        frequency: Option<f32>,
+        annotation: DiseaseAnnotation,
+        negated: bool,
     }
 
     impl DiseaseComponents<'_> {
@@ -370,6 +726,15 @@ pub(crate) mod disease_to_hpo {
         fn orpha_disease_id(&self) -> HpoResult<OrphaDiseaseId> {
             OrphaDiseaseId::try_from(self.id)
         }
+
+        fn decipher_disease_id(&self) -> HpoResult<DecipherDiseaseId> {
+            DecipherDiseaseId::try_from(self.id)
+        }
+
+        fn set_term_id(&mut self, term_id: HpoTermId) {
+            self.hpo_id = term_id;
+            self.annotation.set_term_id(term_id);
+        }
     }
 
     fn parse_line(line: &str) -> HpoResult<Option<DiseaseKind<'_>>> {
@@ -377,13 +742,15 @@ pub(crate) mod disease_to_hpo {
             Ok(parse_disease_components(line)?.map(DiseaseKind::Omim))
         } else if line.starts_with("ORPHA") {
             Ok(parse_disease_components(line)?.map(DiseaseKind::Orpha))
+        } else if line.starts_with("DECIPHER") {
+            Ok(parse_disease_components(line)?.map(DiseaseKind::Decipher))
         } else {
             Ok(None)
         }
     }
 
     fn parse_disease_components(line: &'_ str) -> HpoResult<Option<DiseaseComponents<'_>>> {
-        let mut cols = line.trim().splitn(5, '\t');
+        let mut cols = line.trim().split('\t');
 
         let Some(id_col) = cols.next() else {
             return Err(HpoError::InvalidInput(line.to_string()));
@@ -397,9 +764,7 @@ pub(crate) mod disease_to_hpo {
             return Err(HpoError::InvalidInput(line.to_string()));
         };
 
-        if let Some("NOT") = cols.next() {
-            return Ok(None);
-        }
+        let negated = cols.next() == Some("NOT");
 
         let hpo_id = if let Some(id) = cols.next() {
             HpoTermId::try_from(id)?
@@ -407,44 +772,231 @@ pub(crate) mod disease_to_hpo {
             return Err(HpoError::InvalidInput(line.to_string()));
         };
 
+        // The remaining columns are only present in the full
+        // 12-column `phenotype.hpoa` layout:
+        // reference, evidence, onset, frequency, sex, modifier, aspect, biocuration
+        let details: Vec<&str> = cols.collect();
+        let mut annotation = DiseaseAnnotation::new(hpo_id);
+        if let Some(evidence) = details.get(1).filter(|evidence| !evidence.is_empty()) {
+            // New evidence codes can be added in any HPO release, so unknown
+            // codes do not prevent the annotation from being added
+            if let Ok(evidence) = EvidenceCode::try_from(*evidence) {
+                annotation = annotation.with_evidence(evidence);
+            } else {
+                warn!("Ignoring unknown evidence code: {evidence}");
+            }
+        }
+        let mut frequency = None;
+        if details.len() >= 8 {
+            frequency = parse_frequency(details[3]);
+            annotation = annotation_details(annotation, &details)?;
+        }
+
         Ok(Some(DiseaseComponents {
             id: disease_id,
             name: disease_name,
             hpo_id,
+            frequency,
+            annotation,
+            negated,
         }))
     }
 
-    /// Quick and dirty parser for development and debugging
+    /// Adds onset, sex, modifiers, aspect and biocuration to the annotation
+    ///
+    /// `details` are all columns after the HPO term ID
+    fn annotation_details(
+        mut annotation: DiseaseAnnotation,
+        details: &[&str],
+    ) -> HpoResult<DiseaseAnnotation> {
+        if !details[2].is_empty() {
+            annotation = annotation.with_onset(HpoTermId::try_from(details[2])?);
+        }
+        if !details[4].is_empty() {
+            annotation = annotation.with_sex(Sex::try_from(details[4])?);
+        }
+        for modifier in details[5].split(';').filter(|value| !value.is_empty()) {
+            annotation = annotation.with_modifier(HpoTermId::try_from(modifier)?);
+        }
+        if !details[6].is_empty() {
+            annotation = annotation.with_aspect(Aspect::try_from(details[6])?);
+        }
+        for biocuration in details[7].split(';').filter(|value| !value.is_empty()) {
+            annotation = annotation.with_biocuration(biocuration);
+        }
+        Ok(annotation)
+    }
+
+    /// Parses the frequency of a disease annotation and normalises it to `0.0..=1.0`
+    ///
+    /// The frequency can be specified as
+    ///
+    /// - HPO frequency term, e.g. `HP:0040281` (Very frequent). The mean
+    ///   of the frequency range of the term is used, e.g. `0.895` for `80% - 99%`
+    /// - Fraction of patients, e.g. `3/7`
+    /// - Percentage of patients, e.g. `45%`
+    ///
+    /// Returns `None` if the frequency is not specified. Unknown or invalid
+    /// frequencies are logged and ignored, they do not prevent the annotation
+    /// from being added.
+    fn parse_frequency(value: &str) -> Option<f32> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        let frequency = if let Some(percent) = value.strip_suffix('%') {
+            percent
+                .trim()
+                .parse::<f32>()
+                .ok()
+                .map(|percent| percent / 100.0)
+        } else if let Some((n, total)) = value.split_once('/') {
+            match (n.parse::<f32>(), total.parse::<f32>()) {
+                (Ok(n), Ok(total)) if total > 0.0 => Some(n / total),
+                _ => None,
+            }
+        } else {
+            match value {
+                // Obligate: 100%
+                "HP:0040280" => Some(1.0),
+                // Very frequent: 80% - 99%
+                "HP:0040281" => Some(0.895),
+                // Frequent: 30% - 79%
+                "HP:0040282" => Some(0.545),
+                // Occasional: 5% - 29%
+                "HP:0040283" => Some(0.17),
+                // Very rare: 1% - 4%
+                "HP:0040284" => Some(0.025),
+                // Excluded: 0%
+                "HP:0040285" => Some(0.0),
+                _ => None,
+            }
+        };
+        match frequency {
+            Some(frequency) if (0.0..=1.0).contains(&frequency) => Some(frequency),
+            _ => {
+                warn!("Ignoring invalid frequency: {value}");
+                None
+            }
+        }
+    }
+
+    /// Parses `phenotype.hpoa` data from any reader
+    ///
+    /// Annotations with an evidence code that is excluded in the
+    /// builder are skipped.
     ///
     /// # Errors
     ///
-    /// - [`HpoError::CannotOpenFile`]: Source file not present or can't be opened
+    /// - [`HpoError::InvalidInput`]: The data can't be read or a line contains invalid data
     /// - [`HpoError::ParseIntError`]: A line contains an invalid `omim_disease_id`
-    /// - [`HpoError::DoesNotExist`]: A line contains a non-existing [`HpoTermId`]
-    pub fn parse<P: AsRef<Path>>(file: P, builder: &mut Builder<ConnectedTerms>) -> HpoResult<()> {
-        let filename = file.as_ref().display().to_string();
-        let file = File::open(file).map_err(|_| HpoError::CannotOpenFile(filename))?;
-        let reader = BufReader::new(file);
-        for line in reader.lines() {
-            let line = line.unwrap();
-            match parse_line(&line)? {
-                Some(DiseaseKind::Omim(omim)) => {
-                    builder.annotate_omim_disease(
-                        omim.omim_disease_id()?,
-                        omim.name,
-                        omim.hpo_id,
-                    )?;
+    /// - [`HpoError::DoesNotExist`]: A line contains a non-existing [`HpoTermId`],
+    ///   unless lenient annotations are enabled, see [`Builder::lenient_annotations`]
+    pub fn parse_reader<R: BufRead>(
+        reader: R,
+        source: &str,
+        builder: &mut Builder<ConnectedTerms>,
+    ) -> HpoResult<()> {
+        // Negated annotations are only added to diseases that have
+        // positive annotations, after all lines are parsed
+        let mut excluded_omim: Vec<(OmimDiseaseId, HpoTermId)> = Vec::new();
+        let mut excluded_orpha: Vec<(OrphaDiseaseId, HpoTermId)> = Vec::new();
+        let mut excluded_decipher: Vec<(DecipherDiseaseId, HpoTermId)> = Vec::new();
+
+        let mut metadata = AnnotationMetadata::new(crate::DISEASE_FILENAME);
+        let mut in_header = true;
+
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line.map_err(|err| HpoError::InvalidInput(err.to_string()))?;
+            if in_header {
+                // The header contains metadata as `#key: value`
+                if let Some((key, value)) = line.strip_prefix('#').and_then(|l| l.split_once(':')) {
+                    metadata.add_property(key.trim(), value.trim());
+                    continue;
+                }
+                in_header = false;
+            }
+            let Some(mut kind) = parse_line(&line)? else {
+                continue;
+            };
+            let components = match &mut kind {
+                DiseaseKind::Omim(components)
+                | DiseaseKind::Orpha(components)
+                | DiseaseKind::Decipher(components) => components,
+            };
+            let Some(term_id) =
+                builder.resolve_annotation_term(components.hpo_id, source, idx + 1, &line)
+            else {
+                continue;
+            };
+            components.set_term_id(term_id);
+
+            match kind {
+                DiseaseKind::Omim(omim) if omim.negated => {
+                    if builder.is_excluded_evidence(omim.annotation.evidence()) {
+                        continue;
+                    }
+                    excluded_omim.push((omim.omim_disease_id()?, omim.hpo_id));
                 }
-                Some(DiseaseKind::Orpha(orpha)) => {
-                    builder.annotate_orpha_disease(
-                        orpha.orpha_disease_id()?,
+                DiseaseKind::Orpha(orpha) if orpha.negated => {
+                    if builder.is_excluded_evidence(orpha.annotation.evidence()) {
+                        continue;
+                    }
+                    excluded_orpha.push((orpha.orpha_disease_id()?, orpha.hpo_id));
+                }
+                DiseaseKind::Decipher(decipher) if decipher.negated => {
+                    if builder.is_excluded_evidence(decipher.annotation.evidence()) {
+                        continue;
+                    }
+                    excluded_decipher.push((decipher.decipher_disease_id()?, decipher.hpo_id));
+                }
+                DiseaseKind::Omim(omim) => {
+                    let omim_id = omim.omim_disease_id()?;
+                    let added =
+                        builder.add_omim_disease_annotation(omim_id, omim.name, omim.annotation)?;
+                    if let (true, Some(frequency)) = (added, omim.frequency) {
+                        builder.set_omim_disease_frequency(omim_id, omim.hpo_id, frequency)?;
+                    }
+                }
+                DiseaseKind::Orpha(orpha) => {
+                    let orpha_id = orpha.orpha_disease_id()?;
+                    let added = builder.add_orpha_disease_annotation(
+                        orpha_id,
                         orpha.name,
-                        orpha.hpo_id,
+                        orpha.annotation,
                     )?;
+                    if let (true, Some(frequency)) = (added, orpha.frequency) {
+                        builder.set_orpha_disease_frequency(orpha_id, orpha.hpo_id, frequency)?;
+                    }
+                }
+                DiseaseKind::Decipher(decipher) => {
+                    let decipher_id = decipher.decipher_disease_id()?;
+                    let added = builder.add_decipher_disease_annotation(
+                        decipher_id,
+                        decipher.name,
+                        decipher.annotation,
+                    )?;
+                    if let (true, Some(frequency)) = (added, decipher.frequency) {
+                        builder.set_decipher_disease_frequency(
+                            decipher_id,
+                            decipher.hpo_id,
+                            frequency,
+                        )?;
+                    }
                 }
-                _ => {}
             }
         }
+
+        for (omim_id, term_id) in excluded_omim {
+            builder.exclude_omim_disease_term(omim_id, term_id)?;
+        }
+        for (orpha_id, term_id) in excluded_orpha {
+            builder.exclude_orpha_disease_term(orpha_id, term_id)?;
+        }
+        for (decipher_id, term_id) in excluded_decipher {
+            builder.exclude_decipher_disease_term(decipher_id, term_id)?;
+        }
+        builder.metadata_mut().add_annotation(metadata);
         Ok(())
     }
 
@@ -463,9 +1015,13 @@ pub(crate) mod disease_to_hpo {
         #[test]
         fn test_skip_not() {
             let s = "OMIM:600171\tGonadal agenesis\tNOT\tHP:0000055\tOMIM:600171\tTAS\tP\tHPO:skoehler[2014-11-27]";
-            assert!(parse_line(s)
-                .expect("This line has the correct format")
-                .is_none());
+            let Some(DiseaseKind::Omim(omim)) =
+                parse_line(s).expect("This line has the correct format")
+            else {
+                panic!("Omim line should be parsed as Omim correctly");
+            };
+            assert!(omim.negated);
+            assert_eq!(omim.hpo_id, "HP:0000055");
         }
 
         #[test]
@@ -486,9 +1042,29 @@ pub(crate) mod disease_to_hpo {
         #[test]
         fn test_skip_orpha_not() {
             let s = "ORPHA:600171\tGonadal agenesis\tNOT\tHP:0000055\tOMIM:600171\tTAS\tP\tHPO:skoehler[2014-11-27]";
-            assert!(parse_line(s)
-                .expect("This line has the correct format")
-                .is_none());
+            let Some(DiseaseKind::Orpha(orpha)) =
+                parse_line(s).expect("This line has the correct format")
+            else {
+                panic!("Orpha line should be parsed as Orpha correctly");
+            };
+            assert!(orpha.negated);
+        }
+
+        #[test]
+        fn test_correct_decipher() {
+            let s = "DECIPHER:18\tWolf-Hirschhorn Syndrome\t\tHP:0000252\tDECIPHER:18\tIEA\t\t\t\t\tP\tHPO:skoehler[2013-05-29]";
+            let Some(DiseaseKind::Decipher(decipher)) =
+                parse_line(s).expect("This line has the correct format")
+            else {
+                panic!("Decipher line should be parsed as Decipher correctly");
+            };
+            assert_eq!(decipher.name, "Wolf-Hirschhorn Syndrome");
+            assert_eq!(decipher.id, "18");
+            assert_eq!(decipher.hpo_id, "HP:0000252");
+            assert_eq!(
+                decipher.decipher_disease_id().expect("the ID is numeric"),
+                DecipherDiseaseId::from(18u32)
+            );
         }
 
         #[test]
@@ -506,6 +1082,79 @@ pub(crate) mod disease_to_hpo {
             }
         }
 
+        #[test]
+        fn test_frequency() {
+            let s = "OMIM:600171\tGonadal agenesis\t\tHP:0000055\tOMIM:600171\tTAS\t\t3/4\t\t\tP\tHPO:skoehler[2014-11-27]";
+            let Some(DiseaseKind::Omim(omim)) =
+                parse_line(s).expect("This line has the correct format")
+            else {
+                panic!("Omim line should be parsed as Omim correctly");
+            };
+            assert_eq!(omim.frequency, Some(0.75));
+
+            let s = "OMIM:600171\tGonadal agenesis\t\tHP:0000055\tOMIM:600171\tTAS\t\t\t\t\tP\tHPO:skoehler[2014-11-27]";
+            let Some(DiseaseKind::Omim(omim)) =
+                parse_line(s).expect("This line has the correct format")
+            else {
+                panic!("Omim line should be parsed as Omim correctly");
+            };
+            assert_eq!(omim.frequency, None);
+        }
+
+        #[test]
+        fn test_annotation_details() {
+            let s = "OMIM:619340\tDevelopmental and epileptic encephalopathy 96\t\tHP:0011097\tPMID:31675180\tPCS\tHP:0003577\t1/2\tFEMALE\tHP:0012828;HP:0012825\tP\tHPO:probinson[2021-06-21];HPO:skoehler[2022-01-01]";
+            let Some(DiseaseKind::Omim(omim)) =
+                parse_line(s).expect("This line has the correct format")
+            else {
+                panic!("Omim line should be parsed as Omim correctly");
+            };
+            let annotation = omim.annotation;
+            assert_eq!(annotation.term_id(), HpoTermId::from(11097u32));
+            assert_eq!(annotation.evidence(), Some(EvidenceCode::Pcs));
+            assert_eq!(annotation.onset(), Some(HpoTermId::from(3577u32)));
+            assert_eq!(annotation.sex(), Some(Sex::Female));
+            assert_eq!(annotation.modifiers().len(), 2);
+            assert_eq!(annotation.aspect(), Some(Aspect::Phenotype));
+            assert_eq!(annotation.biocuration().len(), 2);
+
+            let s = "OMIM:619340\tDevelopmental and epileptic encephalopathy 96\t\tHP:0011097\tPMID:31675180\tFOO\t\t\t\t\tP\tHPO:probinson[2021-06-21]";
+            let Some(DiseaseKind::Omim(omim)) =
+                parse_line(s).expect("An unknown evidence code does not fail the line")
+            else {
+                panic!("Omim line should be parsed as Omim correctly");
+            };
+            assert_eq!(omim.annotation.evidence(), None);
+            assert_eq!(omim.annotation.aspect(), Some(Aspect::Phenotype));
+        }
+
+        #[test]
+        fn test_parse_frequency() {
+            assert_eq!(parse_frequency(""), None);
+            assert_eq!(parse_frequency("HP:0040280"), Some(1.0));
+            assert_eq!(parse_frequency("HP:0040283"), Some(0.17));
+            assert_eq!(parse_frequency("1/4"), Some(0.25));
+            assert_eq!(parse_frequency("45%"), Some(0.45));
+            assert_eq!(parse_frequency("0/3"), Some(0.0));
+            assert_eq!(parse_frequency("5/3"), None);
+            assert_eq!(parse_frequency("1/0"), None);
+            assert_eq!(parse_frequency("120%"), None);
+            assert_eq!(parse_frequency("HP:0000118"), None);
+            assert_eq!(parse_frequency("often"), None);
+        }
+
+        #[test]
+        fn test_invalid_frequency_is_ignored() {
+            let s = "OMIM:619340\tDevelopmental and epileptic encephalopathy 96\t\tHP:0011097\tPMID:31675180\tPCS\t\tfrequently\t\t\tP\tHPO:probinson[2021-06-21]";
+            let Some(DiseaseKind::Omim(omim)) =
+                parse_line(s).expect("An invalid frequency does not fail the line")
+            else {
+                panic!("Omim line should be parsed as Omim correctly");
+            };
+            assert_eq!(omim.frequency, None);
+            assert_eq!(omim.hpo_id, HpoTermId::from(11097u32));
+        }
+
         #[test]
         fn test_invalid_omim_id() {
             let s = "OMIM_600171\tGonadal agenesis\t\tHP:0000055\tOMIM:600171\tTAS\tP\tHPO:skoehler[2014-11-27]";
@@ -524,34 +1173,107 @@ pub(crate) mod disease_to_hpo {
             assert!(parse_line(s).is_err());
         }
     }
-}
 
-pub(crate) fn load_from_jax_files_with_transivitve_genes<P: AsRef<Path>>(
-    obo_file: P,
-    gene_file: P,
-    disease_file: P,
-) -> HpoResult<Ontology> {
-    let builder = Builder::new();
-    let builder = hp_obo::read_obo_file(obo_file, builder)?;
-    let mut builder = builder.connect_all_terms();
-    gene_to_hpo::parse_phenotype_to_genes(gene_file, &mut builder)?;
-    disease_to_hpo::parse(disease_file, &mut builder)?;
-    builder
-        .calculate_information_content()?
-        .build_with_defaults()
+    #[cfg(test)]
+    mod test_lenient_parsing {
+        use super::*;
+        use crate::parser::LoadIssue;
+
+        const DATA: &str = "OMIM:5\tDisease 1\t\tHP:0000001\tPMID:1\tPCS
+OMIM:5\tDisease 1\t\tHP:0000002\tPMID:1\tPCS
+OMIM:5\tDisease 1\tNOT\tHP:0000003\tPMID:1\tPCS
+";
+
+        #[test]
+        fn test_strict_unknown_term() {
+            let mut builder = Builder::new();
+            builder.new_term("Foo", 1u32);
+            let mut builder = builder.terms_complete().connect_all_terms();
+            assert!(matches!(
+                parse_reader(DATA.as_bytes(), "test", &mut builder),
+                Err(HpoError::DoesNotExist)
+            ));
+        }
+
+        #[test]
+        fn test_lenient_unknown_term() {
+            let mut builder = Builder::new().lenient_annotations(false);
+            builder.new_term("Foo", 1u32);
+            let mut builder = builder.terms_complete().connect_all_terms();
+            parse_reader(DATA.as_bytes(), "test", &mut builder).expect("unknown terms are skipped");
+
+            let report = builder.load_report();
+            assert_eq!(report.len(), 2);
+            assert_eq!(report.remapped().count(), 0);
+            assert_eq!(
+                report.issues()[0],
+                LoadIssue::Skipped {
+                    file: "test".to_string(),
+                    line: 2,
+                    text: "OMIM:5\tDisease 1\t\tHP:0000002\tPMID:1\tPCS".to_string(),
+                    term_id: HpoTermId::from(2u32),
+                }
+            );
+            assert_eq!(
+                report.issues()[1].to_string(),
+                "skipped test, line 3 (HP:0000003 does not exist): OMIM:5\tDisease 1\tNOT\tHP:0000003\tPMID:1\tPCS"
+            );
+        }
+    }
 }
 
-pub(crate) fn load_from_jax_files<P: AsRef<Path>>(
-    obo_file: P,
-    gene_file: P,
-    disease_file: P,
-) -> HpoResult<Ontology> {
-    let builder = Builder::new();
-    let builder = hp_obo::read_obo_file(obo_file, builder)?;
-    let mut builder = builder.connect_all_terms();
-    gene_to_hpo::parse_genes_to_phenotype(gene_file, &mut builder)?;
-    disease_to_hpo::parse(disease_file, &mut builder)?;
-    builder
-        .calculate_information_content()?
-        .build_with_defaults()
+#[cfg(test)]
+mod test_open_file {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn plain_file() {
+        let mut content = String::new();
+        open_file("tests/small.obo")
+            .expect("the file exists")
+            .read_to_string(&mut content)
+            .expect("the file is valid UTF-8");
+        assert_eq!(content, std::fs::read_to_string("tests/small.obo").unwrap());
+    }
+
+    #[test]
+    fn missing_file() {
+        assert!(matches!(
+            open_file("tests/missing.obo"),
+            Err(HpoError::CannotOpenFile(_))
+        ));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn gzip_file() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let original = std::fs::read("tests/small.obo").unwrap();
+        let filename = std::env::temp_dir().join("hpo_test_open_gzip_file.obo");
+        let mut encoder = GzEncoder::new(File::create(&filename).unwrap(), Compression::default());
+        encoder.write_all(&original).unwrap();
+        encoder.finish().unwrap();
+
+        let mut content = Vec::new();
+        open_file(&filename)
+            .expect("the file exists")
+            .read_to_end(&mut content)
+            .expect("the file can be decompressed");
+        std::fs::remove_file(&filename).unwrap();
+        assert_eq!(content, original);
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn gzip_file_without_feature() {
+        let filename = std::env::temp_dir().join("hpo_test_open_gzip_file_no_feature.obo");
+        std::fs::write(&filename, [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+        let res = open_file(&filename);
+        std::fs::remove_file(&filename).unwrap();
+        assert!(matches!(res, Err(HpoError::InvalidInput(_))));
+    }
 }
@@ -6,15 +6,17 @@
 //! to update their versions to the newest one.
 pub(crate) mod ontology;
 pub(crate) mod term;
+use std::ops::Range;
 use std::{cmp::Ordering, fmt::Display};
 
-use crate::{term::internal::HpoTermInternal, HpoError};
+use crate::{term::internal::HpoTermInternal, u32_from_bytes, HpoError, HpoResult};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub(crate) enum BinaryVersion {
     V1,
     V2,
     V3,
+    V4,
 }
 
 impl TryFrom<u8> for BinaryVersion {
@@ -24,6 +26,7 @@ impl TryFrom<u8> for BinaryVersion {
             1u8 => Ok(BinaryVersion::V1),
             2u8 => Ok(BinaryVersion::V2),
             3u8 => Ok(BinaryVersion::V3),
+            4u8 => Ok(BinaryVersion::V4),
             _ => Err(HpoError::NotImplemented),
         }
     }
@@ -38,6 +41,7 @@ impl Display for BinaryVersion {
                 BinaryVersion::V1 => "1",
                 BinaryVersion::V2 => "2",
                 BinaryVersion::V3 => "3",
+                BinaryVersion::V4 => "4",
             }
         )
     }
@@ -49,6 +53,7 @@ impl From<&BinaryVersion> for u8 {
             BinaryVersion::V1 => 1,
             BinaryVersion::V2 => 2,
             BinaryVersion::V3 => 3,
+            BinaryVersion::V4 => 4,
         }
     }
 }
@@ -94,11 +99,6 @@ impl<'a> Bytes<'a> {
     pub fn version(&self) -> BinaryVersion {
         self.version
     }
-
-    pub fn u32_prefix(&self) -> u32 {
-        assert!(self.len() > 4, "4 bytes are required to extract u32");
-        u32::from_be_bytes([self[0], self[1], self[2], self[3]])
-    }
 }
 
 impl<Idx> std::ops::Index<Idx> for Bytes<'_>
@@ -112,7 +112,37 @@ where
     }
 }
 
+/// Returns the `range` of `bytes`
+///
+/// # Errors
+///
+/// [`HpoError::BinarySectionError`]: `bytes` is too short for `range`
+pub(crate) fn checked_slice<'a>(
+    bytes: &'a [u8],
+    range: Range<usize>,
+    section: &str,
+) -> HpoResult<&'a [u8]> {
+    bytes
+        .get(range.clone())
+        .ok_or_else(|| HpoError::BinarySectionError {
+            section: section.to_string(),
+            expected: range.end,
+            actual: bytes.len(),
+        })
+}
+
+/// Returns the big-endian `u32` at `offset` of `bytes`
+///
+/// # Errors
+///
+/// [`HpoError::BinarySectionError`]: `bytes` is too short
+pub(crate) fn checked_u32(bytes: &[u8], offset: usize, section: &str) -> HpoResult<u32> {
+    checked_slice(bytes, offset..offset + 4, section).map(u32_from_bytes)
+}
+
 /// Builder to crate multiple [`HpoTermInternal`] from raw bytes
+///
+/// The builder returns an error for incomplete data and stops afterwards
 pub(crate) struct BinaryTermBuilder<'a>(Bytes<'a>, usize);
 
 impl<'a> BinaryTermBuilder<'a> {
@@ -123,7 +153,7 @@ impl<'a> BinaryTermBuilder<'a> {
 }
 
 impl Iterator for BinaryTermBuilder<'_> {
-    type Item = HpoTermInternal;
+    type Item = HpoResult<HpoTermInternal>;
     fn next(&mut self) -> Option<Self::Item> {
         let bytes = self.0.subset(self.1..);
 
@@ -131,14 +161,26 @@ impl Iterator for BinaryTermBuilder<'_> {
             return None;
         }
 
-        let term_len = bytes.u32_prefix() as usize;
-
-        assert!(
-            (bytes.len() >= term_len),
-            "Invalid bytes left over in BinaryTermBuilder"
-        );
+        // Errors report the offsets within the whole section
+        let term_len = match checked_u32(self.0.data, self.1, "terms") {
+            Ok(len) => len as usize,
+            Err(err) => {
+                self.1 = self.0.len();
+                return Some(Err(err));
+            }
+        };
+
+        if term_len < 4 || bytes.len() < term_len {
+            let err = HpoError::BinarySectionError {
+                section: "terms".to_string(),
+                expected: self.1 + term_len.max(4),
+                actual: self.0.len(),
+            };
+            self.1 = self.0.len();
+            return Some(Err(err));
+        }
 
         self.1 += term_len;
-        Some(HpoTermInternal::try_from(bytes).expect("Invalid byte input"))
+        Some(HpoTermInternal::try_from(bytes))
     }
 }
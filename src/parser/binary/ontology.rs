@@ -1,22 +1,25 @@
 //! Parsing the ontology
-use crate::parser::binary::Bytes;
-use crate::{HpoError, HpoResult};
+use std::ops::Range;
+
+use tracing::debug;
+
+use crate::parser::binary::{checked_slice, checked_u32, Bytes};
+use crate::{u32_from_bytes, HpoError, HpoResult};
 
 /// Identifies the encoding version of the provided binary data
 ///
 /// # Errors
 ///
 /// This method can fail for the following reasons:
-/// - Incorrect byte encoding: [`HpoError::ParseBinaryError`]
+/// - Incomplete header: [`HpoError::BinarySectionError`]
 /// - Invalid version: [`HpoError::NotImplemented`]
 ///
 pub(crate) fn version(bytes: &'_ [u8]) -> HpoResult<Bytes<'_>> {
-    if bytes.len() < 5 {
-        return Err(HpoError::ParseBinaryError);
-    }
+    checked_slice(bytes, 0..5, "header")?;
 
     if bytes[0..3] == [0x48, 0x50, 0x4f] {
         match bytes[3] {
+            4u8 => Ok(Bytes::new(&bytes[4..], super::BinaryVersion::V4)),
             3u8 => Ok(Bytes::new(&bytes[4..], super::BinaryVersion::V3)),
             2u8 => Ok(Bytes::new(&bytes[4..], super::BinaryVersion::V2)),
             _ => Err(HpoError::NotImplemented),
@@ -25,3 +28,292 @@ pub(crate) fn version(bytes: &'_ [u8]) -> HpoResult<Bytes<'_>> {
         Ok(Bytes::new(bytes, super::BinaryVersion::V1))
     }
 }
+
+/// Returns the data of a length-prefixed section of binary versions 1 to 3
+///
+/// Every section starts with its length as big-endian `u32`. `start` is
+/// moved to the start of the next section.
+///
+/// # Errors
+///
+/// [`HpoError::BinarySectionError`]: The section is incomplete
+pub(crate) fn length_prefixed_section<'a>(
+    bytes: &'a [u8],
+    start: &mut usize,
+    section: Section,
+) -> HpoResult<&'a [u8]> {
+    let len = checked_u32(bytes, *start, section.name())? as usize;
+    let data = checked_slice(bytes, *start + 4..*start + 4 + len, section.name())?;
+    *start += 4 + len;
+    Ok(data)
+}
+
+/// The sections of a binary ontology, starting with version 4
+///
+/// Every section is identified in the section directory by its
+/// type id. Type ids must never be re-used or changed, new sections
+/// must use a new type id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Section {
+    Terms,
+    Parents,
+    Genes,
+    OmimDiseases,
+    OrphaDiseases,
+    DecipherDiseases,
+    Metadata,
+    TermIndex,
+    Ancestors,
+    InformationContent,
+}
+
+impl Section {
+    /// All known sections, in the order they are written
+    pub(crate) const ALL: [Section; 10] = [
+        Section::Terms,
+        Section::Parents,
+        Section::Genes,
+        Section::OmimDiseases,
+        Section::OrphaDiseases,
+        Section::DecipherDiseases,
+        Section::Metadata,
+        Section::TermIndex,
+        Section::Ancestors,
+        Section::InformationContent,
+    ];
+
+    /// The type id of the section in the section directory
+    pub(crate) fn id(self) -> u32 {
+        match self {
+            Section::Terms => 1,
+            Section::Parents => 2,
+            Section::Genes => 3,
+            Section::OmimDiseases => 4,
+            Section::OrphaDiseases => 5,
+            Section::DecipherDiseases => 6,
+            Section::Metadata => 7,
+            Section::TermIndex => 8,
+            Section::Ancestors => 9,
+            Section::InformationContent => 10,
+        }
+    }
+
+    fn from_id(id: u32) -> Option<Self> {
+        Section::ALL.into_iter().find(|section| section.id() == id)
+    }
+
+    /// The name of the section, used in error messages
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Section::Terms => "terms",
+            Section::Parents => "parents",
+            Section::Genes => "genes",
+            Section::OmimDiseases => "OMIM diseases",
+            Section::OrphaDiseases => "ORPHA diseases",
+            Section::DecipherDiseases => "DECIPHER diseases",
+            Section::Metadata => "metadata",
+            Section::TermIndex => "term index",
+            Section::Ancestors => "ancestors",
+            Section::InformationContent => "information content",
+        }
+    }
+}
+
+/// The number of bytes of a single entry in the [`Section::TermIndex`]
+///
+/// Every entry consists of four big-endian `u32`: The term id and the
+/// offsets of the term in the [`Section::Terms`], [`Section::Parents`]
+/// and [`Section::Ancestors`] sections. Entries are sorted by term id.
+pub(crate) const TERM_INDEX_ENTRY_LEN: usize = 16;
+
+/// The number of bytes of a single entry in the [`Section::InformationContent`]
+///
+/// Every entry consists of the term id as big-endian `u32`, followed by
+/// the information content of the term, see `InformationContent::as_bytes`.
+pub(crate) const INFORMATION_CONTENT_ENTRY_LEN: usize = 24;
+
+/// The number of bytes of a single entry in the section directory
+const DIRECTORY_ENTRY_LEN: usize = 12;
+
+/// Combines the `header` and all `sections` into a binary ontology
+///
+/// A checksum and the section directory are written right after the header:
+///
+/// | Byte offset | Number of bytes | Description |
+/// | --- | --- | --- |
+/// | 0 | 4 | CRC-32 checksum of all following data as big-endian `u32` |
+/// | 4 | 4 | The number of sections `n` as big-endian `u32` |
+/// | 8 | 12 * n | One entry per section |
+///
+/// Each entry consists of three big-endian `u32`: the type id of the section,
+/// its offset from the start of the binary data and its length.
+///
+/// The data of all sections follows the section directory.
+///
+/// # Panics
+///
+/// Panics when the binary data is larger than `u32::MAX`
+pub(crate) fn with_sections(mut header: Vec<u8>, sections: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    fn usize_to_u32(n: usize) -> u32 {
+        n.try_into().expect("unable to convert {n} to u32")
+    }
+
+    let mut data = Vec::new();
+    let mut offset = header.len() + 8 + DIRECTORY_ENTRY_LEN * sections.len();
+    data.extend_from_slice(&usize_to_u32(sections.len()).to_be_bytes());
+    for (id, section) in sections {
+        data.extend_from_slice(&id.to_be_bytes());
+        data.extend_from_slice(&usize_to_u32(offset).to_be_bytes());
+        data.extend_from_slice(&usize_to_u32(section.len()).to_be_bytes());
+        offset += section.len();
+    }
+    for (_, section) in sections {
+        data.extend_from_slice(section);
+    }
+
+    header.extend_from_slice(&crc32fast::hash(&data).to_be_bytes());
+    header.append(&mut data);
+    header
+}
+
+/// Compares the checksum at `start` with the checksum of all following data
+///
+/// # Errors
+///
+/// - The checksum is missing: [`HpoError::BinarySectionError`]
+/// - The checksum does not match: [`HpoError::BinaryChecksumError`]
+pub(crate) fn verify_checksum(bytes: &[u8], start: usize) -> HpoResult<()> {
+    let expected = checked_u32(bytes, start, "checksum")?;
+    let actual = crc32fast::hash(&bytes[start + 4..]);
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(HpoError::BinaryChecksumError { expected, actual })
+    }
+}
+
+/// The section directory of a binary ontology, starting with version 4
+///
+/// Sections with an unknown type id, e.g. written by a newer
+/// version of this crate, are skipped.
+#[derive(Debug)]
+pub(crate) struct SectionDirectory<'a> {
+    bytes: &'a [u8],
+    sections: Vec<(Section, Range<usize>)>,
+}
+
+impl<'a> SectionDirectory<'a> {
+    /// Parses the section directory that starts at `start` of the binary data
+    ///
+    /// The checksum is not verified, see [`verify_checksum`]
+    ///
+    /// # Errors
+    ///
+    /// [`HpoError::BinarySectionError`]: The directory is incomplete or a section
+    /// is outside of the binary data
+    pub(crate) fn from_bytes(bytes: &'a [u8], start: usize) -> HpoResult<Self> {
+        let count = checked_u32(bytes, start + 4, "section directory")? as usize;
+        let entries_start = start + 8;
+        let entries_end = entries_start + count * DIRECTORY_ENTRY_LEN;
+        let entries = checked_slice(bytes, entries_start..entries_end, "section directory")?;
+
+        let mut sections = Vec::with_capacity(count);
+        for entry in entries.chunks_exact(DIRECTORY_ENTRY_LEN) {
+            let id = u32_from_bytes(&entry[0..4]);
+            let offset = u32_from_bytes(&entry[4..8]) as usize;
+            let len = u32_from_bytes(&entry[8..12]) as usize;
+            let section = Section::from_id(id);
+            let name =
+                section.map_or_else(|| format!("unknown section {id}"), |s| s.name().to_string());
+
+            if offset < entries_end {
+                return Err(HpoError::BinarySectionError {
+                    section: name,
+                    expected: entries_end,
+                    actual: offset,
+                });
+            }
+            checked_slice(bytes, offset..offset + len, &name)?;
+
+            if let Some(section) = section {
+                sections.push((section, offset..offset + len));
+            } else {
+                debug!("Skipping unknown binary section {id}");
+            }
+        }
+        Ok(Self { bytes, sections })
+    }
+
+    /// Returns the data of the `section` or `None` if the section is not present
+    pub(crate) fn get(&self, section: Section) -> Option<&'a [u8]> {
+        self.range(section).map(|range| &self.bytes[range])
+    }
+
+    /// Returns the position of the `section` in the binary data
+    /// or `None` if the section is not present
+    pub(crate) fn range(&self, section: Section) -> Option<Range<usize>> {
+        self.sections
+            .iter()
+            .find(|(kind, _)| *kind == section)
+            .map(|(_, range)| range.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn section_directory() {
+        let bytes = with_sections(
+            vec![0xff, 0xff],
+            &[
+                (Section::Terms.id(), vec![1, 2, 3]),
+                (99, vec![4]),
+                (Section::Genes.id(), vec![]),
+                (Section::Metadata.id(), vec![5, 6]),
+            ],
+        );
+        assert_eq!(bytes.len(), 2 + 8 + 4 * 12 + 6);
+        assert!(verify_checksum(&bytes, 2).is_ok());
+
+        let directory = SectionDirectory::from_bytes(&bytes, 2).unwrap();
+        assert_eq!(directory.get(Section::Terms), Some(&[1u8, 2, 3][..]));
+        assert_eq!(directory.get(Section::Genes), Some(&[][..]));
+        assert_eq!(directory.get(Section::Metadata), Some(&[5u8, 6][..]));
+        assert_eq!(directory.get(Section::Parents), None);
+    }
+
+    #[test]
+    fn section_out_of_bounds() {
+        let bytes = with_sections(Vec::new(), &[(Section::Terms.id(), vec![1, 2, 3])]);
+        assert_eq!(bytes.len(), 23);
+
+        let err = SectionDirectory::from_bytes(&bytes[..22], 0).unwrap_err();
+        assert!(matches!(
+            err,
+            HpoError::BinarySectionError { section, expected: 23, actual: 22 } if section == "terms"
+        ));
+
+        let err = SectionDirectory::from_bytes(&bytes[..10], 0).unwrap_err();
+        assert!(matches!(
+            err,
+            HpoError::BinarySectionError { section, expected: 20, actual: 10 } if section == "section directory"
+        ));
+
+        assert!(SectionDirectory::from_bytes(&[], 0).is_err());
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let mut bytes = with_sections(Vec::new(), &[(Section::Terms.id(), vec![1, 2, 3])]);
+        assert!(verify_checksum(&bytes, 0).is_ok());
+
+        bytes[21] = 0xff;
+        assert!(matches!(
+            verify_checksum(&bytes, 0),
+            Err(HpoError::BinaryChecksumError { .. })
+        ));
+        assert!(verify_checksum(&bytes[..2], 0).is_err());
+    }
+}
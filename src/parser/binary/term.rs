@@ -3,6 +3,7 @@
 
 use super::Bytes;
 use crate::term::internal::HpoTermInternal;
+use crate::term::{Definition, Synonym, SynonymScope, SynonymType, Xref};
 use crate::HpoError;
 
 /// Creates an `HpoTermInternal` from bytes
@@ -32,7 +33,10 @@ pub(crate) fn from_bytes_v1(bytes: Bytes) -> Result<HpoTermInternal, HpoError> {
         return Err(HpoError::ParseBinaryError);
     }
 
-    let Ok(name) = String::from_utf8(bytes[9..total_len as usize].to_vec()) else {
+    let Some(name) = bytes.data.get(9..total_len as usize) else {
+        return Err(HpoError::ParseBinaryError);
+    };
+    let Ok(name) = String::from_utf8(name.to_vec()) else {
         return Err(HpoError::ParseBinaryError);
     };
     Ok(HpoTermInternal::new(name, id.into()))
@@ -86,3 +90,127 @@ pub(crate) fn from_bytes_v2(bytes: Bytes) -> Result<HpoTermInternal, HpoError> {
     }
     Ok(term)
 }
+
+/// Creates an `HpoTermInternal` from bytes
+///
+/// The binary layout is defined as:
+///
+/// | Byte offset | Number of bytes | Description |
+/// | --- | --- | --- |
+/// | 0 | 4 | The total length of the binary data blob as big-endian `u32` |
+/// | 4 | 4 | The Term ID as big-endian `u32` |
+/// | 8 | 1 | The length of the Term Name (converted to a u8 vector) as a `u8` |
+/// | 9 | n | The Term name as u8 vector. If the name has more than 255 bytes, it is trimmed to 255 |
+/// | 9 + n | 1 | Flags, currently only obsolete
+/// | 10 + n | 4 | Term ID of a replacement term as big-endian `u32` or 0 if None |
+/// | 14 + n | x | Term details (synonyms, definition etc) |
+///
+/// See [`HpoTermInternal::as_bytes`] for the layout of the term details
+pub(crate) fn from_bytes_v4(bytes: Bytes) -> Result<HpoTermInternal, HpoError> {
+    // The term must be parsed within its own total length, the following
+    // bytes belong to the next term
+    let total_len = u32_at(&bytes, 0)? as usize;
+    let Some(data) = bytes.data.get(..total_len) else {
+        return Err(HpoError::ParseBinaryError);
+    };
+    let bytes = Bytes::new(data, bytes.version());
+
+    // Checks that the term contains at least the name, flags and replacement
+    let mut term = from_bytes_v2(bytes)?;
+
+    let mut offset = 14 + bytes[8] as usize;
+
+    // Synonyms
+    let n_synonyms = u32_at(&bytes, offset)?;
+    offset += 4;
+    for _ in 0..n_synonyms {
+        let Some(&scope) = bytes.data.get(offset) else {
+            return Err(HpoError::ParseBinaryError);
+        };
+        let scope = SynonymScope::try_from(scope)?;
+        offset += 1;
+        let kind = string_at(&bytes, &mut offset)?;
+        let name = string_at(&bytes, &mut offset)?;
+        let kind = if kind.is_empty() {
+            None
+        } else {
+            Some(SynonymType::from(kind.as_str()))
+        };
+        term.synonyms_mut().push(Synonym::new(&name, scope, kind));
+    }
+
+    // Definition
+    let text = string_at(&bytes, &mut offset)?;
+    let references = strings_at(&bytes, &mut offset)?;
+    if !text.is_empty() || !references.is_empty() {
+        *term.definition_mut() = Some(Definition::new(&text, references));
+    }
+
+    // Comment
+    let comment = string_at(&bytes, &mut offset)?;
+    if !comment.is_empty() {
+        *term.comment_mut() = Some(comment);
+    }
+
+    // Alternative IDs
+    let n_alt_ids = u32_at(&bytes, offset)?;
+    offset += 4;
+    for _ in 0..n_alt_ids {
+        term.alt_ids_mut().push(u32_at(&bytes, offset)?.into());
+        offset += 4;
+    }
+
+    // Cross-references
+    for xref in strings_at(&bytes, &mut offset)? {
+        let xref = Xref::try_from(xref.as_str()).map_err(|_| HpoError::ParseBinaryError)?;
+        term.xrefs_mut().push(xref);
+    }
+
+    // Terms to consider instead of an obsolete term
+    let n_consider = u32_at(&bytes, offset)?;
+    offset += 4;
+    for _ in 0..n_consider {
+        term.consider_mut().push(u32_at(&bytes, offset)?.into());
+        offset += 4;
+    }
+
+    // Subsets
+    *term.subsets_mut() = strings_at(&bytes, &mut offset)?;
+
+    if offset == total_len {
+        Ok(term)
+    } else {
+        Err(HpoError::ParseBinaryError)
+    }
+}
+
+/// Returns the big-endian `u32` at the given offset
+fn u32_at(bytes: &Bytes, offset: usize) -> Result<u32, HpoError> {
+    bytes
+        .data
+        .get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or(HpoError::ParseBinaryError)
+}
+
+/// Returns the length-prefixed string at the given offset
+///
+/// The `offset` is moved past the end of the string
+fn string_at(bytes: &Bytes, offset: &mut usize) -> Result<String, HpoError> {
+    let len = u32_at(bytes, *offset)? as usize;
+    *offset += 4;
+    let Some(raw) = bytes.data.get(*offset..*offset + len) else {
+        return Err(HpoError::ParseBinaryError);
+    };
+    *offset += len;
+    String::from_utf8(raw.to_vec()).map_err(|_| HpoError::ParseBinaryError)
+}
+
+/// Returns the list of length-prefixed strings at the given offset
+///
+/// The `offset` is moved past the end of the last string
+fn strings_at(bytes: &Bytes, offset: &mut usize) -> Result<Vec<String>, HpoError> {
+    let n = u32_at(bytes, *offset)?;
+    *offset += 4;
+    (0..n).map(|_| string_at(bytes, offset)).collect()
+}
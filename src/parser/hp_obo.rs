@@ -1,130 +1,647 @@
-use tracing::{error, trace, warn};
+use tracing::{trace, warn};
 
-use crate::{parser::Path, HpoError, HpoResult};
-use std::fs;
+use crate::{HpoError, HpoResult};
+use std::collections::BTreeSet;
+use std::io::{BufRead, Lines, Write};
 
+use crate::term::{Definition, HpoTerm, Synonym, SynonymScope, SynonymType, Xref};
 use crate::{term::internal::HpoTermInternal, HpoTermId};
+use crate::{Ontology, OntologyMetadata};
 
 use crate::ontology::builder::{AllTerms, LooseCollection};
 use crate::ontology::Builder;
+use crate::parser::release_version;
 
 /// Links terms to each other (Child - Parent)
 ///
 /// This type is used to store the Term - Parent dependencies
-/// for all HPO-Terms
-type Connections = Vec<(HpoTermId, HpoTermId)>;
+/// for all HPO-Terms, along with the line number and text
+/// of the `is_a` line.
+type Connections = Vec<(HpoTermId, HpoTermId, usize, String)>;
 
-/// Parses the `hp.obo` file as provided by Jax
+/// The source name that is used in errors when parsing from a reader
+pub(crate) const READER_SOURCE: &str = "<reader>";
+
+/// Report of all issues that occured while parsing OBO data in lenient mode
+///
+/// Each issue is an [`HpoError::ParseOboError`] that contains the
+/// source, line number and text of the offending line.
+/// Lenient parsing skips the offending line (or the full stanza, if the term
+/// cannot be created at all) instead of failing.
+///
+/// Invalid synonyms, xrefs and definitions are skipped with a warning in
+/// strict mode as well and are only recorded in the report.
+///
+/// # Examples
+///
+/// ```
+/// use hpo::builder::Builder;
+///
+/// let obo = "[Term]
+/// id: HP:0000001
+/// name: All
+///
+/// [Term]
+/// id: HP:0000002
+/// name: Child
+/// is_a: HP:0000001 ! All
+/// alt_id: invalid
+/// ";
+///
+/// let (builder, report) = Builder::new().add_obo_lenient(obo.as_bytes()).unwrap();
+/// assert_eq!(report.len(), 1);
+/// for warning in report.warnings() {
+///     println!("{warning}");
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ParseReport {
+    warnings: Vec<HpoError>,
+}
+
+impl ParseReport {
+    /// Returns all issues that occured during parsing
+    pub fn warnings(&self) -> &[HpoError] {
+        &self.warnings
+    }
+
+    /// Returns the number of issues
+    pub fn len(&self) -> usize {
+        self.warnings.len()
+    }
+
+    /// Returns `true` if the data was parsed without any issues
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+/// Handles issues during parsing, depending on the parsing mode
+///
+/// In strict mode, structural issues ([`Issues::add`]) are returned as error.
+/// In lenient mode, they are collected in a [`ParseReport`], together with all
+/// issues of optional data ([`Issues::warn`]).
+struct Issues<'a> {
+    source: &'a str,
+    lenient: bool,
+    report: ParseReport,
+}
+
+impl Issues<'_> {
+    /// Records an issue in the given line
+    ///
+    /// # Errors
+    ///
+    /// Returns the issue as [`HpoError::ParseOboError`] in strict mode
+    fn add(&mut self, line: usize, text: &str, reason: &str) -> HpoResult<()> {
+        let err = self.error(line, text, reason);
+        if self.lenient {
+            warn!("{}", err);
+            self.report.warnings.push(err);
+            Ok(())
+        } else {
+            Err(err)
+        }
+    }
+
+    /// Records an issue of optional data in the given line
+    ///
+    /// The issue is never returned as error, the offending line is skipped
+    /// with a warning in both modes.
+    fn warn(&mut self, line: usize, text: &str, reason: &str) {
+        let err = self.error(line, text, reason);
+        warn!("{}", err);
+        self.report.warnings.push(err);
+    }
+
+    fn error(&self, line: usize, text: &str, reason: &str) -> HpoError {
+        HpoError::ParseOboError {
+            file: self.source.to_string(),
+            line,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Parses OBO data, e.g. from `hp.obo`, from any reader
 ///
 /// It extracts the `HpoTermId`, the name and the list of parents
-/// and inserts them into the [`Ontology`]
+/// and inserts them into the [`Ontology`](`crate::Ontology`)
 ///
-/// Once the parsing is finshed, the indirect parents for each term
-/// are cached.
+/// The data is parsed stanza by stanza, so the full content is never
+/// loaded into memory at once. `[Typedef]` and other non-`[Term]` stanzas
+/// are ignored.
 ///
-/// If you use this function you cannot add additional terms or
-/// parents afterwards, since all dependency data will be already cached.
-pub(super) fn read_obo_file<P: AsRef<Path>>(
-    filename: P,
+/// `source` is the name of the data source, e.g. the filename, and is only
+/// used to locate errors. In `lenient` mode, invalid data is skipped and
+/// reported in the [`ParseReport`] instead of returning an error.
+///
+/// If you use this function you cannot add additional terms
+/// afterwards, since the builder transitions to `Builder<AllTerms>`.
+pub(crate) fn read_obo<R: BufRead>(
+    reader: R,
+    source: &str,
     mut builder: Builder<LooseCollection>,
-) -> HpoResult<Builder<AllTerms>> {
+    lenient: bool,
+) -> HpoResult<(Builder<AllTerms>, ParseReport)> {
+    let mut issues = Issues {
+        source,
+        lenient,
+        report: ParseReport::default(),
+    };
+
     // stores tuples of Term - Parent
     let mut connections: Connections = Vec::new();
 
-    let Ok(file_content) = fs::read_to_string(&filename) else {
-        return Err(HpoError::CannotOpenFile(
-            filename.as_ref().display().to_string(),
-        ));
-    };
-
-    for term in file_content.split("\n\n") {
-        if let Some(term) = term.strip_prefix("[Term]\n") {
-            if let Some(raw_term) = term_from_obo(term) {
-                let id = *raw_term.id();
-                builder.add_term(raw_term);
-                add_connections(&mut connections, term, id);
-            } else {
-                warn!("Unable to parse: {}", term);
+    for stanza in Stanzas::new(reader, source) {
+        let stanza = stanza?;
+        match stanza.kind.as_deref() {
+            Some("Term") => {
+                if let Some(raw_term) = term_from_obo(&stanza, &mut issues, &mut connections)? {
+                    builder.add_term(raw_term);
+                }
+            }
+            Some(kind) => trace!("Ignoring [{}] stanza in line {}", kind, stanza.line),
+            None if stanza.lines.is_empty() => (),
+            None => {
+                trace!("Parsing the header");
+                builder.set_hpo_version(version_from_obo(&stanza).unwrap_or_else(|| {
+                    warn!("No HPO Ontology version detected");
+                    (0u16, 0u8, 0u8)
+                }));
+                metadata_from_obo(&stanza, builder.metadata_mut());
             }
-        } else if term.starts_with("format-version: 1.2") {
-            trace!("Parsing the header");
-            builder.set_hpo_version(version_from_obo(term).unwrap_or_else(|| {
-                warn!("No HPO Ontology version detected");
-                (0u16, 0u8, 0u8)
-            }));
-        } else {
-            trace!("Ignoring: {}", term);
         }
     }
 
     let mut builder = builder.terms_complete();
 
-    for (child, parent) in connections {
-        builder.add_parent_unchecked(parent, child);
+    for (child, parent, line, text) in connections {
+        if builder.add_parent(parent, child).is_err() {
+            issues.add(line, &text, "parent term does not exist")?;
+        }
     }
 
-    Ok(builder)
+    Ok((builder, issues.report))
 }
 
-fn version_from_obo(header: &str) -> Option<(u16, u8, u8)> {
-    header.lines().find_map(|line| {
-        line.strip_prefix("data-version: hp/releases/")
-            .and_then(|version| {
-                if version.len() == 10 {
-                    Some((
-                        version[0..4].parse().unwrap_or(0u16),
-                        version[5..7].parse().unwrap_or(0u8),
-                        version[8..10].parse().unwrap_or(0u8),
-                    ))
-                } else {
-                    None
+/// A single stanza of an OBO file
+///
+/// The `kind` is the name of the stanza, e.g. `Term` or `Typedef`,
+/// or `None` for the header of the file. `line` is the line number of the
+/// stanza name and `lines` contains all non-empty lines of the stanza,
+/// together with their line number.
+struct Stanza {
+    kind: Option<String>,
+    line: usize,
+    lines: Vec<(usize, String)>,
+}
+
+/// Iterates the [`Stanza`]s of OBO data
+///
+/// A new stanza starts with every line in brackets, e.g. `[Term]`,
+/// independent of the number of blank lines between stanzas.
+/// Windows line endings (CRLF) are supported as well.
+struct Stanzas<'a, R> {
+    lines: Lines<R>,
+    source: &'a str,
+    kind: Option<String>,
+    line_number: usize,
+    stanza_start: usize,
+    done: bool,
+}
+
+impl<'a, R: BufRead> Stanzas<'a, R> {
+    fn new(reader: R, source: &'a str) -> Self {
+        Self {
+            lines: reader.lines(),
+            source,
+            kind: None,
+            line_number: 0,
+            stanza_start: 0,
+            done: false,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Stanzas<'_, R> {
+    type Item = HpoResult<Stanza>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut lines = Vec::new();
+        loop {
+            let Some(line) = self.lines.next() else {
+                self.done = true;
+                return Some(Ok(Stanza {
+                    kind: self.kind.take(),
+                    line: self.stanza_start,
+                    lines,
+                }));
+            };
+            self.line_number += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(HpoError::ParseOboError {
+                        file: self.source.to_string(),
+                        line: self.line_number,
+                        text: String::new(),
+                        reason: err.to_string(),
+                    }));
                 }
-            })
+            };
+            let line = line.trim_end_matches('\r');
+            if let Some(kind) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                let stanza = Stanza {
+                    kind: self.kind.replace(kind.to_string()),
+                    line: self.stanza_start,
+                    lines,
+                };
+                self.stanza_start = self.line_number;
+                return Some(Ok(stanza));
+            }
+            if !line.trim().is_empty() {
+                lines.push((self.line_number, line.to_string()));
+            }
+        }
+    }
+}
+
+fn version_from_obo(header: &Stanza) -> Option<(u16, u8, u8)> {
+    header.lines.iter().find_map(|(_, line)| {
+        line.strip_prefix("data-version: hp/releases/")
+            .filter(|version| version.len() == 10)
+            .and_then(release_version)
     })
 }
 
-fn term_from_obo(term: &str) -> Option<HpoTermInternal> {
-    let mut id: Option<&str> = None;
-    let mut name: Option<&str> = None;
-    let mut obsolete: Option<&str> = None;
-    let mut replaced_by: Option<&str> = None;
-    for line in term.lines() {
-        match parse_line(line) {
-            ("id", value) => id = Some(value),
-            ("name", value) => name = Some(value),
-            ("is_obsolete", value) => obsolete = Some(value),
-            ("replaced_by", value) => replaced_by = Some(value),
+/// Adds all release information from the header to the metadata
+fn metadata_from_obo(header: &Stanza, metadata: &mut OntologyMetadata) {
+    for (_, line) in &header.lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key {
+            "format-version" => metadata.set_format_version(value),
+            "data-version" => metadata.set_data_version(value),
+            "ontology" => metadata.set_ontology(value),
+            "date" => metadata.set_date(value),
+            "remark" => metadata.add_remark(value),
+            _ => {}
+        }
+    }
+}
+
+/// Parses a `[Term]` stanza
+///
+/// The `is_a` connections of the term are added to `connections`.
+/// Returns `None` if the stanza does not contain a valid term
+/// and the parsing is lenient.
+fn term_from_obo(
+    stanza: &Stanza,
+    issues: &mut Issues,
+    connections: &mut Connections,
+) -> HpoResult<Option<HpoTermInternal>> {
+    let mut id: Option<HpoTermId> = None;
+    let mut name: Option<String> = None;
+    let mut obsolete = false;
+    let mut replaced_by: Option<HpoTermId> = None;
+    let mut parents: Vec<(HpoTermId, usize, &str)> = Vec::new();
+    let mut alt_ids: Vec<HpoTermId> = Vec::new();
+    let mut consider: Vec<HpoTermId> = Vec::new();
+    let mut subsets: Vec<String> = Vec::new();
+    let mut xrefs: Vec<Xref> = Vec::new();
+    let mut synonyms: Vec<Synonym> = Vec::new();
+    let mut definition: Option<Definition> = None;
+    let mut comment: Option<String> = None;
+    for (line_number, line) in &stanza.lines {
+        let line_number = *line_number;
+        let Some((key, value)) = parse_line(line) else {
+            issues.add(line_number, line, "line is not a `key: value` pair")?;
+            continue;
+        };
+        match key {
+            "id" => match HpoTermId::try_from(value) {
+                Ok(term_id) => id = Some(term_id),
+                Err(_) => issues.add(line_number, line, "invalid term ID")?,
+            },
+            "name" => name = Some(unescape(value)),
+            "is_obsolete" => obsolete = value == "true",
+            "replaced_by" => match HpoTermId::try_from(value) {
+                Ok(term_id) => replaced_by = Some(term_id),
+                Err(_) => issues.add(line_number, line, "invalid replacement term ID")?,
+            },
+            "is_a" => match first_token(value).map(HpoTermId::try_from) {
+                Some(Ok(term_id)) => parents.push((term_id, line_number, line)),
+                _ => issues.add(line_number, line, "invalid parent term ID")?,
+            },
+            "alt_id" => match HpoTermId::try_from(value) {
+                Ok(term_id) => alt_ids.push(term_id),
+                Err(_) => issues.add(line_number, line, "invalid alternative ID")?,
+            },
+            "consider" => match HpoTermId::try_from(value) {
+                Ok(term_id) => consider.push(term_id),
+                Err(_) => issues.add(line_number, line, "invalid consider term ID")?,
+            },
+            "subset" => subsets.push(value.to_string()),
+            // xrefs can have a trailing description or modifiers
+            "xref" => match first_token(value).map(Xref::try_from) {
+                Some(Ok(xref)) => xrefs.push(xref),
+                _ => issues.warn(line_number, line, "invalid xref"),
+            },
+            "synonym" => match synonym_from_obo(value) {
+                Some(synonym) => synonyms.push(synonym),
+                None => issues.warn(line_number, line, "invalid synonym"),
+            },
+            "def" => match definition_from_obo(value) {
+                Some(def) => definition = Some(def),
+                None => issues.warn(line_number, line, "invalid definition"),
+            },
+            "comment" => comment = Some(unescape(value)),
             _ => (),
         }
     }
-    if let (Some(id), Some(name)) = (id, name) {
-        let mut term = HpoTermInternal::try_new(id, name).unwrap();
-        if obsolete == Some("true") {
-            *term.obsolete_mut() = true;
+
+    let (Some(id), Some(name)) = (id, name) else {
+        issues.add(stanza.line, "[Term]", "term without ID or name")?;
+        return Ok(None);
+    };
+
+    let mut term = HpoTermInternal::new(name, id);
+    *term.obsolete_mut() = obsolete;
+    *term.replacement_mut() = replaced_by;
+    *term.alt_ids_mut() = alt_ids;
+    *term.consider_mut() = consider;
+    *term.subsets_mut() = subsets;
+    *term.xrefs_mut() = xrefs;
+    *term.synonyms_mut() = synonyms;
+    *term.definition_mut() = definition;
+    *term.comment_mut() = comment;
+
+    for (parent, line_number, line) in parents {
+        connections.push((id, parent, line_number, line.to_string()));
+    }
+
+    Ok(Some(term))
+}
+
+/// Parses the value of a `synonym` line
+///
+/// ```text
+/// "Dry mouth" EXACT layperson []
+/// "High arched palate" EXACT []
+/// ```
+fn synonym_from_obo(value: &str) -> Option<Synonym> {
+    let (name, rest) = quoted_value(value)?;
+    let mut fields = rest.split_whitespace();
+    let scope = SynonymScope::try_from(fields.next()?).ok()?;
+    let kind = fields
+        .next()
+        .filter(|field| !field.starts_with('['))
+        .map(SynonymType::from);
+    Some(Synonym::new(&name, scope, kind))
+}
+
+/// Parses the value of a `def` line
+///
+/// ```text
+/// "Dryness of the mouth due to salivary gland dysfunction." [HPO:probinson, PMID:19125428]
+/// ```
+fn definition_from_obo(value: &str) -> Option<Definition> {
+    let (text, rest) = quoted_value(value)?;
+    let references = rest
+        .trim()
+        .strip_prefix('[')
+        .and_then(references_from_obo)
+        .unwrap_or_default();
+    Some(Definition::new(&text, references))
+}
+
+/// Parses a comma-separated list of references up to the closing `]`
+///
+/// Escaped commas and brackets, e.g. `\,`, are part of the reference.
+/// Returns `None` if the list is not closed.
+fn references_from_obo(value: &str) -> Option<Vec<String>> {
+    let mut references = Vec::new();
+    let mut reference = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => reference.push(unescape_char(chars.next()?)),
+            ',' | ']' => {
+                let trimmed = reference.trim();
+                if !trimmed.is_empty() {
+                    references.push(trimmed.to_string());
+                }
+                if c == ']' {
+                    return Some(references);
+                }
+                reference.clear();
+            }
+            _ => reference.push(c),
         }
-        if let Some(replacement) = replaced_by {
-            *term.replacement_mut() =
-                Some(HpoTermId::try_from(replacement).expect("Invalid replacement"));
+    }
+    None
+}
+
+/// Splits a value that starts with a quoted string into the
+/// unescaped string and the remainder after the closing quote
+fn quoted_value(value: &str) -> Option<(String, &str)> {
+    let value = value.strip_prefix('"')?;
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => unescaped.push(unescape_char(chars.next()?.1)),
+            '"' => return Some((unescaped, &value[idx + 1..])),
+            _ => unescaped.push(c),
         }
-        return Some(term);
     }
     None
 }
 
-fn add_connections(connections: &mut Connections, term: &str, id: HpoTermId) {
-    for line in term.lines() {
-        if let Some(value) = line.strip_prefix("is_a: ") {
-            if let Some((term_id, _)) = value.split_once(' ') {
-                connections.push((id, HpoTermId::try_from(term_id).unwrap()));
-            } else {
-                error!("Unable to parse HPO ID from {}", value);
+/// Replaces all OBO escape sequences, e.g. `\n`, in an unquoted value
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                unescaped.push(unescape_char(escaped));
             }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
+}
+
+/// Returns the character represented by an OBO escape sequence
+fn unescape_char(c: char) -> char {
+    match c {
+        'n' => '\n',
+        't' => '\t',
+        'W' => ' ',
+        other => other,
+    }
+}
+
+/// Splits an OBO line into its key and value
+///
+/// Trailing whitespace of the value is removed
+fn parse_line(line: &str) -> Option<(&str, &str)> {
+    line.split_once(':')
+        .map(|(key, value)| (key.trim(), value.trim()))
+}
+
+/// Returns the first whitespace-separated token of a value
+///
+/// This is used to remove trailing comments, e.g. `HP:0000001 ! All`
+fn first_token(value: &str) -> Option<&str> {
+    value.split_whitespace().next()
+}
+
+/// Writes the ontology as OBO 1.2 data
+///
+/// The header contains the `data-version`, `date`, remarks and ontology ID from
+/// the metadata of the ontology and the definitions of all used subsets and
+/// synonym types. If the metadata does not contain a `data-version`, it is
+/// derived from the HPO version of the ontology.
+/// Terms are written in the order of their ID.
+pub(crate) fn write_obo<W: Write>(ontology: &Ontology, mut writer: W) -> std::io::Result<()> {
+    let mut terms: Vec<HpoTerm> = ontology.into_iter().collect();
+    terms.sort_by_key(HpoTerm::id);
+    let metadata = ontology.metadata();
+
+    writeln!(writer, "format-version: 1.2")?;
+    if let Some(version) = metadata.data_version() {
+        writeln!(writer, "data-version: {version}")?;
+    } else {
+        let version = ontology.hpo_version();
+        if version != "0000-00-00" {
+            writeln!(writer, "data-version: hp/releases/{version}")?;
+        }
+    }
+    if let Some(date) = metadata.date() {
+        writeln!(writer, "date: {date}")?;
+    }
+    let subsets: BTreeSet<&str> = terms
+        .iter()
+        .flat_map(|term| term.subsets().iter().map(String::as_str))
+        .collect();
+    for subset in subsets {
+        writeln!(writer, "subsetdef: {subset} \"{}\"", escape(subset))?;
+    }
+    let synonym_types: BTreeSet<&str> = terms
+        .iter()
+        .flat_map(|term| term.synonyms().iter())
+        .filter_map(|synonym| synonym.synonym_type().map(SynonymType::as_str))
+        .collect();
+    for kind in synonym_types {
+        writeln!(writer, "synonymtypedef: {kind} \"{}\"", escape(kind))?;
+    }
+    for remark in metadata.remarks() {
+        writeln!(writer, "remark: {remark}")?;
+    }
+    writeln!(writer, "ontology: {}", metadata.ontology().unwrap_or("hp"))?;
+
+    for term in &terms {
+        writeln!(writer)?;
+        write_term(term, &mut writer)?;
+    }
+    Ok(())
+}
+
+/// Writes a single `[Term]` stanza
+fn write_term<W: Write>(term: &HpoTerm, writer: &mut W) -> std::io::Result<()> {
+    writeln!(writer, "[Term]")?;
+    writeln!(writer, "id: {}", term.id())?;
+    writeln!(writer, "name: {}", escape(term.name()))?;
+    for alt_id in term.alt_ids() {
+        writeln!(writer, "alt_id: {alt_id}")?;
+    }
+    if let Some(definition) = term.definition() {
+        writeln!(
+            writer,
+            "def: \"{}\" [{}]",
+            escape(definition.text()),
+            definition
+                .references()
+                .iter()
+                .map(|reference| escape_reference(reference))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )?;
+    }
+    if let Some(comment) = term.comment() {
+        writeln!(writer, "comment: {}", escape(comment))?;
+    }
+    for subset in term.subsets() {
+        writeln!(writer, "subset: {subset}")?;
+    }
+    for synonym in term.synonyms() {
+        write!(
+            writer,
+            "synonym: \"{}\" {}",
+            escape(synonym.name()),
+            synonym.scope()
+        )?;
+        if let Some(kind) = synonym.synonym_type() {
+            write!(writer, " {kind}")?;
+        }
+        writeln!(writer, " []")?;
+    }
+    for xref in term.xrefs() {
+        writeln!(writer, "xref: {xref}")?;
+    }
+    for parent in term.parents() {
+        writeln!(writer, "is_a: {} ! {}", parent.id(), escape(parent.name()))?;
+    }
+    if term.is_obsolete() {
+        writeln!(writer, "is_obsolete: true")?;
+    }
+    for consider in term.consider() {
+        writeln!(writer, "consider: {consider}")?;
+    }
+    if let Some(replacement) = term.replacement_id() {
+        writeln!(writer, "replaced_by: {replacement}")?;
+    }
+    Ok(())
+}
+
+/// Escapes backslashes, quotes and newlines for OBO values
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
         }
     }
+    escaped
 }
 
-fn parse_line(line: &str) -> (&str, &str) {
-    line.split_once(": ").expect("unable to parse line")
+/// Escapes a reference of a definition, including commas and brackets
+fn escape_reference(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in escape(value).chars() {
+        if matches!(c, ',' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
 #[cfg(test)]
@@ -132,16 +649,31 @@ mod test {
     // use std::fs;
 
     use super::*;
+    use std::path::Path;
 
-    #[test]
-    fn split_terms() {
-        let builder = Builder::new();
-        let builder = read_obo_file("tests/small.obo", builder).unwrap();
-        let ont = builder
+    /// Parses the `hp.obo` file, see [`read_obo`] for details
+    pub(crate) fn read_obo_file<P: AsRef<Path>>(
+        filename: P,
+        builder: Builder<LooseCollection>,
+    ) -> HpoResult<Builder<AllTerms>> {
+        let source = filename.as_ref().display().to_string();
+        read_obo(super::super::open_file(filename)?, &source, builder, false)
+            .map(|(builder, _)| builder)
+    }
+
+    /// Builds the ontology of `tests/small.obo`
+    fn small_ontology() -> Ontology {
+        read_obo_file("tests/small.obo", Builder::new())
+            .unwrap()
             .connect_all_terms()
             .calculate_information_content()
             .unwrap()
-            .build_minimal();
+            .build_minimal()
+    }
+
+    #[test]
+    fn split_terms() {
+        let ont = small_ontology();
 
         assert_eq!(ont.len(), 4);
 
@@ -157,4 +689,437 @@ mod test {
 
         assert_eq!(ont.hpo_version(), "2022-10-05");
     }
+
+    #[test]
+    fn write_sub_ontology() {
+        let ont = small_ontology();
+        let sub = ont
+            .sub_ontology(ont.hpo(217u32).unwrap(), [ont.hpo(218u32).unwrap()])
+            .unwrap();
+        assert_eq!(sub.hpo_version(), "2022-10-05");
+
+        let mut obo = Vec::new();
+        write_obo(&sub, &mut obo).unwrap();
+        let text = String::from_utf8(obo).unwrap();
+        assert!(text.starts_with("format-version: 1.2\ndata-version: hp/releases/2022-10-05\n"));
+        assert!(text.contains("id: HP:0000218\n"));
+        assert!(!text.contains("id: HP:0000219\n"));
+    }
+
+    #[test]
+    fn write_and_read() {
+        let ont = small_ontology();
+
+        let mut obo = Vec::new();
+        write_obo(&ont, &mut obo).unwrap();
+        let text = String::from_utf8(obo.clone()).unwrap();
+        assert!(text.starts_with("format-version: 1.2\ndata-version: hp/releases/2022-10-05\n"));
+        assert!(text.contains("subsetdef: hposlim_core \"hposlim_core\"\n"));
+        assert!(text.contains("synonymtypedef: layperson \"layperson\"\n"));
+        assert!(text.contains("is_a: HP:0000217 ! Xerostomia\n"));
+        assert!(text.contains("synonym: \"Dry mouth\" EXACT layperson []\n"));
+        assert!(text.contains("is_obsolete: true\n"));
+        assert!(text.contains("replaced_by: HP:0000315\n"));
+
+        let (builder, report) =
+            read_obo(obo.as_slice(), READER_SOURCE, Builder::new(), true).unwrap();
+        assert!(report.is_empty());
+        let other = builder
+            .connect_all_terms()
+            .calculate_information_content()
+            .unwrap()
+            .build_minimal();
+
+        assert_eq!(ont.len(), other.len());
+        assert_eq!(ont.hpo_version(), other.hpo_version());
+        for term in &ont {
+            let other = other.hpo(term.id()).unwrap();
+            assert_eq!(term.name(), other.name());
+            assert_eq!(term.is_obsolete(), other.is_obsolete());
+            assert_eq!(term.replacement_id(), other.replacement_id());
+            assert!(term.parent_ids().iter().eq(other.parent_ids().iter()));
+            assert_eq!(term.alt_ids(), other.alt_ids());
+            assert_eq!(term.consider(), other.consider());
+            assert_eq!(term.subsets(), other.subsets());
+            assert_eq!(term.xrefs(), other.xrefs());
+            assert_eq!(term.synonyms(), other.synonyms());
+            assert_eq!(term.definition(), other.definition());
+            assert_eq!(term.comment(), other.comment());
+        }
+    }
+
+    #[test]
+    fn write_and_read_escaped_values() {
+        let obo = r#"[Term]
+id: HP:0000001
+name: All
+
+[Term]
+id: HP:0000118
+name: Phenotypic \\ abnormality\n with \"escapes\"
+def: "Text." [PMID:1, URL:https://example.com/?a=1\,b=2\], ISBN:3]
+is_a: HP:0000001 ! All
+"#;
+        let ont = Ontology::from_obo(obo.as_bytes()).unwrap();
+        let term = ont.hpo(118u32).unwrap();
+        assert_eq!(term.name(), "Phenotypic \\ abnormality\n with \"escapes\"");
+        assert_eq!(
+            term.definition().unwrap().references(),
+            ["PMID:1", "URL:https://example.com/?a=1,b=2]", "ISBN:3"]
+        );
+
+        let mut written = Vec::new();
+        ont.write_obo(&mut written).unwrap();
+        let other = Ontology::from_obo(written.as_slice()).unwrap();
+        let other_term = other.hpo(118u32).unwrap();
+        assert_eq!(term.name(), other_term.name());
+        assert_eq!(term.definition(), other_term.definition());
+    }
+
+    #[test]
+    fn escape_values() {
+        assert_eq!(
+            escape("a \"quoted\"\nvalue\\"),
+            "a \\\"quoted\\\"\\nvalue\\\\"
+        );
+        let (unescaped, _) = quoted_value(&format!("\"{}\"", escape("a \"b\"\nc"))).unwrap();
+        assert_eq!(unescaped, "a \"b\"\nc");
+    }
+
+    #[test]
+    fn read_from_memory() {
+        let obo = "format-version: 1.2\r
+data-version: hp/releases/2023-04-05\r
+\r
+[Term]\r
+id: HP:0000001\r
+name: All\r
+\r
+\r
+\r
+[Term]\r
+id: HP:0000002\r
+name: Child\r
+is_a: HP:0000001 ! All\r
+[Term]\r
+id: HP:0000003\r
+name: Grandchild\r
+is_a: HP:0000002 ! Child\r
+\r
+[Typedef]\r
+id: UPHENO:0000001\r
+name: affects\r
+";
+        let (builder, report) =
+            read_obo(obo.as_bytes(), READER_SOURCE, Builder::new(), false).unwrap();
+        assert!(report.is_empty());
+        let ont = builder
+            .connect_all_terms()
+            .calculate_information_content()
+            .unwrap()
+            .build_minimal();
+
+        assert_eq!(ont.len(), 3);
+        assert_eq!(ont.hpo_version(), "2023-04-05");
+        assert_eq!(ont.hpo(2u32).unwrap().name(), "Child");
+        assert_eq!(ont.hpo(3u32).unwrap().name(), "Grandchild");
+        assert!(ont
+            .hpo(3u32)
+            .unwrap()
+            .all_parent_ids()
+            .contains(&1u32.into()));
+    }
+
+    #[test]
+    fn non_ascii_version() {
+        for version in ["2022é1-05", "2022-1é-05", "2022-10-é"] {
+            let obo = format!("format-version: 1.2\ndata-version: hp/releases/{version}\n");
+            let header = Stanzas::new(obo.as_bytes(), READER_SOURCE)
+                .next()
+                .unwrap()
+                .unwrap();
+            assert_eq!(version_from_obo(&header), None);
+        }
+    }
+
+    #[test]
+    fn stanzas() {
+        let obo = "format-version: 1.2\n\n\n[Term]\nid: HP:0000001\n[Typedef]\nid: foo\n\n";
+        let stanzas: Vec<Stanza> = Stanzas::new(obo.as_bytes(), READER_SOURCE)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(stanzas.len(), 3);
+        assert!(stanzas[0].kind.is_none());
+        assert_eq!(stanzas[0].lines, [(1, String::from("format-version: 1.2"))]);
+        assert_eq!(stanzas[1].kind.as_deref(), Some("Term"));
+        assert_eq!(stanzas[1].line, 4);
+        assert_eq!(stanzas[1].lines, [(5, String::from("id: HP:0000001"))]);
+        assert_eq!(stanzas[2].kind.as_deref(), Some("Typedef"));
+        assert_eq!(stanzas[2].line, 6);
+        assert_eq!(stanzas[2].lines, [(7, String::from("id: foo"))]);
+
+        assert_eq!(Stanzas::new("".as_bytes(), READER_SOURCE).count(), 1);
+    }
+
+    const INVALID_OBO: &str = "[Term]
+id: HP:0000001
+name: All
+
+[Term]
+id: HP:0000002
+name: Child
+is_a: HP:0000001 ! All
+replaced_by: foobar
+no value
+
+[Term]
+id: HP:0000003
+name: Grandchild
+is_a: HP:0000002 ! Child
+is_a: HP:0000004 ! Does not exist
+
+[Term]
+name: Term without ID
+";
+
+    #[test]
+    fn strict_errors() {
+        let err = read_obo(INVALID_OBO.as_bytes(), "test.obo", Builder::new(), false)
+            .err()
+            .unwrap();
+        let HpoError::ParseOboError {
+            file, line, text, ..
+        } = err
+        else {
+            panic!("Invalid error type");
+        };
+        assert_eq!(file, "test.obo");
+        assert_eq!(line, 9);
+        assert_eq!(text, "replaced_by: foobar");
+    }
+
+    #[test]
+    fn strict_structural_errors() {
+        let header = "[Term]\nid: HP:0000001\nname: All\n\n[Term]\nid: HP:0000002\nname: Child\n";
+        for (line, expected_reason) in [
+            ("is_a: HP:foo ! Invalid", "invalid parent term ID"),
+            (
+                "is_a: HP:0000003 ! Does not exist",
+                "parent term does not exist",
+            ),
+            ("alt_id: HP:foo", "invalid alternative ID"),
+            ("no value", "line is not a `key: value` pair"),
+        ] {
+            let obo = format!("{header}{line}\n");
+            let err = read_obo(obo.as_bytes(), "test.obo", Builder::new(), false)
+                .err()
+                .unwrap();
+            let HpoError::ParseOboError {
+                line: line_number,
+                text,
+                reason,
+                ..
+            } = err
+            else {
+                panic!("Invalid error type");
+            };
+            assert_eq!(line_number, 8);
+            assert_eq!(text, line);
+            assert_eq!(reason, expected_reason);
+        }
+    }
+
+    #[test]
+    fn strict_skips_invalid_optional_data() {
+        let obo = "[Term]
+id: HP:0000001
+name: All
+synonym: \"Missing scope\" []
+xref: invalid
+def: Definition without quotes []
+";
+        let (builder, report) =
+            read_obo(obo.as_bytes(), "test.obo", Builder::new(), false).unwrap();
+        assert_eq!(report.len(), 3);
+
+        let ont = builder
+            .connect_all_terms()
+            .calculate_information_content()
+            .unwrap()
+            .build_minimal();
+        let term = ont.hpo(1u32).unwrap();
+        assert!(term.synonyms().is_empty());
+        assert!(term.xrefs().is_empty());
+        assert!(term.definition().is_none());
+    }
+
+    #[test]
+    fn strict_invalid_term_id() {
+        let obo = "[Term]\nid: HP:foo\nname: Invalid\n";
+        let err = read_obo(obo.as_bytes(), "test.obo", Builder::new(), false)
+            .err()
+            .unwrap();
+        assert!(matches!(err, HpoError::ParseOboError { line: 2, .. }));
+    }
+
+    #[test]
+    fn lenient_report() {
+        let (builder, report) =
+            read_obo(INVALID_OBO.as_bytes(), "test.obo", Builder::new(), true).unwrap();
+        let lines: Vec<usize> = report
+            .warnings()
+            .iter()
+            .map(|warning| match warning {
+                HpoError::ParseOboError { line, .. } => *line,
+                _ => panic!("Invalid error type"),
+            })
+            .collect();
+        assert_eq!(lines, [9, 10, 18, 16]);
+
+        let ont = builder
+            .connect_all_terms()
+            .calculate_information_content()
+            .unwrap()
+            .build_minimal();
+        assert_eq!(ont.len(), 3);
+        assert!(ont.hpo(2u32).unwrap().replacement_id().is_none());
+        assert_eq!(ont.hpo(3u32).unwrap().parents().count(), 1);
+    }
+
+    #[test]
+    fn synonyms() {
+        let ont = small_ontology();
+
+        let synonyms = ont.hpo(217u32).unwrap().synonyms();
+        assert_eq!(synonyms.len(), 4);
+        assert_eq!(synonyms[0].name(), "Decreased salivary flow");
+        assert_eq!(synonyms[0].scope(), SynonymScope::Broad);
+        assert!(synonyms[0].is_layperson());
+
+        let synonyms = ont.hpo(218u32).unwrap().synonyms();
+        assert_eq!(synonyms.len(), 9);
+        assert_eq!(synonyms[1].name(), "High arched palate");
+        assert_eq!(synonyms[1].scope(), SynonymScope::Exact);
+        assert!(synonyms[1].synonym_type().is_none());
+
+        assert!(ont.hpo(284u32).unwrap().synonyms().is_empty());
+    }
+
+    #[test]
+    fn alt_ids() {
+        let ont = small_ontology();
+
+        assert_eq!(
+            ont.hpo(217u32).unwrap().alt_ids(),
+            [HpoTermId::from(2709u32), HpoTermId::from(100_756u32)]
+        );
+        assert!(ont.hpo(284u32).unwrap().alt_ids().is_empty());
+
+        assert!(ont.hpo(2709u32).is_none());
+        let resolved = ont.resolve(2709u32).unwrap();
+        assert!(resolved.is_alternative());
+        assert_eq!(resolved.term().id(), HpoTermId::from(217u32));
+    }
+
+    #[test]
+    fn consider() {
+        let ont = small_ontology();
+
+        assert_eq!(
+            ont.hpo(284u32).unwrap().consider(),
+            [HpoTermId::from(217u32), HpoTermId::from(218u32)]
+        );
+        assert!(ont.hpo(217u32).unwrap().consider().is_empty());
+    }
+
+    #[test]
+    fn subsets() {
+        let ont = small_ontology();
+
+        assert_eq!(ont.hpo(217u32).unwrap().subsets(), ["hposlim_core"]);
+        assert!(ont.hpo(218u32).unwrap().subsets().is_empty());
+
+        let terms: Vec<HpoTermId> = ont
+            .subset_terms("hposlim_core")
+            .iter()
+            .map(|term| term.id())
+            .collect();
+        assert_eq!(terms, [HpoTermId::from(217u32), HpoTermId::from(219u32)]);
+        assert!(ont.subset_terms("foobar").is_empty());
+    }
+
+    #[test]
+    fn xrefs() {
+        let ont = small_ontology();
+
+        let xrefs = ont.hpo(217u32).unwrap().xrefs();
+        assert_eq!(xrefs.len(), 5);
+        assert_eq!(xrefs[0], "MSH:D014987");
+        assert_eq!(xrefs[4].prefix(), "UMLS");
+        assert_eq!(xrefs[4].id(), "C0043352");
+
+        let terms = ont.hpo_by_xref("UMLS:C0043352");
+        assert_eq!(terms.len(), 1);
+        assert_eq!(terms.iter().next().unwrap().id(), HpoTermId::from(217u32));
+
+        assert!(ont.hpo_by_xref("UMLS:C0000000").is_empty());
+        assert!(ont.hpo_by_xref("foobar").is_empty());
+    }
+
+    #[test]
+    fn parse_synonym() {
+        let synonym = synonym_from_obo(r#""Dry mouth" EXACT layperson []"#).unwrap();
+        assert_eq!(synonym.name(), "Dry mouth");
+        assert_eq!(synonym.scope(), SynonymScope::Exact);
+        assert_eq!(synonym.synonym_type(), Some(&SynonymType::Layperson));
+
+        let synonym = synonym_from_obo(r#""Ogival palate" RELATED []"#).unwrap();
+        assert_eq!(synonym.scope(), SynonymScope::Related);
+        assert!(synonym.synonym_type().is_none());
+
+        let synonym = synonym_from_obo(r#""A \"quoted\" name" NARROW [PMID:1]"#).unwrap();
+        assert_eq!(synonym.name(), r#"A "quoted" name"#);
+
+        assert!(synonym_from_obo(r#""Dry mouth" FOO []"#).is_none());
+        assert!(synonym_from_obo(r#""Dry mouth EXACT []"#).is_none());
+    }
+
+    #[test]
+    fn definition_and_comment() {
+        let ont = small_ontology();
+
+        let term = ont.hpo(217u32).unwrap();
+        let definition = term.definition().unwrap();
+        assert_eq!(
+            definition.text(),
+            "Dryness of the mouth due to salivary gland dysfunction."
+        );
+        assert_eq!(definition.references(), ["HPO:probinson"]);
+        assert!(term.comment().is_none());
+
+        let term = ont.hpo(218u32).unwrap();
+        assert_eq!(term.definition().unwrap().references(), ["PMID:19125428"]);
+        let comment = term.comment().unwrap();
+        assert!(comment.starts_with("The measuring device for this assessment"));
+        assert!(comment.ends_with("overused and\napplied inaccurately."));
+
+        assert!(ont.hpo(284u32).unwrap().definition().is_none());
+    }
+
+    #[test]
+    fn parse_definition() {
+        let definition =
+            definition_from_obo(r#""Some text." [PMID:123, https://example.com/foo]"#).unwrap();
+        assert_eq!(definition.text(), "Some text.");
+        assert_eq!(
+            definition.references(),
+            ["PMID:123", "https://example.com/foo"]
+        );
+
+        let definition = definition_from_obo(r#""Some\ntext." []"#).unwrap();
+        assert_eq!(definition.text(), "Some\ntext.");
+        assert!(definition.references().is_empty());
+
+        assert!(definition_from_obo("Some text. []").is_none());
+    }
 }
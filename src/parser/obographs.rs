@@ -0,0 +1,393 @@
+//! Parses the ontology from [OBO Graphs JSON](https://github.com/geneontology/obographs),
+//! e.g. `hp.json`
+//!
+//! Only the `is_a` relationships between HPO terms are used to build the
+//! ontology. Nodes and edges of other ontologies and all other relationship
+//! types are ignored.
+
+use std::io::Read;
+
+use serde::Deserialize;
+use tracing::{trace, warn};
+
+use crate::ontology::builder::{AllTerms, LooseCollection};
+use crate::ontology::Builder;
+use crate::parser::release_version;
+use crate::term::internal::HpoTermInternal;
+use crate::term::{Definition, Synonym, SynonymScope, SynonymType, Xref};
+use crate::{HpoError, HpoResult, HpoTermId};
+
+const OBO_PURL: &str = "http://purl.obolibrary.org/obo/";
+const ALT_ID: &str = "http://www.geneontology.org/formats/oboInOwl#hasAlternativeId";
+const CONSIDER: &str = "http://www.geneontology.org/formats/oboInOwl#consider";
+const REPLACED_BY: &str = "http://purl.obolibrary.org/obo/IAO_0100001";
+
+#[derive(Deserialize)]
+struct GraphDocument {
+    #[serde(default)]
+    graphs: Vec<Graph>,
+}
+
+#[derive(Deserialize)]
+struct Graph {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    meta: Option<GraphMeta>,
+    #[serde(default)]
+    nodes: Vec<Node>,
+    #[serde(default)]
+    edges: Vec<Edge>,
+}
+
+#[derive(Deserialize)]
+struct GraphMeta {
+    #[serde(default)]
+    version: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Node {
+    id: String,
+    #[serde(default)]
+    lbl: Option<String>,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    meta: Option<Meta>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct Meta {
+    #[serde(default)]
+    definition: Option<DefinitionValue>,
+    #[serde(default)]
+    comments: Vec<String>,
+    #[serde(default)]
+    subsets: Vec<String>,
+    #[serde(default)]
+    xrefs: Vec<Value>,
+    #[serde(default)]
+    synonyms: Vec<SynonymValue>,
+    #[serde(default)]
+    basic_property_values: Vec<PropertyValue>,
+    #[serde(default)]
+    deprecated: bool,
+}
+
+#[derive(Deserialize)]
+struct DefinitionValue {
+    val: String,
+    #[serde(default)]
+    xrefs: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Value {
+    val: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SynonymValue {
+    pred: String,
+    val: String,
+    #[serde(default)]
+    synonym_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PropertyValue {
+    pred: String,
+    val: String,
+}
+
+#[derive(Deserialize)]
+struct Edge {
+    sub: String,
+    pred: String,
+    obj: String,
+}
+
+/// Parses OBO Graphs JSON data, e.g. from `hp.json`, from any reader
+///
+/// It extracts all HPO terms with the same details as the OBO parser
+/// (name, obsolete state, replacement, alternative IDs, synonyms, definition,
+/// comment, cross-references, subsets and consider terms) and adds the
+/// `is_a` connections between them.
+pub(crate) fn read_obographs_json<R: Read>(
+    reader: R,
+    mut builder: Builder<LooseCollection>,
+) -> HpoResult<Builder<AllTerms>> {
+    let document: GraphDocument = serde_json::from_reader(reader)
+        .map_err(|err| HpoError::InvalidInput(format!("invalid OBO Graphs JSON: {err}")))?;
+
+    let mut connections: Vec<(HpoTermId, HpoTermId)> = Vec::new();
+
+    for graph in document.graphs {
+        if let Some(iri) = graph.meta.as_ref().and_then(|meta| meta.version.as_deref()) {
+            builder.metadata_mut().set_data_version(iri);
+            if let Some(version) = version_from_iri(iri) {
+                builder.set_hpo_version(version);
+            }
+        }
+        if let Some(id) = &graph.id {
+            builder.metadata_mut().set_ontology(id);
+        }
+        for node in graph.nodes {
+            if let Some(term) = term_from_node(node)? {
+                builder.add_term(term);
+            }
+        }
+        for edge in graph.edges {
+            if edge.pred != "is_a" {
+                continue;
+            }
+            if let (Some(child), Some(parent)) = (term_id(&edge.sub), term_id(&edge.obj)) {
+                connections.push((child?, parent?));
+            } else {
+                trace!("Ignoring edge {} is_a {}", edge.sub, edge.obj);
+            }
+        }
+    }
+
+    let mut builder = builder.terms_complete();
+    for (child, parent) in connections {
+        builder.add_parent(parent, child)?;
+    }
+    Ok(builder)
+}
+
+/// Parses the release date from the version IRI of the graph
+///
+/// ```text
+/// http://purl.obolibrary.org/obo/hp/releases/2023-04-05/hp.json
+/// ```
+fn version_from_iri(iri: &str) -> Option<(u16, u8, u8)> {
+    let (_, version) = iri.split_once("/releases/")?;
+    release_version(version)
+}
+
+/// Returns the [`HpoTermId`] of an IRI or CURIE, e.g.
+/// `http://purl.obolibrary.org/obo/HP_0000118` or `HP:0000118`
+///
+/// Returns `None` if the IRI does not belong to an HPO term and
+/// an error if the ID is invalid.
+fn term_id(iri: &str) -> Option<HpoResult<HpoTermId>> {
+    let curie = iri.strip_prefix(OBO_PURL).unwrap_or(iri);
+    curie
+        .strip_prefix("HP_")
+        .or_else(|| curie.strip_prefix("HP:"))
+        .map(|id| {
+            id.parse::<u32>()
+                .map(HpoTermId::from_u32)
+                .map_err(|_| HpoError::InvalidInput(format!("invalid term ID: {iri}")))
+        })
+}
+
+/// Returns the fragment of an IRI, e.g. `hposlim_core` from
+/// `http://purl.obolibrary.org/obo/hp#hposlim_core`
+fn fragment(iri: &str) -> &str {
+    iri.rsplit_once('#').map_or(iri, |(_, fragment)| fragment)
+}
+
+/// Creates an [`HpoTermInternal`] from a node of the graph
+///
+/// Returns `None` for all nodes that are not HPO classes
+fn term_from_node(node: Node) -> HpoResult<Option<HpoTermInternal>> {
+    if node.kind.as_deref() != Some("CLASS") {
+        return Ok(None);
+    }
+    let Some(id) = term_id(&node.id) else {
+        return Ok(None);
+    };
+    let id = id?;
+    let Some(name) = node.lbl else {
+        warn!("Ignoring term without name: {}", node.id);
+        return Ok(None);
+    };
+
+    let mut term = HpoTermInternal::new(name, id);
+    let meta = node.meta.unwrap_or_default();
+
+    *term.obsolete_mut() = meta.deprecated;
+    *term.definition_mut() = meta
+        .definition
+        .map(|def| Definition::new(&def.val, def.xrefs));
+    *term.comment_mut() = meta.comments.into_iter().next();
+    *term.subsets_mut() = meta
+        .subsets
+        .iter()
+        .map(|subset| fragment(subset).to_string())
+        .collect();
+    *term.xrefs_mut() = meta
+        .xrefs
+        .iter()
+        .map(|xref| Xref::try_from(xref.val.as_str()))
+        .collect::<HpoResult<_>>()?;
+    *term.synonyms_mut() = meta
+        .synonyms
+        .iter()
+        .map(synonym_from_value)
+        .collect::<HpoResult<_>>()?;
+
+    for property in &meta.basic_property_values {
+        let target = || {
+            term_id(&property.val).unwrap_or_else(|| {
+                Err(HpoError::InvalidInput(format!(
+                    "invalid term ID: {}",
+                    property.val
+                )))
+            })
+        };
+        match property.pred.as_str() {
+            ALT_ID => term.alt_ids_mut().push(target()?),
+            CONSIDER => term.consider_mut().push(target()?),
+            REPLACED_BY => *term.replacement_mut() = Some(target()?),
+            _ => (),
+        }
+    }
+
+    Ok(Some(term))
+}
+
+/// Creates a [`Synonym`] from its OBO Graphs representation
+///
+/// ```json
+/// {
+///   "pred": "hasExactSynonym",
+///   "val": "Dry mouth",
+///   "synonymType": "http://purl.obolibrary.org/obo/hp#layperson"
+/// }
+/// ```
+fn synonym_from_value(synonym: &SynonymValue) -> HpoResult<Synonym> {
+    let scope = match synonym.pred.as_str() {
+        "hasExactSynonym" => SynonymScope::Exact,
+        "hasBroadSynonym" => SynonymScope::Broad,
+        "hasNarrowSynonym" => SynonymScope::Narrow,
+        "hasRelatedSynonym" => SynonymScope::Related,
+        pred => {
+            return Err(HpoError::InvalidInput(format!(
+                "invalid synonym scope: {pred}"
+            )))
+        }
+    };
+    let kind = synonym
+        .synonym_type
+        .as_deref()
+        .map(|kind| SynonymType::from(fragment(kind)));
+    Ok(Synonym::new(&synonym.val, scope, kind))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ontology::Ontology;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    fn load(builder: Builder<AllTerms>) -> Ontology {
+        builder
+            .connect_all_terms()
+            .calculate_information_content()
+            .unwrap()
+            .build_minimal()
+    }
+
+    #[test]
+    fn same_as_obo() {
+        let json = File::open("tests/small.json").unwrap();
+        let json = load(read_obographs_json(json, Builder::new()).unwrap());
+        let obo = File::open("tests/small.obo").unwrap();
+        let (obo, _) = crate::parser::hp_obo::read_obo(
+            BufReader::new(obo),
+            "tests/small.obo",
+            Builder::new(),
+            false,
+        )
+        .unwrap();
+        let obo = load(obo);
+
+        assert_eq!(json.len(), obo.len());
+        assert_eq!(json.hpo_version(), obo.hpo_version());
+        assert_eq!(
+            json.metadata().data_version(),
+            Some("http://purl.obolibrary.org/obo/hp/releases/2022-10-05/hp.json")
+        );
+        assert_eq!(
+            json.metadata().ontology(),
+            Some("http://purl.obolibrary.org/obo/hp.json")
+        );
+        for term in &obo {
+            let other = json.hpo(term.id()).unwrap();
+            assert_eq!(term.name(), other.name());
+            assert_eq!(term.is_obsolete(), other.is_obsolete());
+            assert_eq!(term.replacement_id(), other.replacement_id());
+            assert!(term.parent_ids().iter().eq(other.parent_ids().iter()));
+            assert_eq!(term.alt_ids(), other.alt_ids());
+            assert_eq!(term.consider(), other.consider());
+            assert_eq!(term.subsets(), other.subsets());
+            assert_eq!(term.xrefs(), other.xrefs());
+            assert_eq!(term.synonyms(), other.synonyms());
+            assert_eq!(term.definition(), other.definition());
+            assert_eq!(term.comment(), other.comment());
+        }
+    }
+
+    #[test]
+    fn ignore_foreign_nodes() {
+        let json = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All", "type": "CLASS"},
+                {"id": "http://purl.obolibrary.org/obo/UBERON_0000001", "lbl": "Foo", "type": "CLASS"},
+                {"id": "http://purl.obolibrary.org/obo/HP_0000002", "lbl": "Bar", "type": "CLASS"},
+                {"id": "http://purl.obolibrary.org/obo/hp#layperson", "type": "PROPERTY"}
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000002", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"},
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000002", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/UBERON_0000001"},
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000001", "pred": "http://purl.obolibrary.org/obo/BFO_0000050", "obj": "http://purl.obolibrary.org/obo/HP_0000002"}
+            ]
+        }]}"#;
+        let ontology = load(read_obographs_json(json.as_bytes(), Builder::new()).unwrap());
+        assert_eq!(ontology.len(), 2);
+        let term = ontology.hpo(2u32).unwrap();
+        assert_eq!(term.parent_ids().len(), 1);
+        assert!(ontology.hpo(1u32).unwrap().parent_ids().is_empty());
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(read_obographs_json("{\"graphs\": [".as_bytes(), Builder::new()).is_err());
+        let missing_parent = r#"{"graphs": [{
+            "nodes": [{"id": "http://purl.obolibrary.org/obo/HP_0000002", "lbl": "Bar", "type": "CLASS"}],
+            "edges": [{"sub": "http://purl.obolibrary.org/obo/HP_0000002", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"}]
+        }]}"#;
+        assert!(read_obographs_json(missing_parent.as_bytes(), Builder::new()).is_err());
+    }
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(
+            version_from_iri("http://purl.obolibrary.org/obo/hp/releases/2023-04-05/hp.json"),
+            Some((2023, 4, 5))
+        );
+        assert_eq!(
+            version_from_iri("http://purl.obolibrary.org/obo/hp.json"),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_non_ascii_version() {
+        assert_eq!(
+            version_from_iri("http://purl.obolibrary.org/obo/hp/releases/2024é-01-01/hp.json"),
+            None
+        );
+        assert_eq!(
+            version_from_iri("http://purl.obolibrary.org/obo/hp/releases/2024-01-é1/hp.json"),
+            None
+        );
+    }
+}
@@ -450,6 +450,66 @@ impl<'a> HpoSet<'a> {
         self.group = group;
     }
 
+    /// Returns all obsolete terms of the set that can't be replaced automatically
+    ///
+    /// Obsolete terms without a `replaced_by` attribute are neither replaced by
+    /// [`HpoSet::replace_obsolete`] nor by [`HpoSet::with_replaced_obsolete`]. They must
+    /// be curated manually, e.g. by choosing one of the terms that `hp.obo` suggests
+    /// to `consider` instead. Each [`ObsoleteTerm`] contains the obsolete term and
+    /// those candidates.
+    ///
+    /// # Panics
+    ///
+    /// When an `HpoTermId` of the set is not part of the Ontology
+    ///
+    /// # Examples
+    ///
+    /// The example Ontology does not contain obsolete terms,
+    /// so this example does not show an actual effect.
+    ///
+    /// ```
+    /// use hpo::{Ontology, HpoSet};
+    /// use hpo::term::HpoGroup;
+    /// # fn method_that_returns_an_hposet<'a>(ontology: &'a Ontology) -> HpoSet<'a> {
+    /// # let mut hpos = HpoGroup::new();
+    /// # hpos.insert(707u32);
+    /// # hpos.insert(12639u32);
+    /// # hpos.insert(12638u32);
+    /// # hpos.insert(818u32);
+    /// # hpos.insert(2715u32);
+    /// # HpoSet::new(ontology, hpos)
+    /// # }
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// let set: HpoSet = method_that_returns_an_hposet(&ontology);
+    ///
+    /// for obsolete in set.obsolete_terms_to_curate() {
+    ///     println!("{} is obsolete. Consider instead:", obsolete.term().id());
+    ///     for candidate in obsolete.candidates() {
+    ///         println!("{} | {}", candidate.id(), candidate.name());
+    ///     }
+    /// }
+    /// assert!(set.obsolete_terms_to_curate().is_empty());
+    /// ```
+    pub fn obsolete_terms_to_curate(&self) -> Vec<ObsoleteTerm<'a>> {
+        self.group
+            .iter()
+            .map(|term_id| {
+                HpoTerm::try_new(self.ontology, term_id).expect("HpoTermId must be in Ontology")
+            })
+            .filter(|term| term.is_obsolete() && term.replacement_id().is_none())
+            .map(|term| ObsoleteTerm {
+                term,
+                candidates: term
+                    .consider()
+                    .iter()
+                    .filter_map(|term_id| self.ontology.hpo(*term_id))
+                    .collect(),
+            })
+            .collect()
+    }
+
     /// Returns all [`crate::annotations::GeneId`]s that are associated to the set
     ///
     /// # Panics
@@ -739,6 +799,131 @@ impl<'a> HpoSet<'a> {
     pub fn contains(&self, id: &HpoTermId) -> bool {
         self.group.contains(id)
     }
+
+    /// Returns an owned [`HpoSetSnapshot`] of the set
+    ///
+    /// The snapshot does not reference the [`Ontology`] and can be
+    /// stored or serialized (with the `serde` feature)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::{Ontology, HpoSet};
+    /// use hpo::term::HpoGroup;
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// let mut hpos = HpoGroup::new();
+    /// hpos.insert(707u32);
+    /// hpos.insert(12639u32);
+    /// let set = HpoSet::new(&ontology, hpos);
+    ///
+    /// let snapshot = set.snapshot();
+    /// assert_eq!(snapshot.terms().len(), 2);
+    /// assert_eq!(snapshot.terms()[0].name(), "Abnormality of the nervous system");
+    ///
+    /// let set2 = snapshot.to_set(&ontology);
+    /// assert_eq!(set2.len(), 2);
+    /// ```
+    pub fn snapshot(&self) -> HpoSetSnapshot {
+        HpoSetSnapshot {
+            terms: self
+                .iter()
+                .map(|term| TermSnapshot {
+                    id: term.id(),
+                    name: term.name().to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An obsolete term of an [`HpoSet`] that requires manual curation
+///
+/// See [`HpoSet::obsolete_terms_to_curate`]
+#[derive(Debug, Clone)]
+pub struct ObsoleteTerm<'a> {
+    term: HpoTerm<'a>,
+    candidates: Vec<HpoTerm<'a>>,
+}
+
+impl<'a> ObsoleteTerm<'a> {
+    /// The obsolete term
+    pub fn term(&self) -> HpoTerm<'a> {
+        self.term
+    }
+
+    /// The terms to consider instead of the obsolete term
+    ///
+    /// Candidates that are not part of the Ontology are omitted.
+    /// This can be empty, if `hp.obo` does not suggest any term.
+    pub fn candidates(&self) -> &[HpoTerm<'a>] {
+        &self.candidates
+    }
+}
+
+/// An owned snapshot of an [`HpoSet`]
+///
+/// The snapshot contains the ID and name of every term of the set,
+/// independent of the [`Ontology`]. With the `serde` feature, it
+/// implements `Serialize` and `Deserialize`.
+///
+/// See [`HpoSet::snapshot`]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HpoSetSnapshot {
+    terms: Vec<TermSnapshot>,
+}
+
+impl HpoSetSnapshot {
+    /// The terms of the snapshot, sorted by [`HpoTermId`]
+    pub fn terms(&self) -> &[TermSnapshot] {
+        &self.terms
+    }
+
+    /// Returns an [`HpoGroup`] of all [`HpoTermId`]s of the snapshot
+    pub fn ids(&self) -> HpoGroup {
+        self.terms.iter().map(|term| term.id).collect()
+    }
+
+    /// Creates an [`HpoSet`] of the snapshot's terms from the [`Ontology`]
+    ///
+    /// Terms that are not part of the `Ontology` are omitted
+    pub fn to_set<'a>(&self, ontology: &'a Ontology) -> HpoSet<'a> {
+        let group = self
+            .terms
+            .iter()
+            .map(|term| term.id)
+            .filter(|id| ontology.hpo(*id).is_some())
+            .collect();
+        HpoSet::new(ontology, group)
+    }
+}
+
+impl From<&HpoSet<'_>> for HpoSetSnapshot {
+    fn from(set: &HpoSet<'_>) -> Self {
+        set.snapshot()
+    }
+}
+
+/// The ID and name of a single term of an [`HpoSetSnapshot`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TermSnapshot {
+    id: HpoTermId,
+    name: String,
+}
+
+impl TermSnapshot {
+    /// The [`HpoTermId`] of the term
+    pub fn id(&self) -> HpoTermId {
+        self.id
+    }
+
+    /// The name of the term
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 impl<'a> IntoIterator for &'a HpoSet<'a> {
@@ -765,7 +950,7 @@ mod test {
     use crate::term::internal::HpoTermInternal;
     use crate::term::HpoGroup;
     use crate::term::InformationContentKind;
-    use crate::{HpoSet, Ontology};
+    use crate::{HpoSet, HpoTermId, Ontology};
 
     fn builder_from_ontology(ont: &Ontology) -> Builder<LooseCollection> {
         let mut builder = Builder::new();
@@ -850,6 +1035,42 @@ mod test {
         assert_eq!(set.len(), 4);
     }
 
+    #[test]
+    fn test_obsolete_terms_to_curate() {
+        let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+        let mut builder = builder_from_ontology(&ontology);
+
+        let mut obsolete_term = HpoTermInternal::new("Obsolete: Foo".to_string(), 666u32.into());
+        *obsolete_term.obsolete_mut() = true;
+        obsolete_term.consider_mut().push(25454u32.into());
+        obsolete_term.consider_mut().push(66_666u32.into());
+        builder.add_term(obsolete_term);
+
+        let mut replaced_term = HpoTermInternal::new("Obsolete: Bar".to_string(), 667u32.into());
+        *replaced_term.obsolete_mut() = true;
+        *replaced_term.replacement_mut() = Some(25454u32.into());
+        builder.add_term(replaced_term);
+
+        let builder = connect_terms(&ontology, builder.terms_complete());
+        let ontology = builder
+            .calculate_information_content()
+            .expect("Able to calculate IC in tests")
+            .build_with_defaults()
+            .expect("Able to build Ontology in tests");
+
+        let mut hpos = HpoGroup::new();
+        hpos.insert(707u32);
+        hpos.insert(666u32);
+        hpos.insert(667u32);
+        let set = HpoSet::new(&ontology, hpos);
+
+        let obsolete = set.obsolete_terms_to_curate();
+        assert_eq!(obsolete.len(), 1);
+        assert_eq!(obsolete[0].term().id(), HpoTermId::from(666u32));
+        assert_eq!(obsolete[0].candidates().len(), 1);
+        assert_eq!(obsolete[0].candidates()[0].id(), HpoTermId::from(25454u32));
+    }
+
     #[test]
     fn test_with_replaced_obsolete() {
         let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
@@ -916,4 +1137,24 @@ mod test {
         assert!(!set.contains(&666u32.into()));
         assert!(set.contains(&25454u32.into()));
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn snapshot_serde_roundtrip() {
+        let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+        let set = HpoSet::new(&ontology, HpoGroup::from(vec![12639u32, 707]));
+
+        let json = serde_json::to_string(&set.snapshot()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"terms":[{"id":"HP:0000707","name":"Abnormality of the nervous system"},{"id":"HP:0012639","name":"Abnormal nervous system morphology"}]}"#
+        );
+
+        let snapshot: crate::HpoSetSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(snapshot, set.snapshot());
+        let set2 = snapshot.to_set(&ontology);
+        assert_eq!(set2.len(), 2);
+        assert!(set2.contains(&707u32.into()));
+        assert!(set2.contains(&12639u32.into()));
+    }
 }
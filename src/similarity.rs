@@ -200,9 +200,10 @@ impl<T: Similarity> Similarity for CachedSimilarity<T> {
 
 /// Default implementations for combining similarity scores
 /// of 2 [`HpoSet`]s
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum StandardCombiner {
     /// funSimAvg algorithm from [Schlicker A, et. al., BMC Bioinf (2006)](https://pubmed.ncbi.nlm.nih.gov/16776819/)
+    #[default]
     FunSimAvg,
     /// funSimMax algorithm from [Schlicker A, et. al., BMC Bioinf (2006)](https://pubmed.ncbi.nlm.nih.gov/16776819/)
     FunSimMax,
@@ -210,12 +211,6 @@ pub enum StandardCombiner {
     Bma,
 }
 
-impl Default for StandardCombiner {
-    fn default() -> Self {
-        Self::FunSimAvg
-    }
-}
-
 impl TryFrom<&str> for StandardCombiner {
     type Error = HpoError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
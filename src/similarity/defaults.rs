@@ -358,6 +358,13 @@ impl Mutation {
         Self::disease_similarity(diseases_a, diseases_b)
     }
 
+    fn decipher_disease_similarity(a: &HpoTerm, b: &HpoTerm) -> f32 {
+        let diseases_a = a.decipher_disease_ids();
+        let diseases_b = b.decipher_disease_ids();
+
+        Self::disease_similarity(diseases_a, diseases_b)
+    }
+
     fn disease_similarity<T: Eq + Hash + Clone>(
         disease_a: &HashSet<T>,
         disease_b: &HashSet<T>,
@@ -382,6 +389,7 @@ impl Similarity for Mutation {
             InformationContentKind::Gene => Mutation::gene_similarity(a, b),
             InformationContentKind::Omim => Mutation::omim_disease_similarity(a, b),
             InformationContentKind::Orpha => Mutation::orpha_disease_similarity(a, b),
+            InformationContentKind::Decipher => Mutation::decipher_disease_similarity(a, b),
             InformationContentKind::Custom => 0.0,
         }
     }
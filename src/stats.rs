@@ -28,6 +28,7 @@ pub use linkage::Linkage;
 /// [`hypergeom::gene_enrichment`], [`hypergeom::omim_disease_enrichment`] and [`hypergeom::orpha_disease_enrichment`].
 ///
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Enrichment<T> {
     annotation: T,
     pvalue: f64,
@@ -341,4 +342,21 @@ mod test {
         }
         assert!(iter.next().is_none());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn enrichment_serde_roundtrip() {
+        let enrichment = Enrichment::gene(GeneId::from(5u32), 0.01, 3, 2.5);
+        let json = serde_json::to_string(&enrichment).unwrap();
+        assert_eq!(
+            json,
+            r#"{"annotation":5,"pvalue":0.01,"count":3,"enrichment":2.5}"#
+        );
+
+        let enrichment2: Enrichment<GeneId> = serde_json::from_str(&json).unwrap();
+        assert_eq!(enrichment2.id(), enrichment.id());
+        assert_eq!(enrichment2.count(), 3);
+        assert!((enrichment2.pvalue() - 0.01).abs() < f64::EPSILON);
+        assert!((enrichment2.enrichment() - 2.5).abs() < f64::EPSILON);
+    }
 }
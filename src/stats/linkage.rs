@@ -358,7 +358,7 @@ impl<'a> Linkage<'a> {
         let similarities = func(Combinations::new(&self.sets));
 
         let index: Vec<Option<usize>> = (0..self.sets.len()).map(Some).collect();
-        for ((idx1, idx2), sim) in Combinations::new(&index).zip(similarities.into_iter()) {
+        for ((idx1, idx2), sim) in Combinations::new(&index).zip(similarities) {
             self.distance_matrix.insert((*idx1, *idx2), sim);
         }
     }
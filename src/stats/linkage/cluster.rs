@@ -8,6 +8,7 @@
 /// by the [`Linkage::into_cluster`](`crate::stats::Linkage::into_cluster`) and
 /// [`Linkage::cluster`](`crate::stats::Linkage::cluster`) iterators
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cluster {
     idx1: usize,
     idx2: usize,
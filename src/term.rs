@@ -15,16 +15,24 @@
 use crate::Ontology;
 use core::fmt::Debug;
 
+mod definition;
 pub mod group;
 mod hpoterm;
 mod hpotermid;
 mod information_content;
 pub(crate) mod internal;
+mod resolved;
+mod synonym;
+mod xref;
 
+pub use definition::Definition;
 pub use group::HpoGroup;
 pub use hpoterm::HpoTerm;
 pub use hpotermid::HpoTermId;
 pub use information_content::{InformationContent, InformationContentKind};
+pub use resolved::ResolvedTerm;
+pub use synonym::{Synonym, SynonymScope, SynonymType};
+pub use xref::Xref;
 
 /// [`HpoTerm`] iterator
 pub struct Iter<'a> {
@@ -0,0 +1,50 @@
+use std::fmt::Display;
+
+/// The textual definition of an [`HpoTerm`](`crate::HpoTerm`)
+///
+/// The definition contains a description of the term and the
+/// references that support it, e.g. `PMID:19125428` or URLs.
+///
+/// # Examples
+///
+/// ```
+/// use hpo::term::Definition;
+///
+/// let definition = Definition::new(
+///     "Dryness of the mouth due to salivary gland dysfunction.",
+///     vec![String::from("HPO:probinson")],
+/// );
+/// assert_eq!(definition.text(), "Dryness of the mouth due to salivary gland dysfunction.");
+/// assert_eq!(definition.references(), ["HPO:probinson"]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Definition {
+    text: String,
+    references: Vec<String>,
+}
+
+impl Definition {
+    /// Constructs a new [`Definition`]
+    pub fn new(text: &str, references: Vec<String>) -> Self {
+        Self {
+            text: text.to_string(),
+            references,
+        }
+    }
+
+    /// The definition text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The references (`PMID`, URLs, ORCIDs etc) supporting the definition
+    pub fn references(&self) -> &[String] {
+        &self.references
+    }
+}
+
+impl Display for Definition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
@@ -175,6 +175,24 @@ impl<'a> IntoIterator for &'a HpoGroup {
     }
 }
 
+/// Serializes the `HpoGroup` as a sequence of [`HpoTermId`]s
+#[cfg(feature = "serde")]
+impl serde::Serialize for HpoGroup {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+/// Deserializes the `HpoGroup` from a sequence of [`HpoTermId`]s
+///
+/// The sequence does not have to be sorted or unique
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for HpoGroup {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <Vec<HpoTermId> as serde::Deserialize>::deserialize(deserializer).map(HpoGroup::from)
+    }
+}
+
 impl BitOr for &HpoGroup {
     type Output = HpoGroup;
 
@@ -505,4 +523,21 @@ mod tests {
         let expected: Vec<HpoTermId> = vec![2u32.into(), 7u32.into()];
         assert_eq!(result.ids.to_vec(), expected);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let group = HpoGroup::from(vec![3u32, 1, 2]);
+        let json = serde_json::to_string(&group).unwrap();
+        assert_eq!(json, r#"["HP:0000001","HP:0000002","HP:0000003"]"#);
+
+        let group2: HpoGroup =
+            serde_json::from_str(r#"["HP:0000003","HP:0000001","HP:0000002","HP:0000001"]"#)
+                .unwrap();
+        assert_eq!(group2.len(), 3);
+        assert_eq!(
+            group2.iter().collect::<Vec<_>>(),
+            group.iter().collect::<Vec<_>>()
+        );
+    }
 }
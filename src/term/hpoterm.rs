@@ -1,3 +1,5 @@
+use crate::annotations::DecipherDiseaseIterator;
+use crate::annotations::DecipherDiseases;
 use crate::annotations::GeneIterator;
 use crate::annotations::Genes;
 use crate::annotations::OmimDiseaseIterator;
@@ -17,7 +19,10 @@ use crate::HpoResult;
 
 use super::group::Combined;
 
+use super::Definition;
 use super::InformationContent;
+use super::Synonym;
+use super::Xref;
 
 /// The `HpoTerm` represents a single term from the HP Ontology
 ///
@@ -36,6 +41,9 @@ pub struct HpoTerm<'a> {
     information_content: &'a InformationContent,
     obsolete: bool,
     replaced_by: Option<HpoTermId>,
+    // Less frequently used details, e.g. synonyms or xrefs, are
+    // accessed via the internal term to keep `HpoTerm` small
+    internal: &'a HpoTermInternal,
     ontology: &'a Ontology,
 }
 
@@ -87,6 +95,7 @@ impl<'a> HpoTerm<'a> {
             information_content: term.information_content(),
             obsolete: term.obsolete(),
             replaced_by: term.replacement(),
+            internal: term,
             ontology,
         }
     }
@@ -127,6 +136,105 @@ impl<'a> HpoTerm<'a> {
         self.name
     }
 
+    /// Returns all synonyms of the term
+    ///
+    /// Synonyms are only available if the ontology was built from
+    /// an `hp.obo` file or from a binary file (version 4 or newer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::{HpoTerm, Ontology};
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// let term = ontology.hpo(118u32).unwrap();
+    /// for synonym in term.synonyms() {
+    ///     println!("{} ({})", synonym.name(), synonym.scope());
+    /// }
+    /// ```
+    pub fn synonyms(&self) -> &'a [Synonym] {
+        self.internal.synonyms()
+    }
+
+    /// Returns the [`Definition`] of the term, if available
+    ///
+    /// The definition contains the textual description and the
+    /// references (e.g. `PubMed` IDs or URLs) that support it.
+    ///
+    /// Definitions are only available if the ontology was built from
+    /// an `hp.obo` file or from a binary file (version 4 or newer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::{HpoTerm, Ontology};
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// let term = ontology.hpo(118u32).unwrap();
+    /// if let Some(definition) = term.definition() {
+    ///     println!("{}", definition.text());
+    ///     for reference in definition.references() {
+    ///         println!("{reference}");
+    ///     }
+    /// }
+    /// ```
+    pub fn definition(&self) -> Option<&'a Definition> {
+        self.internal.definition()
+    }
+
+    /// Returns the comment of the term, if available
+    ///
+    /// Comments usually contain additional information on how to use the term.
+    /// They are only available if the ontology was built from
+    /// an `hp.obo` file or from a binary file (version 4 or newer).
+    pub fn comment(&self) -> Option<&'a str> {
+        self.internal.comment()
+    }
+
+    /// Returns the alternative [`HpoTermId`]s of the term
+    ///
+    /// Alternative IDs are the IDs of terms that were merged into this term.
+    /// Use [`Ontology::resolve`] to look up a term by its alternative ID.
+    /// They are only available if the ontology was built from
+    /// an `hp.obo` file or from a binary file (version 4 or newer).
+    pub fn alt_ids(&self) -> &'a [HpoTermId] {
+        self.internal.alt_ids()
+    }
+
+    /// Returns the cross-references of the term to other ontologies
+    ///
+    /// Cross-references map the term e.g. to `UMLS` CUIs, `SNOMED CT` or `MeSH`.
+    /// Use [`Ontology::hpo_by_xref`] for the reverse lookup.
+    /// They are only available if the ontology was built from
+    /// an `hp.obo` file or from a binary file (version 4 or newer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::{HpoTerm, Ontology};
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// let term = ontology.hpo(118u32).unwrap();
+    /// for xref in term.xrefs().iter().filter(|xref| xref.prefix() == "UMLS") {
+    ///     println!("{}", xref.id());
+    /// }
+    /// ```
+    pub fn xrefs(&self) -> &'a [Xref] {
+        self.internal.xrefs()
+    }
+
+    /// Returns the names of all subsets the term belongs to, e.g. `hposlim_core`
+    ///
+    /// Use [`Ontology::subset_terms`] to get all terms of a subset.
+    /// Subsets are only available if the ontology was built from
+    /// an `hp.obo` file or from a binary file (version 4 or newer).
+    pub fn subsets(&self) -> &'a [String] {
+        self.internal.subsets()
+    }
+
     /// Returns the [`HpoTermId`]s of the direct parents
     ///
     /// # Examples
@@ -767,6 +875,42 @@ impl<'a> HpoTerm<'a> {
         self.orpha_diseases
     }
 
+    /// Returns an iterator of all associated [`crate::annotations::DecipherDisease`]s
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::{HpoTerm, Ontology};
+    /// use hpo::annotations::Disease;
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// let term = ontology.hpo(11017u32).unwrap();
+    /// for disease in term.decipher_diseases() {
+    ///     println!("{}", disease.name());
+    /// }
+    /// ```
+    pub fn decipher_diseases(&self) -> DecipherDiseaseIterator<'a> {
+        DecipherDiseaseIterator::new(self.internal.decipher_diseases(), self.ontology)
+    }
+
+    /// Returns the set of all associated [`crate::annotations::DecipherDisease`]s
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::{HpoTerm, Ontology};
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// // The example ontology does not contain any DECIPHER diseases
+    /// let term = ontology.hpo(1939u32).unwrap();
+    /// assert!(term.decipher_disease_ids().is_empty());
+    /// ```
+    pub fn decipher_disease_ids(&self) -> &'a DecipherDiseases {
+        self.internal.decipher_diseases()
+    }
+
     /// Returns the [`InformationContent`] of the term
     ///
     /// # Examples
@@ -1045,6 +1189,17 @@ impl<'a> HpoTerm<'a> {
         self.replaced_by
     }
 
+    /// Returns the [`HpoTermId`]s of terms to consider instead of an obsolete term
+    ///
+    /// Many obsolete terms don't have a single replacement, but one or
+    /// several `consider` candidates that must be curated manually.
+    ///
+    /// See [`HpoSet::obsolete_terms_to_curate`](`crate::HpoSet::obsolete_terms_to_curate`)
+    /// for an overview of all such terms of a set
+    pub fn consider(&self) -> &'a [HpoTermId] {
+        self.internal.consider()
+    }
+
     /// Returns `true` if the term is a descendent of a modifier root term
     ///
     /// # Examples
@@ -140,3 +140,54 @@ impl PartialEq<&str> for HpoTermId {
         self == &HpoTermId::new(other)
     }
 }
+
+/// Serializes the `HpoTermId` as `str`, e.g. `"HP:0000118"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for HpoTermId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes the `HpoTermId` from a `str`, e.g. `"HP:0000118"`
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for HpoTermId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IdVisitor;
+
+        impl serde::de::Visitor<'_> for IdVisitor {
+            type Value = HpoTermId;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an HPO term ID, e.g. \"HP:0000118\"")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<HpoTermId, E> {
+                match s.strip_prefix("HP:") {
+                    Some(id) => id.parse::<u32>().map(HpoTermId::from).map_err(E::custom),
+                    None => Err(E::invalid_value(serde::de::Unexpected::Str(s), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(IdVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        use super::HpoTermId;
+
+        let id = HpoTermId::from(118u32);
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, "\"HP:0000118\"");
+        assert_eq!(serde_json::from_str::<HpoTermId>(&json).unwrap(), id);
+
+        assert!(serde_json::from_str::<HpoTermId>("\"0000118\"").is_err());
+        assert!(serde_json::from_str::<HpoTermId>("\"HP:foo\"").is_err());
+        assert!(serde_json::from_str::<HpoTermId>("118").is_err());
+    }
+}
@@ -10,10 +10,12 @@ use crate::{f32_from_usize, HpoResult};
 /// Proceedings of the 14th International Joint Conference on Artificial Intelligence:
 /// August 20-25. 1995, Morgan Kaufmann, San Francisco CA: Montreal, Canada
 #[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InformationContent {
     gene: f32,
     omim: f32,
     orpha: f32,
+    decipher: f32,
     custom: f32,
 }
 
@@ -48,12 +50,23 @@ impl InformationContent {
         &mut self.orpha
     }
 
+    /// The DECIPHER-disease-specific information content
+    pub fn decipher_disease(&self) -> f32 {
+        self.decipher
+    }
+
+    /// A mutable reference to the DECIPHER-disease-specific information content
+    pub fn decipher_disease_mut(&mut self) -> &mut f32 {
+        &mut self.decipher
+    }
+
     /// Returns the information content of the provided kind
     pub fn get_kind(&self, kind: &InformationContentKind) -> f32 {
         match kind {
             InformationContentKind::Gene => self.gene(),
             InformationContentKind::Omim => self.omim_disease(),
             InformationContentKind::Orpha => self.orpha_disease(),
+            InformationContentKind::Decipher => self.decipher_disease(),
             InformationContentKind::Custom => self.custom(),
         }
     }
@@ -101,6 +114,17 @@ impl InformationContent {
         Ok(())
     }
 
+    /// Calculates and caches the DECIPHER `InformationContent`
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if there are more DECIPHER diseases than `u16::MAX`
+    /// because larger numbers can't be safely converted to `f32`
+    pub fn set_decipher_disease(&mut self, total: usize, current: usize) -> HpoResult<()> {
+        self.decipher = Self::calculate(total, current)?;
+        Ok(())
+    }
+
     /// A custom Information content that can be provided by a client
     /// based on their own method
     pub fn custom(&self) -> f32 {
@@ -111,10 +135,52 @@ impl InformationContent {
     pub fn custom_mut(&mut self) -> &mut f32 {
         &mut self.custom
     }
+
+    /// Returns a binary representation of the `InformationContent`
+    ///
+    /// The binary layout is defined as:
+    ///
+    /// | Byte offset | Number of bytes | Description |
+    /// | --- | --- | --- |
+    /// | 0 | 4 | Gene information content as big-endian `f32` |
+    /// | 4 | 4 | OMIM information content as big-endian `f32` |
+    /// | 8 | 4 | ORPHA information content as big-endian `f32` |
+    /// | 12 | 4 | DECIPHER information content as big-endian `f32` |
+    /// | 16 | 4 | Custom information content as big-endian `f32` |
+    pub(crate) fn as_bytes(&self) -> [u8; 20] {
+        let mut res = [0u8; 20];
+        for (chunk, value) in res.chunks_exact_mut(4).zip([
+            self.gene,
+            self.omim,
+            self.orpha,
+            self.decipher,
+            self.custom,
+        ]) {
+            chunk.copy_from_slice(&value.to_be_bytes());
+        }
+        res
+    }
+
+    /// Creates an `InformationContent` from its binary representation
+    ///
+    /// See [`InformationContent::as_bytes`] for the binary layout
+    pub(crate) fn from_bytes(bytes: &[u8; 20]) -> Self {
+        let value = |idx: usize| {
+            f32::from_be_bytes([bytes[idx], bytes[idx + 1], bytes[idx + 2], bytes[idx + 3]])
+        };
+        Self {
+            gene: value(0),
+            omim: value(4),
+            orpha: value(8),
+            decipher: value(12),
+            custom: value(16),
+        }
+    }
 }
 
 /// Different types of information contents
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InformationContentKind {
     /// Information content related to the associated genes
     Gene,
@@ -122,6 +188,8 @@ pub enum InformationContentKind {
     Omim,
     /// Information content related to the associated ORPHA-diseases
     Orpha,
+    /// Information content related to the associated DECIPHER-diseases
+    Decipher,
     /// This information content can be custom defined by clients
     Custom,
 }
@@ -1,15 +1,16 @@
 use crate::annotations::OrphaDiseases;
 use crate::annotations::{AnnotationId, OrphaDiseaseId};
-use crate::parser::binary::term::{from_bytes_v1, from_bytes_v2};
+use crate::annotations::{DecipherDiseaseId, DecipherDiseases};
+use crate::parser::binary::term::{from_bytes_v1, from_bytes_v2, from_bytes_v4};
 use crate::parser::binary::{BinaryVersion, Bytes};
 use std::hash::Hash;
 
 use crate::annotations::{GeneId, Genes};
 use crate::annotations::{OmimDiseaseId, OmimDiseases};
-use crate::term::{HpoGroup, HpoTermId, InformationContent};
+use crate::term::{Definition, HpoGroup, HpoTermId, InformationContent, Synonym, Xref};
+use crate::DEFAULT_NUM_ALL_PARENTS;
 use crate::DEFAULT_NUM_PARENTS;
 use crate::{HpoError, DEFAULT_NUM_GENES};
-use crate::{HpoResult, DEFAULT_NUM_ALL_PARENTS};
 use crate::{HpoTerm, DEFAULT_NUM_OMIM, DEFAULT_NUM_ORPHA};
 
 #[derive(Clone, Debug)]
@@ -22,9 +23,17 @@ pub(crate) struct HpoTermInternal {
     genes: Genes,
     omim_diseases: OmimDiseases,
     orpha_diseases: OrphaDiseases,
+    decipher_diseases: DecipherDiseases,
     ic: InformationContent,
     obsolete: bool,
     replacement: Option<HpoTermId>,
+    synonyms: Vec<Synonym>,
+    definition: Option<Definition>,
+    comment: Option<String>,
+    alt_ids: Vec<HpoTermId>,
+    xrefs: Vec<Xref>,
+    consider: Vec<HpoTermId>,
+    subsets: Vec<String>,
 }
 
 impl Hash for HpoTermInternal {
@@ -50,17 +59,20 @@ impl HpoTermInternal {
             genes: Genes::with_capacity(DEFAULT_NUM_GENES),
             omim_diseases: OmimDiseases::with_capacity(DEFAULT_NUM_OMIM),
             orpha_diseases: OrphaDiseases::with_capacity(DEFAULT_NUM_ORPHA),
+            decipher_diseases: DecipherDiseases::default(),
             ic: InformationContent::default(),
             obsolete: false,
             replacement: None,
+            synonyms: Vec::new(),
+            definition: None,
+            comment: None,
+            alt_ids: Vec::new(),
+            xrefs: Vec::new(),
+            consider: Vec::new(),
+            subsets: Vec::new(),
         }
     }
 
-    pub fn try_new(id: &str, name: &str) -> HpoResult<HpoTermInternal> {
-        let id = HpoTermId::try_from(id)?;
-        Ok(Self::new(name.to_string(), id))
-    }
-
     pub fn id(&self) -> &HpoTermId {
         &self.id
     }
@@ -97,6 +109,10 @@ impl HpoTermInternal {
         &self.orpha_diseases
     }
 
+    pub fn decipher_diseases(&self) -> &DecipherDiseases {
+        &self.decipher_diseases
+    }
+
     pub fn parents_cached(&self) -> bool {
         if self.parents.is_empty() {
             true
@@ -125,6 +141,10 @@ impl HpoTermInternal {
         self.orpha_diseases.insert(orpha_disease_id)
     }
 
+    pub fn add_decipher_disease(&mut self, decipher_disease_id: DecipherDiseaseId) -> bool {
+        self.decipher_diseases.insert(decipher_disease_id)
+    }
+
     pub fn information_content(&self) -> &InformationContent {
         &self.ic
     }
@@ -149,6 +169,62 @@ impl HpoTermInternal {
         &mut self.replacement
     }
 
+    pub fn synonyms(&self) -> &[Synonym] {
+        &self.synonyms
+    }
+
+    pub fn synonyms_mut(&mut self) -> &mut Vec<Synonym> {
+        &mut self.synonyms
+    }
+
+    pub fn definition(&self) -> Option<&Definition> {
+        self.definition.as_ref()
+    }
+
+    pub fn definition_mut(&mut self) -> &mut Option<Definition> {
+        &mut self.definition
+    }
+
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    pub fn comment_mut(&mut self) -> &mut Option<String> {
+        &mut self.comment
+    }
+
+    pub fn alt_ids(&self) -> &[HpoTermId] {
+        &self.alt_ids
+    }
+
+    pub fn alt_ids_mut(&mut self) -> &mut Vec<HpoTermId> {
+        &mut self.alt_ids
+    }
+
+    pub fn xrefs(&self) -> &[Xref] {
+        &self.xrefs
+    }
+
+    pub fn xrefs_mut(&mut self) -> &mut Vec<Xref> {
+        &mut self.xrefs
+    }
+
+    pub fn consider(&self) -> &[HpoTermId] {
+        &self.consider
+    }
+
+    pub fn consider_mut(&mut self) -> &mut Vec<HpoTermId> {
+        &mut self.consider
+    }
+
+    pub fn subsets(&self) -> &[String] {
+        &self.subsets
+    }
+
+    pub fn subsets_mut(&mut self) -> &mut Vec<String> {
+        &mut self.subsets
+    }
+
     /// Returns a binary representation of the `HpoTermInternal`
     ///
     /// The binary layout is defined as:
@@ -161,6 +237,22 @@ impl HpoTermInternal {
     /// | 9 | n | The Term name as u8 vector. If the name has more than 255 bytes, it is trimmed to 255 |
     /// | 9 + n | 1 | Flag to indicate if term is obsolete
     /// | 10 + n | 4 | Term ID of a replacement term as big-endian `u32` or `0` if `None` |
+    /// | 14 + n | x | Term details (see below) |
+    ///
+    /// Strings in the term details are encoded with their length as
+    /// big-endian `u32`, followed by the UTF-8 bytes. Lists are encoded with
+    /// the number of items as big-endian `u32`, followed by the items.
+    /// The details are, in this order:
+    ///
+    /// | Content | Description |
+    /// | --- | --- |
+    /// | List of synonyms | Each synonym: [`SynonymScope`](`crate::term::SynonymScope`) as `u8`, synonym type (string, empty if `None`), name (string) |
+    /// | Definition | Text (string), references (list of strings). An empty text and no references means `None` |
+    /// | Comment | String, empty if `None` |
+    /// | List of alternative IDs | Each ID as big-endian `u32` |
+    /// | List of cross-references | Each [`Xref`] as string, e.g. `UMLS:C0043352` |
+    /// | List of `consider` terms | Each ID as big-endian `u32` |
+    /// | List of subsets | Each subset name as string |
     ///
     /// # Panics
     ///
@@ -172,9 +264,11 @@ impl HpoTermInternal {
         // 1 byte for obsolete flag
         // 4 byte for replacement term
         // name in u8 encoded
+        // term details
         let name = self.name().as_bytes();
         let name_length = std::cmp::min(name.len(), 255);
-        let size = name_length + 4 + 4 + 1 + 1 + 4;
+        let details = self.details_as_bytes();
+        let size = name_length + 4 + 4 + 1 + 1 + 4 + details.len();
 
         let mut res = Vec::with_capacity(size);
 
@@ -210,6 +304,62 @@ impl HpoTermInternal {
                 .to_vec(),
         );
 
+        // Synonyms, definition etc
+        res.extend_from_slice(&details);
+
+        res
+    }
+
+    /// Returns the binary representation of the term details
+    ///
+    /// See [`HpoTermInternal::as_bytes`] for the binary layout
+    fn details_as_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::new();
+
+        // Synonyms
+        res.extend_from_slice(&usize_to_u32(self.synonyms.len()).to_be_bytes());
+        for synonym in &self.synonyms {
+            res.push(synonym.scope().into());
+            push_string(
+                &mut res,
+                synonym.synonym_type().map_or("", |kind| kind.as_str()),
+            );
+            push_string(&mut res, synonym.name());
+        }
+
+        // Definition
+        if let Some(definition) = &self.definition {
+            push_string(&mut res, definition.text());
+            push_strings(&mut res, definition.references());
+        } else {
+            push_string(&mut res, "");
+            push_strings(&mut res, &[]);
+        }
+
+        // Comment
+        push_string(&mut res, self.comment.as_deref().unwrap_or_default());
+
+        // Alternative IDs
+        res.extend_from_slice(&usize_to_u32(self.alt_ids.len()).to_be_bytes());
+        for alt_id in &self.alt_ids {
+            res.extend_from_slice(&alt_id.to_be_bytes());
+        }
+
+        // Cross-references
+        res.extend_from_slice(&usize_to_u32(self.xrefs.len()).to_be_bytes());
+        for xref in &self.xrefs {
+            push_string(&mut res, &xref.to_string());
+        }
+
+        // Terms to consider instead of an obsolete term
+        res.extend_from_slice(&usize_to_u32(self.consider.len()).to_be_bytes());
+        for term_id in &self.consider {
+            res.extend_from_slice(&term_id.to_be_bytes());
+        }
+
+        // Subsets
+        push_strings(&mut res, &self.subsets);
+
         res
     }
 
@@ -236,6 +386,27 @@ impl HpoTermInternal {
         }
         term_parents
     }
+
+    /// Returns a binary representation of all ancestors of the term
+    ///
+    /// The binary layout is defined as:
+    ///
+    /// | Byte offset | Number of bytes | Description |
+    /// | --- | --- | --- |
+    /// | 0 | 4 | The number of ancestors as big-endian `u32` |
+    /// | 4 | 4 * n | The Term ID of all ancestors as big-endian `u32`, sorted ascending |
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if there are more than `u32::MAX` ancestors
+    pub fn ancestors_as_byte(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(4 + 4 * self.all_parents().len());
+        res.extend_from_slice(&usize_to_u32(self.all_parents().len()).to_be_bytes());
+        for ancestor in self.all_parents() {
+            res.extend_from_slice(&ancestor.to_be_bytes());
+        }
+        res
+    }
 }
 
 impl PartialEq for HpoTermInternal {
@@ -254,7 +425,8 @@ impl TryFrom<Bytes<'_>> for HpoTermInternal {
     fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
         match bytes.version() {
             BinaryVersion::V1 => from_bytes_v1(bytes),
-            _ => from_bytes_v2(bytes),
+            BinaryVersion::V2 | BinaryVersion::V3 => from_bytes_v2(bytes),
+            BinaryVersion::V4 => from_bytes_v4(bytes),
         }
     }
 }
@@ -264,14 +436,40 @@ impl From<&HpoTerm<'_>> for HpoTermInternal {
         let mut internal = Self::new(term.name().to_string(), term.id());
         *internal.obsolete_mut() = term.is_obsolete();
         *internal.replacement_mut() = term.replaced_by().map(|repl| repl.id());
+        *internal.synonyms_mut() = term.synonyms().to_vec();
+        *internal.definition_mut() = term.definition().cloned();
+        *internal.comment_mut() = term.comment().map(str::to_string);
+        *internal.alt_ids_mut() = term.alt_ids().to_vec();
+        *internal.xrefs_mut() = term.xrefs().to_vec();
+        *internal.consider_mut() = term.consider().to_vec();
+        *internal.subsets_mut() = term.subsets().to_vec();
         internal
     }
 }
 
+fn usize_to_u32(n: usize) -> u32 {
+    n.try_into().expect("unable to convert {n} to u32")
+}
+
+/// Appends the length of the string as big-endian `u32` and the string itself
+fn push_string(res: &mut Vec<u8>, value: &str) {
+    res.extend_from_slice(&usize_to_u32(value.len()).to_be_bytes());
+    res.extend_from_slice(value.as_bytes());
+}
+
+/// Appends the number of strings as big-endian `u32` and every string
+fn push_strings(res: &mut Vec<u8>, values: &[String]) {
+    res.extend_from_slice(&usize_to_u32(values.len()).to_be_bytes());
+    for value in values {
+        push_string(res, value);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::parser::binary::BinaryTermBuilder;
+    use crate::term::{SynonymScope, SynonymType};
 
     #[test]
     fn to_bytes() {
@@ -280,7 +478,7 @@ mod test {
         let bytes: Vec<u8> = term.as_bytes();
 
         let term_len = u32::from_be_bytes(bytes[0..4].try_into().unwrap()) as usize;
-        assert_eq!(term_len, 4 + 4 + 1 + 6 + 5);
+        assert_eq!(term_len, 4 + 4 + 1 + 6 + 5 + 4 + 4 + 4 + 4 + 4 + 4 + 4 + 4);
         let term_id = u32::from_be_bytes(bytes[4..8].try_into().unwrap());
         assert_eq!(term_id, 123);
         let name_len = bytes[8] as usize;
@@ -293,11 +491,94 @@ mod test {
     fn from_bytes() {
         let term = HpoTermInternal::new(String::from("Foobar"), 123u32.into());
         let bytes: Vec<u8> = term.as_bytes();
-        let term2 = HpoTermInternal::try_from(Bytes::new(&bytes[..], BinaryVersion::V2)).unwrap();
+        let term2 = HpoTermInternal::try_from(Bytes::new(&bytes[..], BinaryVersion::V4)).unwrap();
         assert_eq!(term2.name(), term.name());
         assert_eq!(term2.id(), term.id());
     }
 
+    #[test]
+    fn synonyms_to_and_from_bytes() {
+        let mut term = HpoTermInternal::new(String::from("Foobar"), 123u32.into());
+        term.synonyms_mut().push(Synonym::new(
+            "Foo",
+            SynonymScope::Exact,
+            Some(SynonymType::Layperson),
+        ));
+        term.synonyms_mut()
+            .push(Synonym::new("Bar 😀", SynonymScope::Related, None));
+        let bytes: Vec<u8> = term.as_bytes();
+
+        let term2 = HpoTermInternal::try_from(Bytes::new(&bytes[..], BinaryVersion::V4)).unwrap();
+        assert_eq!(term2.synonyms(), term.synonyms());
+
+        // The V3 parser ignores the synonyms
+        let term3 = HpoTermInternal::try_from(Bytes::new(&bytes[..], BinaryVersion::V3)).unwrap();
+        assert_eq!(term3.name(), "Foobar");
+        assert!(term3.synonyms().is_empty());
+
+        assert!(HpoTermInternal::try_from(Bytes::new(
+            &bytes[..bytes.len() - 1],
+            BinaryVersion::V4
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn definition_and_comment_to_and_from_bytes() {
+        let mut term = HpoTermInternal::new(String::from("Foobar"), 123u32.into());
+        let bytes: Vec<u8> = term.as_bytes();
+        let term2 = HpoTermInternal::try_from(Bytes::new(&bytes[..], BinaryVersion::V4)).unwrap();
+        assert!(term2.definition().is_none());
+        assert!(term2.comment().is_none());
+
+        *term.definition_mut() = Some(Definition::new(
+            "Some\ndefinition",
+            vec![String::from("PMID:123"), String::from("HPO:probinson")],
+        ));
+        *term.comment_mut() = Some(String::from("A comment"));
+        let bytes: Vec<u8> = term.as_bytes();
+
+        let term2 = HpoTermInternal::try_from(Bytes::new(&bytes[..], BinaryVersion::V4)).unwrap();
+        assert_eq!(term2.definition(), term.definition());
+        assert_eq!(term2.comment(), Some("A comment"));
+    }
+
+    #[test]
+    fn alt_ids_and_consider_to_and_from_bytes() {
+        let mut term = HpoTermInternal::new(String::from("Foobar"), 123u32.into());
+        term.alt_ids_mut().push(12u32.into());
+        term.alt_ids_mut().push(9_999_999u32.into());
+        term.consider_mut().push(14u32.into());
+        let bytes: Vec<u8> = term.as_bytes();
+
+        let term2 = HpoTermInternal::try_from(Bytes::new(&bytes[..], BinaryVersion::V4)).unwrap();
+        assert_eq!(term2.alt_ids(), term.alt_ids());
+        assert_eq!(term2.consider(), term.consider());
+    }
+
+    #[test]
+    fn xrefs_to_and_from_bytes() {
+        let mut term = HpoTermInternal::new(String::from("Foobar"), 123u32.into());
+        term.xrefs_mut()
+            .push(Xref::try_from("UMLS:C0043352").unwrap());
+        term.xrefs_mut()
+            .push(Xref::try_from("SNOMEDCT_US:87715008").unwrap());
+        let bytes: Vec<u8> = term.as_bytes();
+
+        let term2 = HpoTermInternal::try_from(Bytes::new(&bytes[..], BinaryVersion::V4)).unwrap();
+        assert_eq!(term2.xrefs(), term.xrefs());
+    }
+
+    #[test]
+    fn subsets_to_and_from_bytes() {
+        let mut term = HpoTermInternal::new(String::from("Foobar"), 123u32.into());
+        term.subsets_mut().push(String::from("hposlim_core"));
+        let bytes: Vec<u8> = term.as_bytes();
+
+        let term2 = HpoTermInternal::try_from(Bytes::new(&bytes[..], BinaryVersion::V4)).unwrap();
+        assert_eq!(term2.subsets(), ["hposlim_core"]);
+    }
+
     #[test]
     fn from_multiple_bytes() {
         let mut v: Vec<u8> = Vec::new();
@@ -315,10 +596,10 @@ mod test {
             v.append(&mut t.as_bytes());
         }
 
-        let mut term_iter = BinaryTermBuilder::new(Bytes::new(&v, BinaryVersion::V2));
+        let mut term_iter = BinaryTermBuilder::new(Bytes::new(&v, BinaryVersion::V4));
 
         for (name, id) in test_terms {
-            let term = term_iter.next().unwrap();
+            let term = term_iter.next().unwrap().unwrap();
             println!("Checking: {term:?} [{name}-{id}]");
             assert_eq!(term.name(), name);
             assert_eq!(term.id().as_u32(), id);
@@ -326,4 +607,40 @@ mod test {
 
         assert!(term_iter.next().is_none());
     }
+
+    #[test]
+    fn from_incomplete_bytes() {
+        let first = HpoTermInternal::new(String::from("t1"), 1u32.into()).as_bytes();
+        let second = HpoTermInternal::new(String::from("t2"), 2u32.into()).as_bytes();
+
+        // The second term is missing its last byte
+        let mut v = first.clone();
+        v.extend_from_slice(&second[..second.len() - 1]);
+        let mut term_iter = BinaryTermBuilder::new(Bytes::new(&v, BinaryVersion::V4));
+        assert!(term_iter.next().unwrap().is_ok());
+        let Some(Err(HpoError::BinarySectionError {
+            expected, actual, ..
+        })) = term_iter.next()
+        else {
+            panic!("incomplete term must return a BinarySectionError");
+        };
+        assert_eq!(expected, first.len() + second.len());
+        assert_eq!(actual, first.len() + second.len() - 1);
+        assert!(term_iter.next().is_none());
+
+        // The length of the second term is incomplete
+        let mut v = first.clone();
+        v.extend_from_slice(&second[..2]);
+        let mut term_iter = BinaryTermBuilder::new(Bytes::new(&v, BinaryVersion::V4));
+        assert!(term_iter.next().unwrap().is_ok());
+        let Some(Err(HpoError::BinarySectionError {
+            expected, actual, ..
+        })) = term_iter.next()
+        else {
+            panic!("incomplete term must return a BinarySectionError");
+        };
+        assert_eq!(expected, first.len() + 4);
+        assert_eq!(actual, first.len() + 2);
+        assert!(term_iter.next().is_none());
+    }
 }
@@ -0,0 +1,77 @@
+use crate::HpoTerm;
+
+/// The result of looking up an [`HpoTermId`](`crate::HpoTermId`)
+/// via [`Ontology::resolve`](`crate::Ontology::resolve`)
+///
+/// It reports how the ID was found in the ontology, which is useful
+/// to handle legacy IDs, e.g. from old patient records.
+///
+/// # Examples
+///
+/// ```
+/// use hpo::Ontology;
+/// use hpo::term::ResolvedTerm;
+///
+/// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+///
+/// match ontology.resolve(118u32).unwrap() {
+///     ResolvedTerm::Primary(term) => println!("{} is a current term", term.id()),
+///     ResolvedTerm::Alternative(term) => println!("Use {} instead", term.id()),
+///     ResolvedTerm::Obsolete { replacement, .. } => println!("Use {} instead", replacement.id()),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum ResolvedTerm<'a> {
+    /// The ID is the primary ID of a term
+    ///
+    /// The term might still be obsolete, if it does not have a replacement
+    Primary(HpoTerm<'a>),
+    /// The ID is an alternative ID (`alt_id`) of the contained term
+    Alternative(HpoTerm<'a>),
+    /// The ID belongs to an obsolete term that was replaced by another term
+    Obsolete {
+        /// The obsolete term
+        obsolete: HpoTerm<'a>,
+        /// The term that replaces the obsolete term
+        replacement: HpoTerm<'a>,
+    },
+}
+
+impl<'a> ResolvedTerm<'a> {
+    /// Returns the term that should be used for the ID
+    ///
+    /// This is the primary term for alternative IDs and
+    /// the replacement term for obsolete terms
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hpo::Ontology;
+    ///
+    /// let ontology = Ontology::from_binary("tests/example.hpo").unwrap();
+    ///
+    /// let resolved = ontology.resolve(118u32).unwrap();
+    /// assert_eq!(resolved.term().name(), "Phenotypic abnormality");
+    /// ```
+    pub fn term(&self) -> HpoTerm<'a> {
+        match self {
+            ResolvedTerm::Primary(term) | ResolvedTerm::Alternative(term) => *term,
+            ResolvedTerm::Obsolete { replacement, .. } => *replacement,
+        }
+    }
+
+    /// Returns `true` if the ID is the primary ID of a term
+    pub fn is_primary(&self) -> bool {
+        matches!(self, ResolvedTerm::Primary(_))
+    }
+
+    /// Returns `true` if the ID is an alternative ID of a term
+    pub fn is_alternative(&self) -> bool {
+        matches!(self, ResolvedTerm::Alternative(_))
+    }
+
+    /// Returns `true` if the ID belongs to an obsolete term with a replacement
+    pub fn is_obsolete(&self) -> bool {
+        matches!(self, ResolvedTerm::Obsolete { .. })
+    }
+}
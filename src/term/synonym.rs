@@ -0,0 +1,184 @@
+use std::fmt::Display;
+
+use crate::HpoError;
+
+/// A synonym of an [`HpoTerm`](`crate::HpoTerm`)
+///
+/// Synonyms are alternative names for a term, e.g. lay terms
+/// that patients or non-specialists might use. Each synonym has
+/// a [`SynonymScope`] that describes how closely it matches the term
+/// and an optional [`SynonymType`], e.g. `layperson`.
+///
+/// # Examples
+///
+/// ```
+/// use hpo::term::{Synonym, SynonymScope, SynonymType};
+///
+/// let synonym = Synonym::new("Dry mouth", SynonymScope::Exact, Some(SynonymType::Layperson));
+/// assert_eq!(synonym.name(), "Dry mouth");
+/// assert_eq!(synonym.scope(), SynonymScope::Exact);
+/// assert!(synonym.is_layperson());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Synonym {
+    name: String,
+    scope: SynonymScope,
+    kind: Option<SynonymType>,
+}
+
+impl Synonym {
+    /// Constructs a new [`Synonym`]
+    pub fn new(name: &str, scope: SynonymScope, kind: Option<SynonymType>) -> Self {
+        Self {
+            name: name.to_string(),
+            scope,
+            kind,
+        }
+    }
+
+    /// The synonym text, e.g. `Dry mouth`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The [`SynonymScope`] of the synonym
+    pub fn scope(&self) -> SynonymScope {
+        self.scope
+    }
+
+    /// The [`SynonymType`] of the synonym, if specified
+    pub fn synonym_type(&self) -> Option<&SynonymType> {
+        self.kind.as_ref()
+    }
+
+    /// Returns `true` if the synonym is a layperson term
+    pub fn is_layperson(&self) -> bool {
+        self.kind == Some(SynonymType::Layperson)
+    }
+}
+
+impl Display for Synonym {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// The scope of a [`Synonym`], as defined by the OBO format
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SynonymScope {
+    /// The synonym has the exact same meaning as the term
+    Exact,
+    /// The synonym is broader than the term
+    Broad,
+    /// The synonym is narrower than the term
+    Narrow,
+    /// The synonym is related to the term, but not in a defined way
+    Related,
+}
+
+impl TryFrom<&str> for SynonymScope {
+    type Error = HpoError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "EXACT" => Ok(SynonymScope::Exact),
+            "BROAD" => Ok(SynonymScope::Broad),
+            "NARROW" => Ok(SynonymScope::Narrow),
+            "RELATED" => Ok(SynonymScope::Related),
+            _ => Err(HpoError::InvalidInput(format!(
+                "invalid synonym scope: {value}"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<u8> for SynonymScope {
+    type Error = HpoError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SynonymScope::Exact),
+            1 => Ok(SynonymScope::Broad),
+            2 => Ok(SynonymScope::Narrow),
+            3 => Ok(SynonymScope::Related),
+            _ => Err(HpoError::ParseBinaryError),
+        }
+    }
+}
+
+impl From<SynonymScope> for u8 {
+    fn from(value: SynonymScope) -> Self {
+        match value {
+            SynonymScope::Exact => 0,
+            SynonymScope::Broad => 1,
+            SynonymScope::Narrow => 2,
+            SynonymScope::Related => 3,
+        }
+    }
+}
+
+impl Display for SynonymScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SynonymScope::Exact => "EXACT",
+                SynonymScope::Broad => "BROAD",
+                SynonymScope::Narrow => "NARROW",
+                SynonymScope::Related => "RELATED",
+            }
+        )
+    }
+}
+
+/// The type of a [`Synonym`]
+///
+/// HPO defines a few synonym types in the header of `hp.obo`.
+/// Unknown types are kept as [`SynonymType::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SynonymType {
+    /// A term that is used by patients and non-specialists
+    Layperson,
+    /// An abbreviation of the term
+    Abbreviation,
+    /// The plural form of the term
+    PluralForm,
+    /// The UK spelling of the term
+    UkSpelling,
+    /// A synonym that should no longer be used
+    ObsoleteSynonym,
+    /// Any other synonym type
+    Other(String),
+}
+
+impl SynonymType {
+    /// Returns the identifier of the synonym type as used in `hp.obo`
+    pub fn as_str(&self) -> &str {
+        match self {
+            SynonymType::Layperson => "layperson",
+            SynonymType::Abbreviation => "abbreviation",
+            SynonymType::PluralForm => "plural_form",
+            SynonymType::UkSpelling => "uk_spelling",
+            SynonymType::ObsoleteSynonym => "obsolete_synonym",
+            SynonymType::Other(kind) => kind,
+        }
+    }
+}
+
+impl From<&str> for SynonymType {
+    fn from(value: &str) -> Self {
+        match value {
+            "layperson" => SynonymType::Layperson,
+            "abbreviation" => SynonymType::Abbreviation,
+            "plural_form" => SynonymType::PluralForm,
+            "uk_spelling" => SynonymType::UkSpelling,
+            "obsolete_synonym" => SynonymType::ObsoleteSynonym,
+            other => SynonymType::Other(other.to_string()),
+        }
+    }
+}
+
+impl Display for SynonymType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
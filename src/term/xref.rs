@@ -0,0 +1,67 @@
+use std::fmt::Display;
+
+use crate::HpoError;
+
+/// A cross-reference of an [`HpoTerm`](`crate::HpoTerm`) to another
+/// ontology or terminology, e.g. `UMLS:C0043352` or `SNOMEDCT_US:87715008`
+///
+/// # Examples
+///
+/// ```
+/// use hpo::term::Xref;
+///
+/// let xref = Xref::try_from("UMLS:C0043352").unwrap();
+/// assert_eq!(xref.prefix(), "UMLS");
+/// assert_eq!(xref.id(), "C0043352");
+/// assert_eq!(xref.to_string(), "UMLS:C0043352");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Xref {
+    prefix: String,
+    id: String,
+}
+
+impl Xref {
+    /// The prefix of the referenced ontology, e.g. `UMLS`, `MSH` or `SNOMEDCT_US`
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The ID of the term in the referenced ontology, e.g. `C0043352`
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl TryFrom<&str> for Xref {
+    type Error = HpoError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.split_once(':') {
+            Some((prefix, id)) if !prefix.is_empty() && !id.is_empty() => Ok(Self {
+                prefix: prefix.to_string(),
+                id: id.to_string(),
+            }),
+            _ => Err(HpoError::InvalidInput(format!("invalid xref: {value}"))),
+        }
+    }
+}
+
+impl PartialEq<str> for Xref {
+    fn eq(&self, other: &str) -> bool {
+        other
+            .split_once(':')
+            .is_some_and(|(prefix, id)| prefix == self.prefix && id == self.id)
+    }
+}
+
+impl PartialEq<&str> for Xref {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl Display for Xref {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.prefix, self.id)
+    }
+}